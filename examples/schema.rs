@@ -1,10 +1,11 @@
 use cosmwasm_schema::write_api;
-use cw_huahua_name::msg::{ExecuteMsg, InstantiateMsg, QueryMsg};
+use cw_huahua_name::msg::{ExecuteMsg, InstantiateMsg, QueryMsg, SudoMsg};
 
 fn main() {
     write_api! {
         instantiate: InstantiateMsg,
         query: QueryMsg,
         execute: ExecuteMsg,
+        sudo: SudoMsg,
     }
 }
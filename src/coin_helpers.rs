@@ -1,27 +1,92 @@
 use crate::error::ContractError;
 use cosmwasm_std::Coin;
 
+/// Payment is how the coins sent for a priced action are settled
+#[derive(Debug, PartialEq)]
+pub struct Payment {
+    /// the accepted price that is charged, none when the action is free
+    pub charge: Option<Coin>,
+    /// everything sent on top of the charge, one coin per denom
+    pub change: Vec<Coin>,
+}
+
+/// charge settles `sent` against the `accepted` prices. Coins of the same denom are summed,
+/// the first accepted price they cover is charged and the rest is change.
+/// Sending a denom none of the prices accepts is an error.
+/// No accepted price, or a zero one, means the action is free
+pub fn charge(sent: &[Coin], accepted: &[Coin]) -> Result<Payment, ContractError> {
+    let mut change = vec![];
+    add_coins(&mut change, sent);
+    change.retain(|c| !c.amount.is_zero());
+    if accepted.is_empty() {
+        return Ok(Payment { charge: None, change });
+    }
+
+    if let Some(foreign) = change.iter().find(|c| !accepted.iter().any(|price| price.denom == c.denom)) {
+        return Err(ContractError::UnacceptedDenom { denom: foreign.denom.clone() });
+    }
+    let charge = match charged_coin(&change, accepted) {
+        Some(charge) => charge,
+        None => return Err(ContractError::InsufficientFundsSend {}),
+    };
+    deduct_coin(&mut change, &charge);
+    Ok(Payment { charge: Some(charge).filter(|c| !c.amount.is_zero()), change })
+}
+
+/// assert_sent_sufficient_coin returns an error unless `sent` pays one of the `accepted` prices,
+/// see charge
 pub fn assert_sent_sufficient_coin(
     sent: &[Coin],
-    required: Option<Coin>,
+    accepted: &[Coin],
 ) -> Result<(), ContractError> {
-    if let Some(required_coin) = required {
-        let required_amount = required_coin.amount.u128();
-        if required_amount > 0 {
-            let sent_sufficient_funds = sent.iter().any(|coin| {
-                // check if a given sent coin matches denom
-                // and has sufficient amount
-                coin.denom == required_coin.denom && coin.amount.u128() >= required_amount
-            });
-
-            if sent_sufficient_funds {
-                return Ok(());
-            } else {
-                return Err(ContractError::InsufficientFundsSend {});
+    charge(sent, accepted).map(|_| ())
+}
+
+/// charged_coin is the first of the `accepted` prices covered by `sent`
+pub fn charged_coin(sent: &[Coin], accepted: &[Coin]) -> Option<Coin> {
+    accepted
+        .iter()
+        .find(|price| {
+            // check if a given sent coin matches denom
+            // and has sufficient amount
+            price.amount.is_zero() || sent.iter().any(|coin| coin.denom == price.denom && coin.amount >= price.amount)
+        })
+        .cloned()
+}
+
+/// add_coins merges `coins` into `total`, summing amounts of the same denom
+pub fn add_coins(total: &mut Vec<Coin>, coins: &[Coin]) {
+    for coin in coins {
+        match total.iter_mut().find(|c| c.denom == coin.denom) {
+            Some(existing) => existing.amount += coin.amount,
+            None => total.push(coin.clone()),
+        }
+    }
+}
+
+/// deduct_coin takes `amount` out of `balance`, returns false and leaves `balance`
+/// untouched when it does not hold enough of that denom
+pub fn deduct_coin(balance: &mut Vec<Coin>, amount: &Coin) -> bool {
+    match balance.iter().position(|c| c.denom == amount.denom && c.amount >= amount.amount) {
+        Some(index) => {
+            balance[index].amount -= amount.amount;
+            if balance[index].amount.is_zero() {
+                balance.remove(index);
             }
+            true
+        }
+        None => amount.amount.is_zero(),
+    }
+}
+
+/// subtract_coins takes `coins` out of `total`, stopping at zero for every denom
+pub fn subtract_coins(total: &mut Vec<Coin>, coins: &[Coin]) {
+    for coin in coins {
+        if let Some(existing) = total.iter_mut().find(|c| c.denom == coin.denom) {
+            existing.amount = existing.amount.saturating_sub(coin.amount);
         }
     }
-    Ok(())
+    total.retain(|c| !c.amount.is_zero());
 }
 
 #[cfg(test)]
@@ -31,32 +96,102 @@ mod test {
 
     #[test]
     fn assert_sent_sufficient_coin_works() {
-        match assert_sent_sufficient_coin(&[], Some(coin(0, "token"))) {
+        match assert_sent_sufficient_coin(&[], &[coin(0, "token")]) {
             Ok(()) => {}
             Err(e) => panic!("Unexpected error: {:?}", e),
         };
 
-        match assert_sent_sufficient_coin(&[], Some(coin(5, "token"))) {
+        match assert_sent_sufficient_coin(&[], &[coin(5, "token")]) {
             Ok(()) => panic!("Should have raised insufficient funds error"),
             Err(ContractError::InsufficientFundsSend {}) => {}
             Err(e) => panic!("Unexpected error: {:?}", e),
         };
 
-        match assert_sent_sufficient_coin(&coins(10, "smokin"), Some(coin(5, "token"))) {
+        match assert_sent_sufficient_coin(&coins(10, "smokin"), &[coin(5, "token")]) {
+            Ok(()) => panic!("Should have raised unaccepted denom error"),
+            Err(ContractError::UnacceptedDenom { denom }) => assert_eq!(denom, "smokin"),
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        };
+
+        match assert_sent_sufficient_coin(&coins(4, "token"), &[coin(5, "token")]) {
             Ok(()) => panic!("Should have raised insufficient funds error"),
             Err(ContractError::InsufficientFundsSend {}) => {}
             Err(e) => panic!("Unexpected error: {:?}", e),
         };
 
-        match assert_sent_sufficient_coin(&coins(10, "token"), Some(coin(5, "token"))) {
+        match assert_sent_sufficient_coin(&coins(10, "token"), &[coin(5, "token")]) {
             Ok(()) => {}
             Err(e) => panic!("Unexpected error: {:?}", e),
         };
 
         let sent_coins = vec![coin(2, "smokin"), coin(5, "token"), coin(1, "earth")];
-        match assert_sent_sufficient_coin(&sent_coins, Some(coin(5, "token"))) {
-            Ok(()) => {}
+        match assert_sent_sufficient_coin(&sent_coins, &[coin(5, "token")]) {
+            Ok(()) => panic!("Should have raised unaccepted denom error"),
+            Err(ContractError::UnacceptedDenom { denom }) => assert_eq!(denom, "smokin"),
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        };
+    }
+
+    #[test]
+    fn charge_sums_sent_coins() {
+        // coins of the same denom add up to the price
+        let payment = charge(&[coin(3, "token"), coin(4, "token")], &[coin(5, "token")]).unwrap();
+        assert_eq!(payment, Payment { charge: Some(coin(5, "token")), change: coins(2, "token") });
+
+        // an accepted denom that is not charged is change
+        let payment = charge(&[coin(5, "token"), coin(1, "earth")], &[coin(5, "token"), coin(2, "earth")]).unwrap();
+        assert_eq!(payment, Payment { charge: Some(coin(5, "token")), change: coins(1, "earth") });
+
+        // free actions charge nothing and give everything back
+        let payment = charge(&[coin(1, "smokin"), coin(0, "token")], &[]).unwrap();
+        assert_eq!(payment, Payment { charge: None, change: coins(1, "smokin") });
+        let payment = charge(&coins(2, "token"), &[coin(0, "token")]).unwrap();
+        assert_eq!(payment, Payment { charge: None, change: coins(2, "token") });
+    }
+
+    #[test]
+    fn any_accepted_price_is_enough() {
+        let accepted = vec![coin(5, "token"), coin(2, "earth")];
+        assert_sent_sufficient_coin(&coins(2, "earth"), &accepted).unwrap();
+        assert_eq!(charged_coin(&[coin(1, "token"), coin(3, "earth")], &accepted), Some(coin(2, "earth")));
+
+        match assert_sent_sufficient_coin(&[coin(4, "token"), coin(1, "earth")], &accepted) {
+            Ok(()) => panic!("Should have raised insufficient funds error"),
+            Err(ContractError::InsufficientFundsSend {}) => {}
             Err(e) => panic!("Unexpected error: {:?}", e),
         };
+        assert_sent_sufficient_coin(&[], &[]).unwrap();
+    }
+
+    #[test]
+    fn add_coins_merges_denoms() {
+        let mut total = coins(2, "token");
+        add_coins(&mut total, &[coin(3, "token"), coin(1, "earth")]);
+        assert_eq!(total, vec![coin(5, "token"), coin(1, "earth")]);
+
+        add_coins(&mut total, &[]);
+        assert_eq!(total, vec![coin(5, "token"), coin(1, "earth")]);
+    }
+
+    #[test]
+    fn deduct_coin_works() {
+        let mut balance = vec![coin(5, "token"), coin(1, "earth")];
+        assert!(deduct_coin(&mut balance, &coin(2, "token")));
+        assert_eq!(balance, vec![coin(3, "token"), coin(1, "earth")]);
+
+        assert!(!deduct_coin(&mut balance, &coin(4, "token")));
+        assert!(!deduct_coin(&mut balance, &coin(1, "smokin")));
+        assert_eq!(balance, vec![coin(3, "token"), coin(1, "earth")]);
+
+        // emptied denoms are dropped
+        assert!(deduct_coin(&mut balance, &coin(1, "earth")));
+        assert_eq!(balance, coins(3, "token"));
+    }
+
+    #[test]
+    fn subtract_coins_stops_at_zero() {
+        let mut total = vec![coin(5, "token"), coin(1, "earth")];
+        subtract_coins(&mut total, &[coin(2, "token"), coin(3, "earth"), coin(1, "smokin")]);
+        assert_eq!(total, coins(3, "token"));
     }
 }
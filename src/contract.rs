@@ -1,17 +1,89 @@
 use cosmwasm_std::{
-    entry_point, to_binary, Binary, BankMsg, Coin, Deps, DepsMut, Env, MessageInfo, Response, StdError, StdResult, Addr,
+    coin, entry_point, from_binary, to_binary, Binary, BankMsg, Coin, Deps, DepsMut, Empty, Env, MessageInfo, Order,
+    Response, StdError, StdResult, Storage, Timestamp, Addr, HexBinary, Uint128, Reply, SubMsg, SubMsgResult, WasmMsg,
+    Event,
 };
+use cosmwasm_schema::serde::de::{DeserializeOwned, Deserializer, IgnoredAny};
+use cosmwasm_schema::serde::{Deserialize, Serialize};
+use cw_storage_plus::{Bound, Map};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
 
-use crate::coin_helpers::assert_sent_sufficient_coin;
+use crate::coin_helpers::{
+    add_coins, assert_sent_sufficient_coin, charge, charged_coin, deduct_coin, subtract_coins, Payment,
+};
 use crate::error::ContractError;
-use crate::msg::{ConfigResponse, ExecuteMsg, MigrateMsg, InstantiateMsg, QueryMsg, ResolveRecordResponse};
-use crate::state::{Config, NameRecord, CONFIG, NAME_RESOLVER};
-
-// Name Config
-const MIN_NAME_LENGTH: u64 = 3;
-const MAX_NAME_LENGTH: u64 = 30;
-const MAX_BIO_LENGTH: u64 = 200;
-const MAX_WEBSITE_LENGTH: u64 = 100;
+use crate::msg::{
+    AllNamesResponse, Attestation, AuctionResponse, Availability, AvailabilityResponse, AvailabilityStatus,
+    AttestationIssuersResponse, BalancesResponse, AttestationsResponse, CategoriesResponse, CategoryInfo,
+    CategoryMembersResponse, ConfigResponse, ConfigUpdate, Cw721ReceiveMsg, DnsRecord, DnsZoneResponse,
+    DonationsResponse, ExecuteMsg, FeeQueryMsg, FeeResponse, GatewayKey, GatewayKeysResponse, GatewayPayload, GrantInfo,
+    GrantsResponse, HoldResponse, InstantiateMsg, InvariantsResponse, ListingInfo, ListingsResponse, MigrateMsg,
+    MigrationPlanResponse, MigrationStatusResponse, NameEvent, NameHistoryEntry, NameHistoryResponse, NameHookMsg,
+    NameInfo, NameOwner, NameScoreResponse, NamesResponse, NftApproval, NftExtension, NftInfoResponse, NftReceiverMsg,
+    OperatorsResponse, OwnerOfResponse, OwnersSnapshotResponse, OwnerStatsResponse, PausedActionsResponse,
+    PayoutResponse, PendingOwnerResponse, PhaseResponse, Preset, PrimaryNameResponse, QueryMsg, ReceivedMemo,
+    RecordStatsResponse, RegistrarsResponse, RegistrationData, RegistrationPriceResponse, RegistrationQuotaResponse,
+    ResolveAddressResponse, ResolvePayloadResponse, ResolveRecordAtResponse, ResolveRecordResponse,
+    ResolveRecordsResponse, RevenueShare, SponsorshipResponse, StatsResponse, SudoMsg, SunsetResponse, SupplyResponse,
+    TextRecord, TextRecordsResponse, TokensResponse, TombstoneResponse, TransferMemosResponse, TransferOfferResponse,
+    TreasuryResponse, VerifiersResponse, VerifyGatewaySignatureResponse, WatchersResponse, WebhookResponse,
+};
+use crate::state::{
+    Auction, Beneficiary, Bid, CacheControl, Category, Config, Expiration, Grant, GrantAction, HistoryEntry,
+    HistoryEvent, Hold, LaunchPhase, Listing, Migration, NamePolicy, NameRecord, NameStats, OwnerStats, PaidTerm,
+    PhaseStart, PriceTier, RemovalReason, Socials, Sponsorship, Subname, TokenGate, Tombstone, TransferMemo,
+    TransferOffer, Webhook, ADMIN_FROZEN_NAMES, ALLOWLIST, ATTESTATIONS, ATTESTATION_ISSUERS, AUCTIONS, BIDS,
+    CATEGORIES, CATEGORY_MEMBERS, CHAIN_ADDRESSES, CONFIG, DICTIONARY, DONATIONS, DONOR_TOTALS, ESCROWED,
+    FEES_COLLECTED, FROZEN_NAMES, GATEWAY_KEYS, GRANTS, HISTORY_LENGTHS, HOLDS, LAUNCH_SCHEDULE, LISTINGS, LOCKED_NAMES,
+    MIGRATION, NAME_HISTORY, NAME_RECORDS_V1, NAME_STATS, NAME_WATCHERS, NFT_APPROVALS, OPERATORS, OWNER_INDEX,
+    OWNER_STATS, OWNERS, PAID_TERMS, PAUSED, PAUSED_ACTIONS, PAYOUTS, PENDING_OWNER, PRIMARY_NAME, PROMO_COMMITMENTS,
+    REGISTRANTS, REGISTRARS, RESERVED, SPONSORED_ADDRESSES, SPONSORED_CLAIMS, SPONSORSHIPS, SUBNAMES, SUCCESSOR,
+    SWEEP_CURSOR, TEXT_RECORDS, TOKEN_URIS, TOMBSTONES, TOTAL_NAMES, TOTAL_REGISTRATIONS, TRANSFER_MEMOS,
+    TRANSFER_OFFERS, TREASURY, VERIFIERS, WEBHOOKS, name_resolver,
+};
+use crate::validation::{
+    accepted_prices, batch_prices, invalid_char, name_length, score_name, validate_category, validate_dictionary_word,
+    validate_name, validate_name_policy, validate_price_tiers, validate_profile, PricedAction, validate_socials,
+    validate_content_hash,
+};
+
+// Attestation Config
+const MAX_ATTESTATION_KEY_LENGTH: u64 = 30;
+const MAX_ATTESTATION_VALUE_LENGTH: u64 = 200;
+const MAX_MEMO_LENGTH: u64 = 140;
+// memos an execute clears at most, the rest are cleared by the following executes of the recipient
+const MAX_MEMOS_CLEARED: usize = 10;
+// Chain Address Config
+const MAX_CHAIN_ID_LENGTH: u64 = 50;
+const MAX_CHAIN_ADDRESS_LENGTH: u64 = 128;
+// Token URI Config
+const MAX_TOKEN_URI_LENGTH: u64 = 256;
+// longest character-string of a DNS TXT record, longer values are split over several
+const MAX_TXT_STRING_LENGTH: usize = 255;
+const TOKEN_URI_SCHEMES: [&str; 2] = ["https://", "ipfs://"];
+// Text Record Config
+const MAX_TEXT_RECORDS: u64 = 20;
+const MAX_TEXT_KEY_LENGTH: u64 = 30;
+const MAX_TEXT_VALUE_LENGTH: u64 = 200;
+// webhooks are warned this many seconds before a name expires
+const DAY: u64 = 24 * 3600;
+const YEAR: u64 = 365 * DAY;
+const EXPIRY_NOTICE_PERIOD: u64 = 30 * DAY;
+// reply id of webhook callbacks, their failures are ignored
+const WEBHOOK_REPLY_ID: u64 = 1;
+// Pagination
+const DEFAULT_LIMIT: u32 = 10;
+const MAX_LIMIT: u32 = 30;
+// entries a filtered page may skip before the query fails
+const MAX_SCAN: usize = 300;
+const DEFAULT_MIGRATION_BATCH: u32 = 100;
+// names and auctions the end-block sweep looks at per block
+const END_BLOCK_BATCH: usize = 20;
+const MAX_AVAILABILITY_NAMES: usize = 50;
+const MAX_RESOLVE_NAMES: usize = 30;
+const MAX_RELEASE_BATCH: usize = 30;
+const MAX_REGISTER_BATCH: usize = 30;
 // Semantic Versioning
 const CONTRACT_NAME: &str = env!("CARGO_PKG_NAME");
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
@@ -28,13 +100,49 @@ pub fn instantiate(
         .and_then(|s| deps.api.addr_validate(s.as_str()).ok())
         .unwrap_or(info.sender);
 
+    let preset = msg.preset.map(|preset| preset_config(preset, msg.purchase_price.first())).unwrap_or_default();
     let config = Config {
         owner: owner.clone(),
         purchase_price: msg.purchase_price,
         transfer_price: msg.transfer_price,
         edit_price: msg.edit_price,
+        max_supply: msg.max_supply,
+        frozen: false,
+        community_fund: None,
+        min_delegation: None,
+        registration_duration: msg.registration_duration.or(preset.registration_duration),
+        grace_period: msg.grace_period.unwrap_or(preset.grace_period),
+        renewal_price: msg.renewal_price.or(preset.renewal_price),
+        edit_byte_price: None,
+        length_prices: preset.length_prices,
+        watch_deposit: None,
+        release_cooldown: 0,
+        fee_collector: None,
+        revenue_split: vec![],
+        fee_calculator: None,
+        ibc_channels: vec![],
+        hold_deposit: None,
+        hold_duration: 0,
+        name_policy: NamePolicy::default(),
+        burn_fees: msg.burn_fees,
+        referral_percent: 0,
+        premium_multiplier: 0,
+        premium_window: 0,
+        launch_phase: None,
+        allowlist_gate: None,
+        marketplace_fee_percent: 0,
+        royalty_percent: 0,
+        max_names_per_address: None,
+        registration_cooldown_seconds: None,
+        max_renewal_horizon: None,
     };
     CONFIG.save(deps.storage, &config)?;
+    TOTAL_NAMES.save(deps.storage, &0)?;
+    TOTAL_REGISTRATIONS.save(deps.storage, &0)?;
+    if msg.launch_schedule.windows(2).any(|pair| pair[0].starts_at >= pair[1].starts_at) {
+        return Err(StdError::generic_err("Launch phases must start in order"));
+    }
+    LAUNCH_SCHEDULE.save(deps.storage, &msg.launch_schedule)?;
 
     // Use CW2 to set the contract version, this is needed for migrations
     cw2::set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
@@ -44,6 +152,49 @@ pub fn instantiate(
         .add_attribute("owner", owner))
 }
 
+/// PresetConfig is what a Preset sets when the InstantiateMsg leaves it unset
+#[derive(Default)]
+struct PresetConfig {
+    registration_duration: Option<u64>,
+    grace_period: u64,
+    renewal_price: Option<Coin>,
+    length_prices: Vec<PriceTier>,
+}
+
+fn preset_config(preset: Preset, purchase_price: Option<&Coin>) -> PresetConfig {
+    match preset {
+        Preset::Identity => PresetConfig {
+            registration_duration: Some(YEAR),
+            grace_period: 30 * DAY,
+            renewal_price: purchase_price.cloned(),
+            length_prices: vec![],
+        },
+        Preset::Collectible => PresetConfig {
+            // the tiers are priced in the purchase_price denom, free registries stay free
+            length_prices: purchase_price
+                .map(|price| {
+                    [(3, 100u128), (5, 10)]
+                        .into_iter()
+                        .map(|(max_length, multiplier)| PriceTier {
+                            max_length,
+                            price: Coin {
+                                denom: price.denom.clone(),
+                                amount: price.amount * Uint128::from(multiplier),
+                            },
+                        })
+                        .collect()
+                })
+                .unwrap_or_default(),
+            ..PresetConfig::default()
+        },
+        Preset::Enterprise => PresetConfig {
+            registration_duration: Some(YEAR),
+            grace_period: 90 * DAY,
+            ..PresetConfig::default()
+        },
+    }
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn execute(
     deps: DepsMut,
@@ -51,254 +202,6030 @@ pub fn execute(
     info: MessageInfo,
     msg: ExecuteMsg,
 ) -> Result<Response, ContractError> {
+    // records and counters are being rewritten, keep them still until the migration completes
+    if !matches!(msg, ExecuteMsg::ContinueMigration { .. }) && MIGRATION.may_load(deps.storage)?.is_some() {
+        return Err(ContractError::MigrationInProgress {});
+    }
+    if !matches!(msg, ExecuteMsg::Unpause {}) && PAUSED.may_load(deps.storage)?.unwrap_or_default() {
+        return Err(ContractError::Paused {});
+    }
+    let action = action_name(&msg);
+    if PAUSED_ACTIONS.has(deps.storage, action) {
+        return Err(ContractError::ActionPaused { action: action.to_string() });
+    }
+    if let Some(name) = target_names(&msg).into_iter().find(|name| FROZEN_NAMES.has(deps.storage, name.as_bytes())) {
+        return Err(ContractError::NameFrozen { name: name.to_string() });
+    }
+    // the sender is active again, memos of names it received have been seen
+    clear_transfer_memos(deps.storage, &info.sender)?;
+
     match msg {
-        ExecuteMsg::Register { name, bio, website } => execute_register(deps, env, info, name, bio, website),
-        ExecuteMsg::Transfer { name, to } => execute_transfer(deps, env, info, name, to),
+        ExecuteMsg::Register { name, bio, website, round_up, referrer } => {
+            execute_register(deps, env, info, name, bio, website, round_up, referrer)
+        }
+        ExecuteMsg::RegisterBatch { registrations } => execute_register_batch(deps, env, info, registrations),
+        ExecuteMsg::Renew { name } => execute_renew(deps, env, info, name),
+        ExecuteMsg::RenewFor { name, years } => execute_renew_for(deps, env, info, name, years),
+        ExecuteMsg::Transfer { name, to, round_up, memo } => execute_transfer(deps, env, info, name, to, round_up, memo),
+        ExecuteMsg::OfferTransfer { name, to, price } => execute_offer_transfer(deps, env, info, name, to, price),
+        ExecuteMsg::AcceptTransfer { name } => execute_accept_transfer(deps, env, info, name),
+        ExecuteMsg::ListForSale { name, price } => execute_list_for_sale(deps, env, info, name, price),
+        ExecuteMsg::Delist { name } => execute_delist(deps, env, info, name),
+        ExecuteMsg::Buy { name } => execute_buy(deps, env, info, name),
+        ExecuteMsg::SetMarketplaceFees { marketplace_fee_percent, royalty_percent } => {
+            execute_set_marketplace_fees(deps, env, info, marketplace_fee_percent, royalty_percent)
+        }
+        ExecuteMsg::SetRegistrationLimits { max_names_per_address, registration_cooldown_seconds } => {
+            execute_set_registration_limits(deps, env, info, max_names_per_address, registration_cooldown_seconds)
+        }
+        ExecuteMsg::CancelTransfer { name } => execute_cancel_transfer(deps, env, info, name),
         ExecuteMsg::Refund {} => execute_refund(deps, env, info),
-        ExecuteMsg::Edit { name, bio, website } => execute_edit(deps, env, info, name, bio, website),
-        ExecuteMsg::Editconf { purchase_price, transfer_price, edit_price } => execute_edit_conf(deps, env, info, purchase_price, transfer_price, edit_price),
+        ExecuteMsg::ClaimPayout {} => execute_claim_payout(deps, env, info),
+        ExecuteMsg::SetTreasury { fee_collector, revenue_split } => {
+            execute_set_treasury(deps, env, info, fee_collector, revenue_split)
+        }
+        ExecuteMsg::SetFeeCalculator { address } => execute_set_fee_calculator(deps, env, info, address),
+        ExecuteMsg::SetIbcChannels { channels } => execute_set_ibc_channels(deps, env, info, channels),
+        ExecuteMsg::Withdraw { amount, denom } => execute_withdraw(deps, env, info, amount, denom),
+        ExecuteMsg::StartAuction { name, min_bid, duration } => {
+            execute_start_auction(deps, env, info, name, min_bid, duration)
+        }
+        ExecuteMsg::PlaceBid { name } => execute_place_bid(deps, env, info, name),
+        ExecuteMsg::CloseAuction { name } => execute_close_auction(deps, env, info, name),
+        ExecuteMsg::Release { name } => execute_release(deps, env, info, name),
+        ExecuteMsg::ReleaseBatch { names } => execute_release_batch(deps, env, info, names),
+        ExecuteMsg::ForceRelease { name } => execute_force_release(deps, env, info, name),
+        ExecuteMsg::Edit { name, bio, website, round_up } => execute_edit(deps, env, info, name, bio, website, round_up),
+        ExecuteMsg::Editconf { purchase_price, transfer_price, edit_price, length_prices, name_policy } => {
+            execute_edit_conf(deps, env, info, purchase_price, transfer_price, edit_price, length_prices, name_policy)
+        }
+        ExecuteMsg::AddCategory { category, requires_approval } => execute_add_category(deps, env, info, category, requires_approval),
+        ExecuteMsg::RemoveCategory { category } => execute_remove_category(deps, env, info, category),
+        ExecuteMsg::JoinCategory { name, category } => execute_join_category(deps, env, info, name, category),
+        ExecuteMsg::LeaveCategory { name, category } => execute_leave_category(deps, env, info, name, category),
+        ExecuteMsg::ApproveCategoryMember { name, category } => execute_approve_category_member(deps, env, info, name, category),
+        ExecuteMsg::AddVerifier { address } => execute_add_verifier(deps, env, info, address),
+        ExecuteMsg::RemoveVerifier { address } => execute_remove_verifier(deps, env, info, address),
+        ExecuteMsg::AddRegistrar { address } => execute_add_registrar(deps, env, info, address),
+        ExecuteMsg::RemoveRegistrar { address } => execute_remove_registrar(deps, env, info, address),
+        ExecuteMsg::RegisterFor { name, owner, bio, website } => {
+            execute_register_for(deps, env, info, name, owner, bio, website)
+        }
+        ExecuteMsg::SetVerified { name, verified } => execute_set_verified(deps, env, info, name, verified),
+        ExecuteMsg::AddAttestationIssuer { address } => execute_add_attestation_issuer(deps, env, info, address),
+        ExecuteMsg::RemoveAttestationIssuer { address } => execute_remove_attestation_issuer(deps, env, info, address),
+        ExecuteMsg::SetGatewayKey { id, pubkey } => execute_set_gateway_key(deps, env, info, id, pubkey),
+        ExecuteMsg::SetAttestationOptIn { name, accept } => execute_set_attestation_opt_in(deps, env, info, name, accept),
+        ExecuteMsg::Attest { name, key, value } => execute_attest(deps, env, info, name, key, value),
+        ExecuteMsg::RemoveAttestation { name, issuer, key } => execute_remove_attestation(deps, env, info, name, issuer, key),
+        ExecuteMsg::SetCacheControl { name, cache_control } => execute_set_cache_control(deps, env, info, name, cache_control),
+        ExecuteMsg::SetSocials { name, socials } => execute_set_socials(deps, env, info, name, socials),
+        ExecuteMsg::SetContentHash { name, value } => execute_set_content_hash(deps, env, info, name, value),
+        ExecuteMsg::SetMaxSupply { max_supply } => execute_set_max_supply(deps, env, info, max_supply),
+        ExecuteMsg::Pause {} => execute_set_paused(deps, env, info, true),
+        ExecuteMsg::Unpause {} => execute_set_paused(deps, env, info, false),
+        ExecuteMsg::FreezeConfig {} => execute_freeze_config(deps, env, info),
+        ExecuteMsg::Sunset { successor } => execute_sunset(deps, env, info, successor),
+        ExecuteMsg::TransferOwnership { new_owner } => execute_transfer_ownership(deps, env, info, new_owner),
+        ExecuteMsg::AcceptOwnership {} => execute_accept_ownership(deps, env, info),
+        ExecuteMsg::ContinueMigration { limit } => execute_continue_migration(deps, env, info, limit),
+        ExecuteMsg::RebuildIndexes { start_after, limit } => {
+            execute_rebuild_indexes(deps, env, info, start_after, limit)
+        }
+        ExecuteMsg::FundSponsorship { open } => execute_fund_sponsorship(deps, env, info, open),
+        ExecuteMsg::SetSponsoredAddresses { add, remove } => execute_set_sponsored_addresses(deps, env, info, add, remove),
+        ExecuteMsg::WithdrawSponsorship {} => execute_withdraw_sponsorship(deps, env, info),
+        ExecuteMsg::RegisterSponsored { name, bio, website, sponsor } => {
+            execute_register_sponsored(deps, env, info, name, bio, website, sponsor)
+        }
+        ExecuteMsg::Grant { grantee, actions, expires } => execute_grant(deps, env, info, grantee, actions, expires),
+        ExecuteMsg::RevokeGrant { grantee } => execute_revoke(deps, env, info, grantee),
+        ExecuteMsg::CommitPromoNames { hashes } => execute_commit_promo_names(deps, env, info, hashes),
+        ExecuteMsg::RemovePromoNames { hashes } => execute_remove_promo_names(deps, env, info, hashes),
+        ExecuteMsg::ClaimPromoName { name, secret, bio, website } => {
+            execute_claim_promo_name(deps, env, info, name, secret, bio, website)
+        }
+        ExecuteMsg::ReserveNames { names } => execute_reserve_names(deps, env, info, names),
+        ExecuteMsg::UnreserveNames { names } => execute_unreserve_names(deps, env, info, names),
+        ExecuteMsg::SetAllowlist { add, remove } => execute_set_allowlist(deps, env, info, add, remove),
+        ExecuteMsg::SetAllowlistGate { gate } => execute_set_allowlist_gate(deps, env, info, gate),
+        ExecuteMsg::SetLaunchPhase { phase } => execute_set_launch_phase(deps, env, info, phase),
+        ExecuteMsg::AssignReservedName { name, owner } => execute_assign_reserved_name(deps, env, info, name, owner),
+        ExecuteMsg::AddDictionaryWords { words } => execute_add_dictionary_words(deps, env, info, words),
+        ExecuteMsg::RemoveDictionaryWords { words } => execute_remove_dictionary_words(deps, env, info, words),
+        ExecuteMsg::SetCommunityFund { address } => execute_set_community_fund(deps, env, info, address),
+        ExecuteMsg::SetMinDelegation { min_delegation } => execute_set_min_delegation(deps, env, info, min_delegation),
+        ExecuteMsg::SetEditBytePrice { edit_byte_price } => execute_set_edit_byte_price(deps, env, info, edit_byte_price),
+        ExecuteMsg::SetExpiryPolicy { registration_duration, grace_period, renewal_price, max_renewal_horizon } => {
+            execute_set_expiry_policy(
+                deps,
+                env,
+                info,
+                registration_duration,
+                grace_period,
+                renewal_price,
+                max_renewal_horizon,
+            )
+        }
+        ExecuteMsg::SetWebhook { name, contract, payload } => execute_set_webhook(deps, env, info, name, contract, payload),
+        ExecuteMsg::NotifyExpiring { name } => execute_notify_expiring(deps, env, info, name),
+        ExecuteMsg::WatchName { name } => execute_watch_name(deps, env, info, name),
+        ExecuteMsg::Reserve { name } => execute_reserve(deps, env, info, name),
+        ExecuteMsg::SetHoldTerms { hold_deposit, hold_duration } => {
+            execute_set_hold_terms(deps, env, info, hold_deposit, hold_duration)
+        }
+        ExecuteMsg::SetPremiumDecay { premium_multiplier, premium_window } => {
+            execute_set_premium_decay(deps, env, info, premium_multiplier, premium_window)
+        }
+        ExecuteMsg::SetReferralPercent { referral_percent } => {
+            execute_set_referral_percent(deps, env, info, referral_percent)
+        }
+        ExecuteMsg::UnwatchName { name } => execute_unwatch_name(deps, env, info, name),
+        ExecuteMsg::NotifyWatchers { name } => execute_notify_watchers(deps, env, info, name),
+        ExecuteMsg::SetWatchDeposit { watch_deposit } => execute_set_watch_deposit(deps, env, info, watch_deposit),
+        ExecuteMsg::SetReleaseCooldown { release_cooldown } => {
+            execute_set_release_cooldown(deps, env, info, release_cooldown)
+        }
+        ExecuteMsg::SetPrimaryName { name } => execute_set_primary_name(deps, env, info, name),
+        ExecuteMsg::SetTextRecord { name, key, value } => execute_set_text_record(deps, env, info, name, key, value),
+        ExecuteMsg::RemoveTextRecord { name, key } => execute_remove_text_record(deps, env, info, name, key),
+        ExecuteMsg::RegisterSubname { parent, label, owner, bio, website } => {
+            execute_register_subname(deps, env, info, parent, label, owner, bio, website)
+        }
+        ExecuteMsg::RemoveSubname { parent, label } => execute_remove_subname(deps, env, info, parent, label),
+        ExecuteMsg::SetAddress { name, chain_id, address } => execute_set_address(deps, env, info, name, chain_id, address),
+        ExecuteMsg::TransferNft { recipient, token_id } => execute_transfer_nft(deps, env, info, recipient, token_id),
+        ExecuteMsg::SendNft { contract, token_id, msg } => execute_send_nft(deps, env, info, contract, token_id, msg),
+        ExecuteMsg::Approve { spender, token_id, expires } => execute_approve(deps, env, info, spender, token_id, expires),
+        ExecuteMsg::Revoke { spender, token_id } => execute_revoke_approval(deps, env, info, spender, token_id),
+        ExecuteMsg::SetOperator { operator, approved } => execute_set_operator(deps, env, info, operator, approved),
+        ExecuteMsg::SetTokenUri { name, token_uri } => execute_set_token_uri(deps, env, info, name, token_uri),
+        ExecuteMsg::Lock { name } => execute_lock(deps, env, info, name),
+        ExecuteMsg::Unlock { name } => execute_unlock(deps, env, info, name),
+        ExecuteMsg::SetNameFrozen { name, frozen } => execute_set_name_frozen(deps, env, info, name, frozen),
+
+    }
+}
+
+/// ACTIONS are the names action_name gives, the only ones governance can pause
+const ACTIONS: &[&str] = &[
+    "register", "register_batch", "transfer", "offer_transfer", "accept_transfer", "cancel_transfer", "list_for_sale",
+    "delist", "buy", "set_marketplace_fees", "set_registration_limits", "refund", "set_treasury", "set_fee_calculator",
+    "set_ibc_channels", "withdraw", "claim_payout", "start_auction", "place_bid", "close_auction", "release",
+    "release_batch", "force_release", "renew", "renew_for", "edit", "editconf", "add_category", "remove_category",
+    "join_category", "leave_category", "approve_category_member", "add_verifier", "remove_verifier", "add_registrar",
+    "remove_registrar", "register_for", "set_verified", "add_attestation_issuer", "remove_attestation_issuer",
+    "set_gateway_key", "set_attestation_opt_in", "attest", "remove_attestation", "set_cache_control", "set_socials",
+    "set_content_hash", "set_max_supply", "pause", "unpause", "freeze_config", "sunset", "transfer_ownership",
+    "accept_ownership", "continue_migration", "rebuild_indexes", "fund_sponsorship", "set_sponsored_addresses",
+    "withdraw_sponsorship", "register_sponsored", "grant", "revoke_grant", "commit_promo_names", "remove_promo_names",
+    "claim_promo_name", "reserve_names", "unreserve_names", "set_allowlist", "set_allowlist_gate", "set_launch_phase",
+    "assign_reserved_name", "add_dictionary_words", "remove_dictionary_words", "set_community_fund",
+    "set_min_delegation", "set_edit_byte_price", "set_expiry_policy", "set_webhook", "notify_expiring", "watch_name",
+    "unwatch_name", "notify_watchers", "set_watch_deposit", "reserve", "set_hold_terms", "set_premium_decay",
+    "set_referral_percent", "set_release_cooldown", "set_primary_name", "set_text_record", "remove_text_record",
+    "register_subname", "remove_subname", "set_address", "transfer_nft", "send_nft", "approve", "revoke",
+    "set_operator", "set_token_uri", "lock", "unlock", "set_name_frozen",
+];
+
+/// action_name is the snake_case name of the ExecuteMsg variant, as it appears in the JSON message
+fn action_name(msg: &ExecuteMsg) -> &'static str {
+    match msg {
+        ExecuteMsg::Register { .. } => "register",
+        ExecuteMsg::RegisterBatch { .. } => "register_batch",
+        ExecuteMsg::Transfer { .. } => "transfer",
+        ExecuteMsg::OfferTransfer { .. } => "offer_transfer",
+        ExecuteMsg::AcceptTransfer { .. } => "accept_transfer",
+        ExecuteMsg::CancelTransfer { .. } => "cancel_transfer",
+        ExecuteMsg::ListForSale { .. } => "list_for_sale",
+        ExecuteMsg::Delist { .. } => "delist",
+        ExecuteMsg::Buy { .. } => "buy",
+        ExecuteMsg::SetMarketplaceFees { .. } => "set_marketplace_fees",
+        ExecuteMsg::SetRegistrationLimits { .. } => "set_registration_limits",
+        ExecuteMsg::Refund { .. } => "refund",
+        ExecuteMsg::SetTreasury { .. } => "set_treasury",
+        ExecuteMsg::SetFeeCalculator { .. } => "set_fee_calculator",
+        ExecuteMsg::SetIbcChannels { .. } => "set_ibc_channels",
+        ExecuteMsg::Withdraw { .. } => "withdraw",
+        ExecuteMsg::ClaimPayout { .. } => "claim_payout",
+        ExecuteMsg::StartAuction { .. } => "start_auction",
+        ExecuteMsg::PlaceBid { .. } => "place_bid",
+        ExecuteMsg::CloseAuction { .. } => "close_auction",
+        ExecuteMsg::Release { .. } => "release",
+        ExecuteMsg::ReleaseBatch { .. } => "release_batch",
+        ExecuteMsg::ForceRelease { .. } => "force_release",
+        ExecuteMsg::Renew { .. } => "renew",
+        ExecuteMsg::RenewFor { .. } => "renew_for",
+        ExecuteMsg::Edit { .. } => "edit",
+        ExecuteMsg::Editconf { .. } => "editconf",
+        ExecuteMsg::AddCategory { .. } => "add_category",
+        ExecuteMsg::RemoveCategory { .. } => "remove_category",
+        ExecuteMsg::JoinCategory { .. } => "join_category",
+        ExecuteMsg::LeaveCategory { .. } => "leave_category",
+        ExecuteMsg::ApproveCategoryMember { .. } => "approve_category_member",
+        ExecuteMsg::AddVerifier { .. } => "add_verifier",
+        ExecuteMsg::RemoveVerifier { .. } => "remove_verifier",
+        ExecuteMsg::AddRegistrar { .. } => "add_registrar",
+        ExecuteMsg::RemoveRegistrar { .. } => "remove_registrar",
+        ExecuteMsg::RegisterFor { .. } => "register_for",
+        ExecuteMsg::SetVerified { .. } => "set_verified",
+        ExecuteMsg::AddAttestationIssuer { .. } => "add_attestation_issuer",
+        ExecuteMsg::RemoveAttestationIssuer { .. } => "remove_attestation_issuer",
+        ExecuteMsg::SetGatewayKey { .. } => "set_gateway_key",
+        ExecuteMsg::SetAttestationOptIn { .. } => "set_attestation_opt_in",
+        ExecuteMsg::Attest { .. } => "attest",
+        ExecuteMsg::RemoveAttestation { .. } => "remove_attestation",
+        ExecuteMsg::SetCacheControl { .. } => "set_cache_control",
+        ExecuteMsg::SetSocials { .. } => "set_socials",
+        ExecuteMsg::SetContentHash { .. } => "set_content_hash",
+        ExecuteMsg::SetMaxSupply { .. } => "set_max_supply",
+        ExecuteMsg::Pause { .. } => "pause",
+        ExecuteMsg::Unpause { .. } => "unpause",
+        ExecuteMsg::FreezeConfig { .. } => "freeze_config",
+        ExecuteMsg::Sunset { .. } => "sunset",
+        ExecuteMsg::TransferOwnership { .. } => "transfer_ownership",
+        ExecuteMsg::AcceptOwnership { .. } => "accept_ownership",
+        ExecuteMsg::ContinueMigration { .. } => "continue_migration",
+        ExecuteMsg::RebuildIndexes { .. } => "rebuild_indexes",
+        ExecuteMsg::FundSponsorship { .. } => "fund_sponsorship",
+        ExecuteMsg::SetSponsoredAddresses { .. } => "set_sponsored_addresses",
+        ExecuteMsg::WithdrawSponsorship { .. } => "withdraw_sponsorship",
+        ExecuteMsg::RegisterSponsored { .. } => "register_sponsored",
+        ExecuteMsg::Grant { .. } => "grant",
+        ExecuteMsg::RevokeGrant { .. } => "revoke_grant",
+        ExecuteMsg::CommitPromoNames { .. } => "commit_promo_names",
+        ExecuteMsg::RemovePromoNames { .. } => "remove_promo_names",
+        ExecuteMsg::ClaimPromoName { .. } => "claim_promo_name",
+        ExecuteMsg::ReserveNames { .. } => "reserve_names",
+        ExecuteMsg::UnreserveNames { .. } => "unreserve_names",
+        ExecuteMsg::SetAllowlist { .. } => "set_allowlist",
+        ExecuteMsg::SetAllowlistGate { .. } => "set_allowlist_gate",
+        ExecuteMsg::SetLaunchPhase { .. } => "set_launch_phase",
+        ExecuteMsg::AssignReservedName { .. } => "assign_reserved_name",
+        ExecuteMsg::AddDictionaryWords { .. } => "add_dictionary_words",
+        ExecuteMsg::RemoveDictionaryWords { .. } => "remove_dictionary_words",
+        ExecuteMsg::SetCommunityFund { .. } => "set_community_fund",
+        ExecuteMsg::SetMinDelegation { .. } => "set_min_delegation",
+        ExecuteMsg::SetEditBytePrice { .. } => "set_edit_byte_price",
+        ExecuteMsg::SetExpiryPolicy { .. } => "set_expiry_policy",
+        ExecuteMsg::SetWebhook { .. } => "set_webhook",
+        ExecuteMsg::NotifyExpiring { .. } => "notify_expiring",
+        ExecuteMsg::WatchName { .. } => "watch_name",
+        ExecuteMsg::UnwatchName { .. } => "unwatch_name",
+        ExecuteMsg::NotifyWatchers { .. } => "notify_watchers",
+        ExecuteMsg::SetWatchDeposit { .. } => "set_watch_deposit",
+        ExecuteMsg::Reserve { .. } => "reserve",
+        ExecuteMsg::SetHoldTerms { .. } => "set_hold_terms",
+        ExecuteMsg::SetPremiumDecay { .. } => "set_premium_decay",
+        ExecuteMsg::SetReferralPercent { .. } => "set_referral_percent",
+        ExecuteMsg::SetReleaseCooldown { .. } => "set_release_cooldown",
+        ExecuteMsg::SetPrimaryName { .. } => "set_primary_name",
+        ExecuteMsg::SetTextRecord { .. } => "set_text_record",
+        ExecuteMsg::RemoveTextRecord { .. } => "remove_text_record",
+        ExecuteMsg::RegisterSubname { .. } => "register_subname",
+        ExecuteMsg::RemoveSubname { .. } => "remove_subname",
+        ExecuteMsg::SetAddress { .. } => "set_address",
+        ExecuteMsg::TransferNft { .. } => "transfer_nft",
+        ExecuteMsg::SendNft { .. } => "send_nft",
+        ExecuteMsg::Approve { .. } => "approve",
+        ExecuteMsg::Revoke { .. } => "revoke",
+        ExecuteMsg::SetOperator { .. } => "set_operator",
+        ExecuteMsg::SetTokenUri { .. } => "set_token_uri",
+        ExecuteMsg::Lock { .. } => "lock",
+        ExecuteMsg::Unlock { .. } => "unlock",
+        ExecuteMsg::SetNameFrozen { .. } => "set_name_frozen",
+    }
+}
+
+/// target_names lists the names `msg` acts on
+fn target_names(msg: &ExecuteMsg) -> Vec<&str> {
+    match msg {
+        ExecuteMsg::Register { name, .. }
+        | ExecuteMsg::Transfer { name, .. }
+        | ExecuteMsg::OfferTransfer { name, .. }
+        | ExecuteMsg::AcceptTransfer { name, .. }
+        | ExecuteMsg::CancelTransfer { name, .. }
+        | ExecuteMsg::ListForSale { name, .. }
+        | ExecuteMsg::Delist { name, .. }
+        | ExecuteMsg::Buy { name, .. }
+        | ExecuteMsg::StartAuction { name, .. }
+        | ExecuteMsg::PlaceBid { name, .. }
+        | ExecuteMsg::CloseAuction { name, .. }
+        | ExecuteMsg::Release { name, .. }
+        | ExecuteMsg::ForceRelease { name, .. }
+        | ExecuteMsg::Renew { name, .. }
+        | ExecuteMsg::RenewFor { name, .. }
+        | ExecuteMsg::Edit { name, .. }
+        | ExecuteMsg::JoinCategory { name, .. }
+        | ExecuteMsg::LeaveCategory { name, .. }
+        | ExecuteMsg::ApproveCategoryMember { name, .. }
+        | ExecuteMsg::RegisterFor { name, .. }
+        | ExecuteMsg::SetVerified { name, .. }
+        | ExecuteMsg::SetAttestationOptIn { name, .. }
+        | ExecuteMsg::Attest { name, .. }
+        | ExecuteMsg::RemoveAttestation { name, .. }
+        | ExecuteMsg::SetCacheControl { name, .. }
+        | ExecuteMsg::SetSocials { name, .. }
+        | ExecuteMsg::SetContentHash { name, .. }
+        | ExecuteMsg::RegisterSponsored { name, .. }
+        | ExecuteMsg::ClaimPromoName { name, .. }
+        | ExecuteMsg::AssignReservedName { name, .. }
+        | ExecuteMsg::SetWebhook { name, .. }
+        | ExecuteMsg::NotifyExpiring { name, .. }
+        | ExecuteMsg::WatchName { name, .. }
+        | ExecuteMsg::UnwatchName { name, .. }
+        | ExecuteMsg::NotifyWatchers { name, .. }
+        | ExecuteMsg::Reserve { name, .. }
+        | ExecuteMsg::SetPrimaryName { name, .. }
+        | ExecuteMsg::SetTextRecord { name, .. }
+        | ExecuteMsg::RemoveTextRecord { name, .. }
+        | ExecuteMsg::SetAddress { name, .. }
+        | ExecuteMsg::SetTokenUri { name, .. }
+        | ExecuteMsg::Lock { name, .. }
+        | ExecuteMsg::Unlock { name, .. }
+        | ExecuteMsg::SetNameFrozen { name, .. } => vec![name.as_str()],
+        ExecuteMsg::ReleaseBatch { names, .. }
+        | ExecuteMsg::ReserveNames { names, .. }
+        | ExecuteMsg::UnreserveNames { names, .. } => {
+            names.iter().map(String::as_str).collect()
+        }
+        ExecuteMsg::RegisterBatch { registrations } => {
+            registrations.iter().map(|registration| registration.name.as_str()).collect()
+        }
+        ExecuteMsg::TransferNft { token_id, .. }
+        | ExecuteMsg::SendNft { token_id, .. }
+        | ExecuteMsg::Approve { token_id, .. }
+        | ExecuteMsg::Revoke { token_id, .. } => {
+            vec![token_id.as_str()]
+        }
+        // subnames are frozen with their parent
+        ExecuteMsg::RegisterSubname { parent, .. }
+        | ExecuteMsg::RemoveSubname { parent, .. } => vec![parent.as_str()],
+        ExecuteMsg::SetMarketplaceFees { .. }
+        | ExecuteMsg::SetRegistrationLimits { .. }
+        | ExecuteMsg::Refund { .. }
+        | ExecuteMsg::SetTreasury { .. }
+        | ExecuteMsg::SetFeeCalculator { .. }
+        | ExecuteMsg::SetIbcChannels { .. }
+        | ExecuteMsg::Withdraw { .. }
+        | ExecuteMsg::ClaimPayout { .. }
+        | ExecuteMsg::Editconf { .. }
+        | ExecuteMsg::AddCategory { .. }
+        | ExecuteMsg::RemoveCategory { .. }
+        | ExecuteMsg::AddVerifier { .. }
+        | ExecuteMsg::RemoveVerifier { .. }
+        | ExecuteMsg::AddRegistrar { .. }
+        | ExecuteMsg::RemoveRegistrar { .. }
+        | ExecuteMsg::AddAttestationIssuer { .. }
+        | ExecuteMsg::RemoveAttestationIssuer { .. }
+        | ExecuteMsg::SetGatewayKey { .. }
+        | ExecuteMsg::SetMaxSupply { .. }
+        | ExecuteMsg::Pause { .. }
+        | ExecuteMsg::Unpause { .. }
+        | ExecuteMsg::FreezeConfig { .. }
+        | ExecuteMsg::Sunset { .. }
+        | ExecuteMsg::TransferOwnership { .. }
+        | ExecuteMsg::AcceptOwnership { .. }
+        | ExecuteMsg::ContinueMigration { .. }
+        | ExecuteMsg::RebuildIndexes { .. }
+        | ExecuteMsg::FundSponsorship { .. }
+        | ExecuteMsg::SetSponsoredAddresses { .. }
+        | ExecuteMsg::WithdrawSponsorship { .. }
+        | ExecuteMsg::Grant { .. }
+        | ExecuteMsg::RevokeGrant { .. }
+        | ExecuteMsg::CommitPromoNames { .. }
+        | ExecuteMsg::RemovePromoNames { .. }
+        | ExecuteMsg::SetAllowlist { .. }
+        | ExecuteMsg::SetAllowlistGate { .. }
+        | ExecuteMsg::SetLaunchPhase { .. }
+        | ExecuteMsg::AddDictionaryWords { .. }
+        | ExecuteMsg::RemoveDictionaryWords { .. }
+        | ExecuteMsg::SetCommunityFund { .. }
+        | ExecuteMsg::SetMinDelegation { .. }
+        | ExecuteMsg::SetEditBytePrice { .. }
+        | ExecuteMsg::SetExpiryPolicy { .. }
+        | ExecuteMsg::SetWatchDeposit { .. }
+        | ExecuteMsg::SetHoldTerms { .. }
+        | ExecuteMsg::SetPremiumDecay { .. }
+        | ExecuteMsg::SetReferralPercent { .. }
+        | ExecuteMsg::SetReleaseCooldown { .. }
+        | ExecuteMsg::SetOperator { .. } => vec![],
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn sudo(deps: DepsMut, env: Env, msg: SudoMsg) -> Result<Response, ContractError> {
+    match msg {
+        SudoMsg::PauseActions { actions } => {
+            validate_actions(&actions)?;
+            for action in &actions {
+                PAUSED_ACTIONS.save(deps.storage, action, &Empty {})?;
+            }
+            Ok(Response::new()
+                .add_attribute("action", "pause_actions")
+                .add_attribute("actions", actions.join(",")))
+        }
+        SudoMsg::UnpauseActions { actions } => {
+            validate_actions(&actions)?;
+            for action in &actions {
+                PAUSED_ACTIONS.remove(deps.storage, action);
+            }
+            Ok(Response::new()
+                .add_attribute("action", "unpause_actions")
+                .add_attribute("actions", actions.join(",")))
+        }
+        SudoMsg::RebuildIndexes { start_after, limit } => rebuild_indexes(deps.storage, &env, start_after, limit),
+        SudoMsg::ForceTransfer { name, to } => sudo_force_transfer(deps, env, name, to),
+        SudoMsg::FreezeName { name } => {
+            if !name_resolver().has(deps.storage, name.as_bytes()) {
+                return Err(ContractError::NameNotExists { name });
+            }
+            FROZEN_NAMES.save(deps.storage, name.as_bytes(), &Empty {})?;
+            Ok(Response::new()
+                .add_attribute("action", "freeze_name")
+                .add_attribute("name", name))
+        }
+        SudoMsg::UnfreezeName { name } => {
+            // the name may have been removed since it was frozen, only the freeze has to exist
+            if !FROZEN_NAMES.has(deps.storage, name.as_bytes()) {
+                return Err(ContractError::NameNotFrozen { name });
+            }
+            FROZEN_NAMES.remove(deps.storage, name.as_bytes());
+            Ok(Response::new()
+                .add_attribute("action", "unfreeze_name")
+                .add_attribute("name", name))
+        }
+        SudoMsg::UpdateConfig(update) => sudo_update_config(deps, update),
+        SudoMsg::EndBlock {} => sudo_end_block(deps, env),
+    }
+}
+
+/// validate_actions returns an error unless every entry names an execute message as action_name does
+fn validate_actions(actions: &[String]) -> Result<(), ContractError> {
+    match actions.iter().find(|action| !ACTIONS.contains(&action.as_str())) {
+        Some(action) => Err(ContractError::UnknownAction { action: action.clone() }),
+        None => Ok(()),
+    }
+}
 
+/// sudo_end_block removes names whose grace period and premium ran out and settles ended auctions. It looks at
+/// END_BLOCK_BATCH names and auctions per block, going on where the previous block stopped
+fn sudo_end_block(deps: DepsMut, env: Env) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let mut cursor = SWEEP_CURSOR.may_load(deps.storage)?.unwrap_or_default();
+
+    let start = cursor.name.as_ref().map(|name| Bound::exclusive(name.as_bytes()));
+    let records = name_resolver()
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(END_BLOCK_BATCH)
+        .collect::<StdResult<Vec<_>>>()?;
+    cursor.name = sweep_cursor(&records);
+    let mut expired = vec![];
+    for (key, record) in records {
+        // the premium is priced from the expired record, it stays until the premium is over
+        if !is_released(&record, &config, &env) || premium_end(deps.storage, &config, &env, &key)?.is_some() {
+            continue;
+        }
+        let name = String::from_utf8_lossy(&key).into_owned();
+        remove_name(deps.storage, &env, &name, &record, &env.contract.address, RemovalReason::Expired)?;
+        expired.push(name);
+    }
+
+    let start = cursor.auction.as_ref().map(|name| Bound::exclusive(name.as_bytes()));
+    let auctions = AUCTIONS
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(END_BLOCK_BATCH)
+        .collect::<StdResult<Vec<_>>>()?;
+    cursor.auction = sweep_cursor(&auctions);
+    let mut response = Response::new();
+    let mut settled = vec![];
+    for (key, auction) in auctions {
+        if env.block.time < auction.ends_at {
+            continue;
+        }
+        let name = String::from_utf8_lossy(&key).into_owned();
+        response = response.add_submessages(settle_auction(deps.storage, &env, &name, true)?.messages);
+        settled.push(name);
+    }
+    SWEEP_CURSOR.save(deps.storage, &cursor)?;
+
+    Ok(response
+        .add_attribute("action", "end_block")
+        .add_attribute("expired", expired.join(","))
+        .add_attribute("settled", settled.join(",")))
+}
+
+/// sweep_cursor is where the sweep goes on after `batch`, a short batch reached the end and it starts over
+fn sweep_cursor<T>(batch: &[(Vec<u8>, T)]) -> Option<String> {
+    match batch.last() {
+        Some((key, _)) if batch.len() == END_BLOCK_BATCH => Some(String::from_utf8_lossy(key).into_owned()),
+        _ => None,
+    }
+}
+
+/// sudo_force_transfer moves a name to `to` on behalf of governance, the contract is the actor in its history
+fn sudo_force_transfer(deps: DepsMut, env: Env, name: String, to: String) -> Result<Response, ContractError> {
+    let new_owner = deps.api.addr_validate(&to)?;
+    let record = match name_resolver().may_load(deps.storage, name.as_bytes())? {
+        Some(record) => record,
+        None => return Err(ContractError::NameNotExists { name }),
+    };
+    let owner = record.owner.clone();
+    let hook = move_name(deps.storage, &env, &name, record, &env.contract.address, &new_owner)?;
+
+    Ok(Response::new()
+        .add_submessages(hook)
+        .add_attribute("action", "force_transfer")
+        .add_attribute("name", name)
+        .add_attribute("owner", owner)
+        .add_attribute("new_owner", new_owner))
+}
+
+/// sudo_update_config applies the parameters set in `update` with the checks of the admin's setters. A frozen
+/// config keeps its prices and policies, only the admin can still change
+fn sudo_update_config(deps: DepsMut, update: ConfigUpdate) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+    let changes_parameters = update.purchase_price.is_some()
+        || update.transfer_price.is_some()
+        || update.edit_price.is_some()
+        || update.length_prices.is_some()
+        || update.renewal_price.is_some()
+        || update.grace_period.is_some()
+        || update.name_policy.is_some();
+    // the freeze is irreversible, governance included
+    if config.frozen && (changes_parameters || update.frozen == Some(false)) {
+        return Err(ContractError::ConfigFrozen {});
+    }
+    if let Some(admin) = update.admin {
+        config.owner = deps.api.addr_validate(&admin)?;
+    }
+    if let Some(purchase_price) = update.purchase_price {
+        config.purchase_price = purchase_price;
+    }
+    if let Some(transfer_price) = update.transfer_price {
+        config.transfer_price = transfer_price;
+    }
+    if let Some(edit_price) = update.edit_price {
+        if let Some(edit_byte_price) = &config.edit_byte_price {
+            if edit_price.iter().any(|coin| coin.denom != edit_byte_price.denom) {
+                return Err(ContractError::EditPriceDenomMismatch {});
+            }
+        }
+        config.edit_price = edit_price;
+    }
+    if let Some(length_prices) = update.length_prices {
+        validate_price_tiers(&length_prices)?;
+        config.length_prices = length_prices;
+    }
+    if let Some(renewal_price) = update.renewal_price {
+        config.renewal_price = Some(renewal_price);
+    }
+    if let Some(grace_period) = update.grace_period {
+        config.grace_period = grace_period;
+    }
+    if let Some(name_policy) = update.name_policy {
+        validate_name_policy(&name_policy)?;
+        config.name_policy = name_policy;
+    }
+    if update.frozen == Some(true) {
+        config.frozen = true;
+    }
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "update_config")
+        .add_attribute("admin", config.owner))
+}
+
+/// reply swallows failing webhook callbacks, a broken webhook must not block its name
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(_deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractError> {
+    match (msg.id, msg.result) {
+        (WEBHOOK_REPLY_ID, SubMsgResult::Err(error)) => Ok(Response::new()
+            .add_attribute("action", "webhook_failed")
+            .add_attribute("error", error)),
+        // callbacks only reply on error, there is nothing to do for one that went through
+        (WEBHOOK_REPLY_ID, SubMsgResult::Ok(_)) => Ok(Response::new()),
+        (id, _) => Err(ContractError::Std(StdError::generic_err(format!("Unknown reply id {}", id)))),
     }
 }
 
 #[entry_point]
-pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+pub fn migrate(deps: DepsMut, env: Env, msg: MigrateMsg) -> Result<Response, ContractError> {
     let ver = cw2::get_contract_version(deps.storage)?;
 
     // ensure we are migrating from an allowed contract
-    if ver.contract != CONTRACT_NAME.to_string() {
+    if ver.contract != CONTRACT_NAME {
         return Err(StdError::generic_err("Can only upgrade from same type").into());
     }
+    let stored = parse_version(&ver.version)?;
+    if stored > parse_version(CONTRACT_VERSION)? {
+        let msg = format!("Cannot downgrade from {} to {}", ver.version, CONTRACT_VERSION);
+        return Err(StdError::generic_err(msg).into());
+    }
     // set the new version
     cw2::set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
-    // do any desired state migrations...
 
-    Ok(Response::default())
+    // sponsorships predate the escrow accounting, their balances are still owed to the sponsors
+    if ESCROWED.may_load(deps.storage)?.is_none() {
+        let mut escrowed = vec![];
+        for item in SPONSORSHIPS.range(deps.storage, None, None, Order::Ascending) {
+            let (_, sponsorship) = item?;
+            add_coins(&mut escrowed, &sponsorship.balance);
+        }
+        ESCROWED.save(deps.storage, &escrowed)?;
+    }
+
+    // rewrite every record into the current layout. A migration left pending by an
+    // earlier upgrade is resumed rather than restarted, so counters are not backfilled twice.
+    if MIGRATION.may_load(deps.storage)?.is_none() {
+        // contracts instantiated before the name counters existed need them backfilled
+        let backfill_counters = TOTAL_NAMES.may_load(deps.storage)?.is_none();
+        if backfill_counters {
+            TOTAL_NAMES.save(deps.storage, &0)?;
+        }
+        // registrations before the counter existed are only known by the names still held
+        if TOTAL_REGISTRATIONS.may_load(deps.storage)?.is_none() {
+            let held = TOTAL_NAMES.load(deps.storage)?;
+            TOTAL_REGISTRATIONS.save(deps.storage, &held)?;
+        }
+        // the layout records are read in depends on the release that wrote them
+        let legacy_layout = matches!(stored, (0, 0..=1, _));
+        MIGRATION.save(
+            deps.storage,
+            &Migration {
+                last_name: None,
+                migrated: 0,
+                backfill_counters,
+                legacy_layout,
+            },
+        )?;
+    }
+    let done = migrate_records(deps.storage, &env, msg.batch_size.map(|size| size as usize))?;
+
+    Ok(Response::new()
+        .add_attribute("method", "migrate")
+        .add_attribute("done", done.to_string()))
+}
+
+/// parse_version reads a `major.minor.patch` version, pre-release and build suffixes are ignored
+fn parse_version(version: &str) -> StdResult<(u64, u64, u64)> {
+    let core = version.split(['-', '+']).next().unwrap_or_default();
+    let parts = core.split('.').map(str::parse::<u64>).collect::<Result<Vec<_>, _>>();
+    match parts.as_deref() {
+        Ok([major, minor, patch]) => Ok((*major, *minor, *patch)),
+        _ => Err(StdError::generic_err(format!("Invalid contract version {}", version))),
+    }
+}
+
+/// migrate_records rewrites up to `limit` records of the pending migration and
+/// returns whether the migration is complete
+fn migrate_records(storage: &mut dyn Storage, env: &Env, limit: Option<usize>) -> StdResult<bool> {
+    let mut migration = match MIGRATION.may_load(storage)? {
+        Some(migration) => migration,
+        None => return Ok(true),
+    };
+
+    let limit = limit.unwrap_or(usize::MAX);
+    let start = migration.last_name.as_ref().map(|name| Bound::exclusive(name.as_bytes()));
+    let records: Vec<(Vec<u8>, NameRecord)> = if migration.legacy_layout {
+        NAME_RECORDS_V1
+            .range(storage, start, None, Order::Ascending)
+            .take(limit)
+            .map(|item| item.map(|(key, record)| (key, record.into())))
+            .collect::<StdResult<_>>()?
+    } else {
+        name_resolver()
+            .range(storage, start, None, Order::Ascending)
+            .take(limit)
+            .collect::<StdResult<_>>()?
+    };
+
+    for (key, record) in &records {
+        // saving again writes the record in the current layout
+        name_resolver().save(storage, key, record)?;
+        // names registered before the owner snapshots are known from now on
+        if OWNERS.may_load(storage, key)?.is_none() {
+            OWNERS.save(storage, key, &Some(record.owner.clone()), env.block.height)?;
+        }
+        if migration.backfill_counters {
+            update_owner_stats(storage, &record.owner, |stats| stats.name_count += 1)?;
+            TOTAL_NAMES.update(storage, |total| -> StdResult<_> { Ok(total + 1) })?;
+            TOTAL_REGISTRATIONS.update(storage, |total| -> StdResult<_> { Ok(total + 1) })?;
+        }
+    }
+    migration.migrated += records.len() as u64;
+
+    let done = records.len() < limit;
+    if done {
+        MIGRATION.remove(storage);
+    } else {
+        migration.last_name = records
+            .last()
+            .map(|(key, _)| String::from_utf8_lossy(key).into_owned());
+        MIGRATION.save(storage, &migration)?;
+    }
+    Ok(done)
+}
+
+/// StoredFields tells which of the newer NameRecord fields a stored record
+/// actually contains, it reads the same namespace as name_resolver
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "cosmwasm_schema::serde")]
+struct StoredFields {
+    #[serde(default)]
+    verified: Present,
+    #[serde(default)]
+    accept_attestations: Present,
+    #[serde(default)]
+    expires_at: Present,
+    #[serde(default)]
+    socials: Present,
+    #[serde(default)]
+    content_hash: Present,
 }
 
+impl StoredFields {
+    fn complete(&self) -> bool {
+        self.verified.0 && self.accept_attestations.0 && self.expires_at.0 && self.socials.0 && self.content_hash.0
+    }
+}
+
+#[derive(Default, Serialize)]
+#[serde(crate = "cosmwasm_schema::serde")]
+struct Present(bool);
+
+impl<'de> Deserialize<'de> for Present {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        IgnoredAny::deserialize(deserializer).map(|_| Present(true))
+    }
+}
+
+const STORED_FIELDS: Map<&[u8], StoredFields> = Map::new("name_resolver");
+
+#[allow(clippy::too_many_arguments)]
 pub fn execute_register(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     name: String,
     bio: String,
     website: String,
+    round_up: bool,
+    referrer: Option<String>,
 ) -> Result<Response, ContractError> {
-    // we only need to check here - at point of registration
-    validate_name(&name)?;
+    assert_registration_open(deps.as_ref(), &env, &info.sender)?;
     let config = CONFIG.load(deps.storage)?;
-    assert_sent_sufficient_coin(&info.funds, config.purchase_price)?;
+    assert_registration_quota(deps.storage, &config, &env, &info.sender, 1)?;
+    let referrer = referrer.map(|referrer| deps.api.addr_validate(&referrer)).transpose()?;
+    if referrer.as_ref() == Some(&info.sender) {
+        return Err(ContractError::SelfReferral {});
+    }
+    let prices = register_prices(deps.as_ref(), &env, &config, &name, &info.sender)?;
+    let prices = discount_hold(deps.storage, &env, &name, &info.sender, prices)?;
+    assert_sent_sufficient_coin(&info.funds, &prices)?;
+    let (paid, surplus) = split_payment(deps.storage, &config, &prices, &info, round_up)?;
 
-    let key = name.as_bytes();
-    let bio_length = bio.len() as u64;
-    let website_length = website.len() as u64;
+    // the referral reward leaves the contract straight away, only the rest of the price is collected
+    let mut fees = paid.clone();
+    let reward: Vec<Coin> = match &referrer {
+        Some(_) => paid
+            .iter()
+            .map(|fee| coin(fee.amount.multiply_ratio(config.referral_percent, 100u64).u128(), &fee.denom))
+            .filter(|reward| !reward.amount.is_zero())
+            .collect(),
+        None => vec![],
+    };
+    subtract_coins(&mut fees, &reward);
+    let (expires_at, burned) =
+        register_name(deps.storage, &env, info.sender.clone(), name.clone(), bio, website, &fees)?;
+    update_owner_stats(deps.storage, &info.sender, |stats| add_coins(&mut stats.total_spent, &reward))?;
 
-    if (bio_length) > MAX_BIO_LENGTH {
-        return Err(ContractError::BioTooLong {
-            bio_length,
-            max_length: MAX_BIO_LENGTH,
-        })
+    let mut response = Response::new().add_messages(surplus).add_messages(burn_messages(burned));
+    if let Some(referrer) = referrer {
+        if !reward.is_empty() {
+            response = response.add_message(BankMsg::Send { to_address: referrer.to_string(), amount: reward.clone() });
+        }
+        response = response
+            .add_attribute("referrer", referrer)
+            .add_attribute("referral_reward", coins_attribute(&reward));
     }
+    Ok(response
+        .add_attribute("action", "register")
+        .add_attribute("name", name)
+        .add_attribute("owner", info.sender)
+        .add_attribute("fees", coins_attribute(&paid))
+        .add_attribute("expires_at", expires_at.map(|expires_at| expires_at.seconds().to_string()).unwrap_or_default()))
+}
 
-    if (website_length) > MAX_WEBSITE_LENGTH {
-        return Err(ContractError::WebsiteTooLong {
-            website_length,
-            max_length: MAX_WEBSITE_LENGTH,
+/// execute_register_batch registers every name of `registrations` to the sender, or fails without registering any
+pub fn execute_register_batch(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    registrations: Vec<RegistrationData>,
+) -> Result<Response, ContractError> {
+    if registrations.len() > MAX_REGISTER_BATCH {
+        return Err(ContractError::TooManyNames {
+            count: registrations.len() as u64,
+            max: MAX_REGISTER_BATCH as u64,
+        });
+    }
+    assert_registration_open(deps.as_ref(), &env, &info.sender)?;
+    let config = CONFIG.load(deps.storage)?;
+    assert_registration_quota(deps.storage, &config, &env, &info.sender, registrations.len() as u64)?;
+    let prices = registrations
+        .iter()
+        .map(|registration| {
+            let prices = register_prices(deps.as_ref(), &env, &config, &registration.name, &info.sender)?;
+            discount_hold(deps.storage, &env, &registration.name, &info.sender, prices)
         })
+        .collect::<StdResult<Vec<_>>>()?;
+    let Payment { charge, change } = charge(&info.funds, &batch_prices(&prices)?)?;
+
+    let names: Vec<String> = registrations.iter().map(|registration| registration.name.clone()).collect();
+    let mut burned = vec![];
+    for (registration, prices) in registrations.into_iter().zip(prices) {
+        // every name pays its own price in the denom the batch is paid with
+        let paid: Vec<Coin> = prices
+            .into_iter()
+            .filter(|price| charge.as_ref().is_some_and(|charge| charge.denom == price.denom))
+            .filter(|price| !price.amount.is_zero())
+            .collect();
+        let (_, name_burned) = register_name(
+            deps.storage,
+            &env,
+            info.sender.clone(),
+            registration.name,
+            registration.bio,
+            registration.website,
+            &paid,
+        )?;
+        add_coins(&mut burned, &name_burned);
     }
 
-    if (NAME_RESOLVER.may_load(deps.storage, key)?).is_some() {
-        // name is already taken
-        return Err(ContractError::NameTaken { name });
+    let mut response = Response::new().add_messages(burn_messages(burned));
+    if !change.is_empty() {
+        response = response.add_message(BankMsg::Send { to_address: info.sender.to_string(), amount: change });
     }
+    Ok(response
+        .add_attribute("action", "register_batch")
+        .add_attribute("names", names.join(",")))
+}
 
-    let record = NameRecord {
-        owner: info.sender,
-        bio: bio,
-        website: website
+/// launch_phase is the phase of the launch at `env`, and the phase that follows it
+fn launch_phase(storage: &dyn Storage, env: &Env) -> StdResult<(LaunchPhase, Option<PhaseStart>)> {
+    if let Some(phase) = CONFIG.load(storage)?.launch_phase {
+        return Ok((phase, None));
+    }
+    let schedule = LAUNCH_SCHEDULE.may_load(storage)?.unwrap_or_default();
+    let started = schedule.iter().take_while(|start| start.starts_at <= env.block.time).count();
+    let phase = match (schedule.is_empty(), started) {
+        (true, _) => LaunchPhase::Public,
+        (false, 0) => LaunchPhase::ReservedOnly,
+        _ => schedule[started - 1].phase,
     };
+    Ok((phase, schedule.get(started).cloned()))
+}
 
-    // name is available
-    NAME_RESOLVER.save(deps.storage, key, &record)?;
+/// assert_registration_open returns an error unless the launch phase lets `sender` register a name itself
+fn assert_registration_open(deps: Deps, env: &Env, sender: &Addr) -> Result<(), ContractError> {
+    let (phase, _) = launch_phase(deps.storage, env)?;
+    match phase {
+        LaunchPhase::Public => Ok(()),
+        LaunchPhase::Allowlist if ALLOWLIST.has(deps.storage, sender) || holds_gate_token(deps, sender)? => Ok(()),
+        _ => Err(ContractError::RegistrationNotOpen { phase }),
+    }
+}
 
-    Ok(Response::default())
+/// assert_registration_quota returns an error if `sender` registering `count` more names now would exceed the
+/// registration limits. A batch counts as a single registration for the cooldown
+fn assert_registration_quota(
+    storage: &dyn Storage,
+    config: &Config,
+    env: &Env,
+    sender: &Addr,
+    count: u64,
+) -> Result<(), ContractError> {
+    let stats = OWNER_STATS.may_load(storage, sender)?.unwrap_or_default();
+    if let Some(max_names_per_address) = config.max_names_per_address {
+        if stats.name_count + count > max_names_per_address {
+            return Err(ContractError::NameLimitReached { max_names_per_address });
+        }
+    }
+    if let Some(until) = next_registration_at(&stats, config, env) {
+        return Err(ContractError::RegistrationCooldown { until });
+    }
+    Ok(())
 }
 
-pub fn execute_transfer(
-    deps: DepsMut,
-    _env: Env,
-    info: MessageInfo,
-    name: String,
-    to: String,
-) -> Result<Response, ContractError> {
-    let config = CONFIG.load(deps.storage)?;
-    assert_sent_sufficient_coin(&info.funds, config.transfer_price)?;
+/// next_registration_at is when the owner of `stats` may register again, None if it may right away
+fn next_registration_at(stats: &OwnerStats, config: &Config, env: &Env) -> Option<Timestamp> {
+    let until = stats.last_registration?.plus_seconds(config.registration_cooldown_seconds?);
+    (env.block.time < until).then_some(until)
+}
 
-    let new_owner = deps.api.addr_validate(&to)?;
-    let key = name.as_bytes();
-    NAME_RESOLVER.update(deps.storage, key, |record| {
-        if let Some(mut record) = record {
-            if info.sender != record.owner {
-                return Err(ContractError::Unauthorized {});
-            }
+/// The queries of cw721 and cw20 contracts sent to check the allowlist gate
+#[derive(Serialize)]
+#[serde(crate = "cosmwasm_schema::serde", rename_all = "snake_case")]
+enum GateQuery {
+    Tokens { owner: String, limit: u32 },
+    Balance { address: String },
+}
 
-            record.owner = new_owner.clone();
-            Ok(record)
-        } else {
-            Err(ContractError::NameNotExists { name: name.clone() })
+#[derive(Deserialize)]
+#[serde(crate = "cosmwasm_schema::serde")]
+struct GateTokens {
+    tokens: Vec<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(crate = "cosmwasm_schema::serde")]
+struct GateBalance {
+    balance: Uint128,
+}
+
+/// holds_gate_token tells whether `sender` holds the token of the allowlist gate.
+/// A token contract that fails to answer lets nobody in
+fn holds_gate_token(deps: Deps, sender: &Addr) -> StdResult<bool> {
+    let holds = match CONFIG.load(deps.storage)?.allowlist_gate {
+        None => false,
+        Some(TokenGate::Cw721 { contract }) => {
+            let query = GateQuery::Tokens { owner: sender.to_string(), limit: 1 };
+            deps.querier
+                .query_wasm_smart::<GateTokens>(contract, &query)
+                .is_ok_and(|res| !res.tokens.is_empty())
         }
-    })?;
-    Ok(Response::default())
+        Some(TokenGate::Cw20 { contract, min_balance }) => {
+            let query = GateQuery::Balance { address: sender.to_string() };
+            deps.querier
+                .query_wasm_smart::<GateBalance>(contract, &query)
+                .is_ok_and(|res| res.balance >= min_balance && !res.balance.is_zero())
+        }
+    };
+    Ok(holds)
 }
 
-pub fn execute_edit(
+/// execute_register_sponsored registers a name for the sender, paid from the sponsor's balance
+pub fn execute_register_sponsored(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     name: String,
     bio: String,
     website: String,
+    sponsor: String,
 ) -> Result<Response, ContractError> {
-    let config = CONFIG.load(deps.storage)?;
-    assert_sent_sufficient_coin(&info.funds, config.edit_price)?;
-
-    let key = name.as_bytes();
-    let bio_length = bio.len() as u64;
-    let website_length = website.len() as u64;
-
-    NAME_RESOLVER.update(deps.storage, key, |record| {
-        if let Some(mut record) = record {
-            if info.sender != record.owner {
-                return Err(ContractError::Unauthorized {});
-            }
+    let sponsor_addr = deps.api.addr_validate(&sponsor)?;
+    let mut sponsorship = match SPONSORSHIPS.may_load(deps.storage, &sponsor_addr)? {
+        Some(sponsorship) => sponsorship,
+        None => return Err(ContractError::SponsorshipNotExists { sponsor }),
+    };
+    if !is_sponsored(deps.storage, &sponsor_addr, &sponsorship, &info.sender) {
+        return Err(ContractError::NotSponsored { sponsor });
+    }
+    assert_registration_open(deps.as_ref(), &env, &info.sender)?;
 
-            if (bio_length) > MAX_BIO_LENGTH {
-                return Err(ContractError::BioTooLong {
-                    bio_length,
-                    max_length: MAX_BIO_LENGTH,
-                })
-            }
+    let config = CONFIG.load(deps.storage)?;
+    assert_staker(deps.as_ref(), &config, &info.sender)?;
+    assert_registration_quota(deps.storage, &config, &env, &info.sender, 1)?;
+    let accepted = register_prices(deps.as_ref(), &env, &config, &name, &info.sender)?;
+    let accepted = discount_hold(deps.storage, &env, &name, &info.sender, accepted)?;
+    let mut burned = vec![];
+    if !accepted.is_empty() {
+        // the first accepted price the balance covers is paid
+        let cost = match charged_coin(&sponsorship.balance, &accepted) {
+            Some(cost) if deduct_coin(&mut sponsorship.balance, &cost) => cost,
+            _ => return Err(ContractError::InsufficientSponsorFunds { sponsor }),
+        };
+        // the sponsor's funds pay the registration, they are revenue now
+        release_escrow(deps.storage, std::slice::from_ref(&cost))?;
+        burned = collect_fees(deps.storage, &config, &[cost])?;
+    }
+    SPONSORSHIPS.save(deps.storage, &sponsor_addr, &sponsorship)?;
+    SPONSORED_CLAIMS.save(deps.storage, (&sponsor_addr, &info.sender), &Empty {})?;
 
-            if (website_length) > MAX_WEBSITE_LENGTH {
-                return Err(ContractError::WebsiteTooLong {
-                    website_length,
-                    max_length: MAX_WEBSITE_LENGTH,
-                })
-            }
+    let (_, hold_burned) = register_name(deps.storage, &env, info.sender, name.clone(), bio, website, &[])?;
+    add_coins(&mut burned, &hold_burned);
 
-            record.bio = bio.clone();
-            record.website = website.clone();
-            Ok(record)
-        } else {
-            Err(ContractError::NameNotExists { name: name.clone() })
-        }
-    })?;
-    Ok(Response::default())
+    Ok(Response::new()
+        .add_messages(burn_messages(burned))
+        .add_attribute("action", "register_sponsored")
+        .add_attribute("name", name)
+        .add_attribute("sponsor", sponsor_addr))
 }
 
-pub fn execute_edit_conf(
+/// execute_claim_promo_name registers a committed promo name for free to whoever reveals its secret
+pub fn execute_claim_promo_name(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
-    purchase_price: Option<Coin>,
-    transfer_price: Option<Coin>,
-    edit_price: Option<Coin>,
+    name: String,
+    secret: String,
+    bio: String,
+    website: String,
 ) -> Result<Response, ContractError> {
-    let get_config = CONFIG.load(deps.storage)?;
-    assert_sent_sufficient_coin(&info.funds, get_config.transfer_price)?;
-
-    if get_config.owner != info.sender {
-        return Err(ContractError::Unauthorized {});
+    let commitment = promo_commitment(&name, &secret);
+    if !PROMO_COMMITMENTS.has(deps.storage, &commitment) {
+        return Err(ContractError::InvalidPromoSecret {});
     }
+    let config = CONFIG.load(deps.storage)?;
+    assert_staker(deps.as_ref(), &config, &info.sender)?;
+    PROMO_COMMITMENTS.remove(deps.storage, &commitment);
 
-    // CONFIG.update(deps.storage, FnOnce::<&Config,>);
-    CONFIG.update(deps.storage, |mut config| -> StdResult<_> {
-        config.purchase_price = purchase_price.clone();
-        config.transfer_price = transfer_price.clone();
-        config.edit_price = edit_price.clone();
-        Ok(config)
-    })?;
+    let (_, burned) = register_name(deps.storage, &env, info.sender.clone(), name.clone(), bio, website, &[])?;
 
-    Ok(Response::default())
+    Ok(Response::new()
+        .add_messages(burn_messages(burned))
+        .add_attribute("action", "claim_promo_name")
+        .add_attribute("name", name)
+        .add_attribute("owner", info.sender))
 }
 
-fn execute_refund(deps: DepsMut, env: Env, info: MessageInfo) -> Result<Response, ContractError> {
-    let balance = deps.querier.query_all_balances(&env.contract.address)?;
-    let config = CONFIG.load(deps.storage)?;
+/// promo_commitment is the hash the admin commits to for a promo name and its secret
+fn promo_commitment(name: &str, secret: &str) -> Vec<u8> {
+    Sha256::digest(format!("{}:{}", name, secret).as_bytes()).to_vec()
+}
 
-    if config.owner != info.sender {
-        return Err(ContractError::Unauthorized {});
-    }
+/// assert_staker returns an error if `address` delegates less than the configured minimum
+fn assert_staker(deps: Deps, config: &Config, address: &Addr) -> Result<(), ContractError> {
+    let min_delegation = match &config.min_delegation {
+        Some(min_delegation) => min_delegation,
+        None => return Ok(()),
+    };
 
-    Ok(send_tokens(balance, "refund", config.owner))
+    let delegated: Uint128 = deps
+        .querier
+        .query_all_delegations(address)?
+        .into_iter()
+        .filter(|delegation| delegation.amount.denom == min_delegation.denom)
+        .map(|delegation| delegation.amount.amount)
+        .sum();
+    if delegated < min_delegation.amount {
+        return Err(ContractError::InsufficientDelegation {
+            min_delegation: min_delegation.clone(),
+        });
+    }
+    Ok(())
 }
 
-fn send_tokens(amount: Vec<Coin>, action: &str, address: Addr) -> Response {
-    Response::new()
-        .add_message(BankMsg::Send {
-            to_address: address.to_string(),
-            amount,
-        })
-        .add_attribute("action", action)
-        .add_attribute("to", address.to_string())
+/// is_sponsored tells whether `address` may still register a name on the sponsorship
+fn is_sponsored(storage: &dyn Storage, sponsor: &Addr, sponsorship: &Sponsorship, address: &Addr) -> bool {
+    let listed = sponsorship.open || SPONSORED_ADDRESSES.has(storage, (sponsor, address));
+    listed && !SPONSORED_CLAIMS.has(storage, (sponsor, address))
 }
 
-#[cfg_attr(not(feature = "library"), entry_point)]
-pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
-    match msg {
-        QueryMsg::ResolveRecord { name } => query_resolver(deps, env, name),
-        QueryMsg::Config {} => to_binary::<ConfigResponse>(&CONFIG.load(deps.storage)?.into()),
+/// register_name runs every registration check except payment and stores the new record.
+/// `paid` is what the owner paid for it, sponsored registrations pass nothing.
+/// It returns when the new registration expires, if it does.
+fn register_name(
+    storage: &mut dyn Storage,
+    env: &Env,
+    owner: Addr,
+    name: String,
+    bio: String,
+    website: String,
+    paid: &[Coin],
+) -> Result<(Option<Timestamp>, Vec<Coin>), ContractError> {
+    if let Some(successor) = SUCCESSOR.may_load(storage)? {
+        return Err(ContractError::Sunset { successor: successor.into_string() });
     }
-}
 
-fn query_resolver(deps: Deps, _env: Env, name: String) -> StdResult<Binary> {
-    let key = name.as_bytes();
+    // we only need to check here - at point of registration
+    let config = CONFIG.load(storage)?;
+    validate_name(&name, &config.name_policy)?;
 
-    let address = match NAME_RESOLVER.may_load(deps.storage, key)? {
-        Some(record) => Some(String::from(&record.owner)),
+    let key = name.as_bytes();
+    validate_profile(&bio, &website, &config.name_policy)?;
+    if AUCTIONS.has(storage, key) {
+        return Err(ContractError::NameInAuction { name });
+    }
+    if RESERVED.has(storage, key) {
+        return Err(ContractError::NameReserved { name });
+    }
+    if let Some(until) = cooldown_end(storage, &config, env, key)? {
+        if TOMBSTONES.load(storage, key)?.previous_owner != owner {
+            return Err(ContractError::NameInCooldown { name, until });
+        }
+    }
+    let mut burned = vec![];
+    if let Some(hold) = HOLDS.may_load(storage, key)? {
+        if hold.holder != owner && env.block.time < hold.expires_at {
+            return Err(ContractError::NameHeld { name, until: hold.expires_at });
+        }
+        // the holder's deposit went towards the price, others register once the hold expired and forfeited it
+        burned = settle_hold(storage, &config, key, hold)?;
+    }
+
+    if let Some(previous) = name_resolver().may_load(storage, key)? {
+        // name is already taken
+        if !is_released(&previous, &config, env) {
+            return Err(ContractError::NameTaken { name });
+        }
+        // expired names are free again once their grace period is over, nothing of the old owner is kept
+        remove_name(storage, env, &name, &previous, &owner, RemovalReason::Expired)?;
+    }
+    let total = TOTAL_NAMES.may_load(storage)?.unwrap_or_default();
+    if let Some(max_supply) = config.max_supply {
+        if total >= max_supply {
+            return Err(ContractError::MaxSupplyReached { max_supply });
+        }
+    }
+    TOTAL_NAMES.save(storage, &(total + 1))?;
+    let registrations = TOTAL_REGISTRATIONS.may_load(storage)?.unwrap_or_default();
+    TOTAL_REGISTRATIONS.save(storage, &(registrations + 1))?;
+
+    update_owner_stats(storage, &owner, |stats| {
+        stats.name_count += 1;
+        stats.first_registration.get_or_insert(env.block.time);
+        stats.last_registration = Some(env.block.time);
+        add_coins(&mut stats.total_spent, paid);
+    })?;
+    add_coins(&mut burned, &collect_fees(storage, &config, paid)?);
+
+    OWNERS.save(storage, name.as_bytes(), &Some(owner.clone()), env.block.height)?;
+    REGISTRANTS.save(storage, name.as_bytes(), &owner)?;
+    let duration = config.registration_duration.unwrap_or_default();
+    record_paid_term(storage, env, key, None, paid, duration)?;
+    record_history(storage, env, &name, &owner, HistoryEvent::Registered { owner: owner.clone() })?;
+    let record = NameRecord {
+        owner,
+        bio,
+        website,
+        verified: false,
+        accept_attestations: false,
+        cache_control: CacheControl::Standard,
+        expires_at: config.registration_duration.map(|duration| env.block.time.plus_seconds(duration)),
+        socials: Socials::default(),
+        content_hash: None,
+    };
+
+    // name is available
+    name_resolver().save(storage, key, &record)?;
+
+    Ok((record.expires_at, burned))
+}
+
+/// execute_start_auction puts an available name up for auction, it can't be registered directly anymore
+pub fn execute_start_auction(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    name: String,
+    min_bid: Coin,
+    duration: u64,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.owner != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+    if let Some(successor) = SUCCESSOR.may_load(deps.storage)? {
+        return Err(ContractError::Sunset { successor: successor.into_string() });
+    }
+    validate_name(&name, &config.name_policy)?;
+
+    let key = name.as_bytes();
+    if AUCTIONS.has(deps.storage, key) {
+        return Err(ContractError::NameInAuction { name });
+    }
+    if let Some(record) = name_resolver().may_load(deps.storage, key)? {
+        if !is_released(&record, &config, &env) {
+            return Err(ContractError::NameTaken { name });
+        }
+    }
+
+    let auction = Auction {
+        min_bid,
+        ends_at: env.block.time.plus_seconds(duration),
+    };
+    AUCTIONS.save(deps.storage, key, &auction)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "start_auction")
+        .add_attribute("name", name)
+        .add_attribute("min_bid", auction.min_bid.to_string())
+        .add_attribute("ends_at", auction.ends_at.to_string()))
+}
+
+/// execute_place_bid holds the sent funds as the new highest bid and refunds the one it outbids
+pub fn execute_place_bid(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    name: String,
+) -> Result<Response, ContractError> {
+    let key = name.as_bytes();
+    let auction = match AUCTIONS.may_load(deps.storage, key)? {
+        Some(auction) => auction,
+        None => return Err(ContractError::AuctionNotExists { name }),
+    };
+    if env.block.time >= auction.ends_at {
+        return Err(ContractError::AuctionEnded { name });
+    }
+
+    let previous = BIDS.may_load(deps.storage, key)?;
+    // a bid must beat the highest one, or be at least min_bid when it is the first
+    let min_bid = match &previous {
+        Some(bid) => Coin { denom: bid.amount.denom.clone(), amount: bid.amount.amount + Uint128::one() },
+        None => auction.min_bid,
+    };
+    let amount = match info.funds.as_slice() {
+        [amount] if amount.denom == min_bid.denom => amount.clone(),
+        _ => return Err(ContractError::InsufficientFundsSend {}),
+    };
+    if amount.amount < min_bid.amount {
+        return Err(ContractError::BidTooLow { min_bid });
+    }
+
+    hold_escrow(deps.storage, std::slice::from_ref(&amount))?;
+    BIDS.save(deps.storage, key, &Bid { bidder: info.sender.clone(), amount: amount.clone() })?;
+
+    let mut response = Response::new();
+    if let Some(outbid) = previous {
+        let refund = vec![outbid.amount];
+        release_escrow(deps.storage, &refund)?;
+        response = response.add_message(BankMsg::Send { to_address: outbid.bidder.into_string(), amount: refund });
+    }
+    Ok(response
+        .add_attribute("action", "place_bid")
+        .add_attribute("name", name)
+        .add_attribute("bidder", info.sender)
+        .add_attribute("amount", amount.to_string()))
+}
+
+/// execute_close_auction ends an auction, registering the name to the highest bidder if there is one
+pub fn execute_close_auction(
+    deps: DepsMut,
+    env: Env,
+    _info: MessageInfo,
+    name: String,
+) -> Result<Response, ContractError> {
+    let key = name.as_bytes();
+    let auction = match AUCTIONS.may_load(deps.storage, key)? {
+        Some(auction) => auction,
+        None => return Err(ContractError::AuctionNotExists { name }),
+    };
+    if env.block.time < auction.ends_at {
+        return Err(ContractError::AuctionNotEnded { name, ends_at: auction.ends_at });
+    }
+    let settled = settle_auction(deps.storage, &env, &name, false)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "close_auction")
+        .add_attribute("name", name)
+        .add_submessages(settled.messages)
+        .add_attributes(settled.attributes))
+}
+
+/// settle_auction closes the auction of `name`, registering the name to the highest bidder if there is one.
+/// `sweeping` refunds the bidder whatever stops the registration, so the end-block sweep never fails on a name
+fn settle_auction(storage: &mut dyn Storage, env: &Env, name: &str, sweeping: bool) -> Result<Response, ContractError> {
+    let key = name.as_bytes();
+    AUCTIONS.remove(storage, key);
+
+    let response = Response::new();
+    // nobody bid, the name can be registered like any other
+    let bid = match BIDS.may_load(storage, key)? {
+        Some(bid) => bid,
+        None => return Ok(response),
+    };
+    BIDS.remove(storage, key);
+    let paid = vec![bid.amount];
+    release_escrow(storage, &paid)?;
+
+    let winner = bid.bidder.clone();
+    let registered = register_name(storage, env, winner, name.to_string(), String::new(), String::new(), &paid);
+    let err = match registered {
+        Ok((_, burned)) => {
+            update_name_stats(storage, key, |stats| {
+                stats.sales += 1;
+                add_coins(&mut stats.sale_volume, &paid);
+            })?;
+            return Ok(response.add_messages(burn_messages(burned)).add_attribute("winner", bid.bidder));
+        }
+        Err(err) => err,
+    };
+    // the contract was sunset or filled up since, the winner gets the bid back
+    if !sweeping && !matches!(err, ContractError::Sunset { .. } | ContractError::MaxSupplyReached { .. }) {
+        return Err(err);
+    }
+    Ok(response
+        .add_message(BankMsg::Send { to_address: bid.bidder.to_string(), amount: paid })
+        .add_attribute("refunded", bid.bidder))
+}
+
+/// execute_renew extends the registration of a name of the sender by one registration period
+pub fn execute_renew(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    name: String,
+) -> Result<Response, ContractError> {
+    if let Some(record) = name_resolver().may_load(deps.storage, name.as_bytes())? {
+        if record.owner != info.sender {
+            return Err(ContractError::Unauthorized {});
+        }
+    }
+    let (expires_at, response) = renew_name(deps, &env, &info, &name, 1)?;
+
+    Ok(response
+        .add_attribute("action", "renew")
+        .add_attribute("name", name)
+        .add_attribute("expires_at", expires_at.seconds().to_string()))
+}
+
+/// execute_renew_for extends the registration of any name by `years` registration periods, paid by the sender
+pub fn execute_renew_for(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    name: String,
+    years: u64,
+) -> Result<Response, ContractError> {
+    if years == 0 {
+        return Err(ContractError::InvalidRenewalYears {});
+    }
+    let (expires_at, response) = renew_name(deps, &env, &info, &name, years)?;
+
+    Ok(response
+        .add_attribute("action", "renew_for")
+        .add_attribute("name", name)
+        .add_attribute("payer", info.sender)
+        .add_attribute("years", years.to_string())
+        .add_attribute("expires_at", expires_at.seconds().to_string()))
+}
+
+/// renew_name extends the registration of a name by `periods` registration periods for the renewal price of
+/// each, paid by the sender. It returns the new expiry and a response with the payment messages
+fn renew_name(
+    deps: DepsMut,
+    env: &Env,
+    info: &MessageInfo,
+    name: &str,
+    periods: u64,
+) -> Result<(Timestamp, Response), ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let mut prices = action_prices(deps.as_ref(), &config, PricedAction::Renew, name, &info.sender)?;
+    for price in prices.iter_mut() {
+        price.amount = price.amount.checked_mul(periods.into()).map_err(StdError::overflow)?;
+    }
+    assert_sent_sufficient_coin(&info.funds, &prices)?;
+
+    let key = name.as_bytes();
+    let mut record = match name_resolver().may_load(deps.storage, key)? {
+        Some(record) => record,
+        None => return Err(ContractError::NameNotExists { name: name.to_string() }),
+    };
+    if is_released(&record, &config, env) {
+        return Err(ContractError::NameExpired { name: name.to_string() });
+    }
+    let (previous_expiry, duration) = match (record.expires_at, config.registration_duration) {
+        (Some(expires_at), Some(duration)) => (expires_at, duration),
+        _ => return Err(ContractError::NameNotExpiring { name: name.to_string() }),
+    };
+
+    // renewing during the grace period extends from the old expiry, not from now
+    let expires_at = previous_expiry.plus_seconds(duration.saturating_mul(periods));
+    if let Some(horizon) = config.max_renewal_horizon {
+        let max_expires_at = env.block.time.plus_seconds(horizon);
+        if expires_at > max_expires_at {
+            return Err(ContractError::RenewalBeyondHorizon { max_expires_at });
+        }
+    }
+    record.expires_at = Some(expires_at);
+    name_resolver().save(deps.storage, key, &record)?;
+
+    let (paid, surplus) = split_payment(deps.storage, &config, &prices, info, false)?;
+    // gifts of other payers extend the term without adding to what the owner gets back on release
+    let owner_paid = if record.owner == info.sender { paid.clone() } else { vec![] };
+    let seconds = expires_at.seconds().saturating_sub(previous_expiry.seconds().max(env.block.time.seconds()));
+    record_paid_term(deps.storage, env, key, Some(previous_expiry), &owner_paid, seconds)?;
+    update_owner_stats(deps.storage, &info.sender, |stats| add_coins(&mut stats.total_spent, &paid))?;
+    let burned = collect_fees(deps.storage, &config, &paid)?;
+    update_name_stats(deps.storage, key, |stats| stats.renewals += periods)?;
+    record_history(deps.storage, env, name, &info.sender, HistoryEvent::Renewed { expires_at })?;
+
+    let response = Response::new().add_messages(surplus).add_messages(burn_messages(burned));
+    Ok((expires_at, response))
+}
+
+/// execute_release removes a name of the sender from the registry, refunding the unused registration time
+pub fn execute_release(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    name: String,
+) -> Result<Response, ContractError> {
+    execute_release_batch(deps, env, info, vec![name])
+}
+
+/// execute_release_batch releases many names of the sender with a single refund transfer
+pub fn execute_release_batch(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    names: Vec<String>,
+) -> Result<Response, ContractError> {
+    if names.len() > MAX_RELEASE_BATCH {
+        return Err(ContractError::TooManyNames {
+            count: names.len() as u64,
+            max: MAX_RELEASE_BATCH as u64,
+        });
+    }
+    let config = CONFIG.load(deps.storage)?;
+
+    let mut refund = vec![];
+    let mut response = Response::new();
+    for name in &names {
+        let record = match name_resolver().may_load(deps.storage, name.as_bytes())? {
+            Some(record) => record,
+            None => return Err(ContractError::NameNotExists { name: name.clone() }),
+        };
+        if record.owner != info.sender {
+            return Err(ContractError::Unauthorized {});
+        }
+        assert_unlocked(deps.storage, name)?;
+        add_coins(&mut refund, &release_refund(deps.storage, &config, &record, name, &env)?);
+
+        remove_name(deps.storage, &env, name, &record, &info.sender, RemovalReason::Released)?;
+        response = notify_watchers(deps.storage, name, response)?;
+    }
+
+    // refunds come out of revenue, never out of funds held for other users
+    let available = revenue(deps.as_ref(), &env)?;
+    let refund: Vec<Coin> = refund
+        .into_iter()
+        .filter_map(|coin| {
+            let held = available.iter().find(|c| c.denom == coin.denom).map(|c| c.amount).unwrap_or_default();
+            let amount = coin.amount.min(held);
+            (!amount.is_zero()).then_some(Coin { denom: coin.denom, amount })
+        })
+        .collect();
+    if !refund.is_empty() {
+        response = response.add_message(BankMsg::Send { to_address: info.sender.to_string(), amount: refund });
+    }
+
+    let action = if names.len() == 1 { "release" } else { "release_batch" };
+    Ok(response
+        .add_attribute("action", action)
+        .add_attribute("names", names.join(","))
+        .add_attribute("owner", info.sender))
+}
+
+/// release_refund is the part of what was paid for the name that pays for registration time left unused.
+/// Names registered for free refund nothing, burned fees are gone and never refunded
+fn release_refund(
+    storage: &dyn Storage,
+    config: &Config,
+    record: &NameRecord,
+    name: &str,
+    env: &Env,
+) -> StdResult<Vec<Coin>> {
+    if config.burn_fees {
+        return Ok(vec![]);
+    }
+    match (record.expires_at, PAID_TERMS.may_load(storage, name.as_bytes())?) {
+        (Some(expires_at), Some(term)) => Ok(unused_payment(&term, expires_at, env)),
+        _ => Ok(vec![]),
+    }
+}
+
+/// unused_payment is the part of `term` paying for the time between now and `expires_at`
+fn unused_payment(term: &PaidTerm, expires_at: Timestamp, env: &Env) -> Vec<Coin> {
+    if term.seconds == 0 {
+        return vec![];
+    }
+    let remaining = expires_at.seconds().saturating_sub(env.block.time.seconds()).min(term.seconds);
+    term.paid
+        .iter()
+        .map(|paid| coin(paid.amount.multiply_ratio(remaining, term.seconds).u128(), &paid.denom))
+        .filter(|unused| !unused.amount.is_zero())
+        .collect()
+}
+
+/// record_paid_term adds `paid` for `seconds` more registration to the term of the name stored under `key`.
+/// What is left of the previous term is carried over at its unused value
+fn record_paid_term(
+    storage: &mut dyn Storage,
+    env: &Env,
+    key: &[u8],
+    previous_expiry: Option<Timestamp>,
+    paid: &[Coin],
+    seconds: u64,
+) -> StdResult<()> {
+    let mut term = PaidTerm { paid: vec![], seconds: 0 };
+    if let (Some(previous), Some(expires_at)) = (PAID_TERMS.may_load(storage, key)?, previous_expiry) {
+        term.paid = unused_payment(&previous, expires_at, env);
+        term.seconds = expires_at.seconds().saturating_sub(env.block.time.seconds()).min(previous.seconds);
+    }
+    add_coins(&mut term.paid, &paid.iter().filter(|coin| !coin.amount.is_zero()).cloned().collect::<Vec<_>>());
+    term.seconds += seconds;
+    if term.paid.is_empty() {
+        PAID_TERMS.remove(storage, key);
+        return Ok(());
+    }
+    PAID_TERMS.save(storage, key, &term)
+}
+
+/// execute_force_release removes any name from the registry, only the admin can call it
+pub fn execute_force_release(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    name: String,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.owner != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+    let record = match name_resolver().may_load(deps.storage, name.as_bytes())? {
+        Some(record) => record,
+        None => return Err(ContractError::NameNotExists { name }),
+    };
+
+    remove_name(deps.storage, &env, &name, &record, &info.sender, RemovalReason::Revoked)?;
+    let response = notify_watchers(deps.storage, &name, Response::new())?;
+
+    Ok(response
+        .add_attribute("action", "force_release")
+        .add_attribute("name", name)
+        .add_attribute("owner", record.owner))
+}
+
+/// remove_name deletes a name with everything attached to it, leaving a tombstone, and updates the counters.
+/// `actor` is who removed it
+fn remove_name(
+    storage: &mut dyn Storage,
+    env: &Env,
+    name: &str,
+    record: &NameRecord,
+    actor: &Addr,
+    reason: RemovalReason,
+) -> StdResult<()> {
+    let key = name.as_bytes();
+    name_resolver().remove(storage, key)?;
+    OWNERS.save(storage, key, &None, env.block.height)?;
+    record_history(storage, env, name, actor, HistoryEvent::Removed { reason: reason.clone() })?;
+    let tombstone = Tombstone {
+        previous_owner: record.owner.clone(),
+        height: env.block.height,
+        removed_at: env.block.time,
+        reason,
+    };
+    TOMBSTONES.save(storage, key, &tombstone)?;
+    WEBHOOKS.remove(storage, key);
+    LOCKED_NAMES.remove(storage, key);
+    TRANSFER_MEMOS.remove(storage, (&record.owner, key));
+    clear_primary_name(storage, &record.owner, name)?;
+    clear_approvals(storage, key)?;
+    clear_name_entries(storage, &TEXT_RECORDS, key)?;
+    clear_name_entries(storage, &CHAIN_ADDRESSES, key)?;
+    clear_name_entries(storage, &SUBNAMES, key)?;
+    TOKEN_URIS.remove(storage, key);
+    TRANSFER_OFFERS.remove(storage, key);
+    LISTINGS.remove(storage, key);
+    REGISTRANTS.remove(storage, key);
+    PAID_TERMS.remove(storage, key);
+    NAME_STATS.remove(storage, key);
+
+    let attestations = ATTESTATIONS
+        .sub_prefix(key)
+        .keys(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    for (issuer, attestation_key) in attestations {
+        ATTESTATIONS.remove(storage, (key, &issuer, &attestation_key));
+    }
+    let categories = CATEGORIES
+        .keys(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    for category in categories {
+        CATEGORY_MEMBERS.remove(storage, (&category, key));
+    }
+
+    TOTAL_NAMES.update(storage, |total| -> StdResult<_> { Ok(total.saturating_sub(1)) })?;
+    update_owner_stats(storage, &record.owner, |stats| {
+        stats.name_count = stats.name_count.saturating_sub(1);
+    })
+}
+
+/// record_history appends `event` by `actor` to the history of the name
+fn record_history(
+    storage: &mut dyn Storage,
+    env: &Env,
+    name: &str,
+    actor: &Addr,
+    event: HistoryEvent,
+) -> StdResult<()> {
+    let key = name.as_bytes();
+    let seq = HISTORY_LENGTHS.may_load(storage, key)?.unwrap_or_default();
+    let entry = HistoryEntry {
+        event,
+        actor: actor.clone(),
+        height: env.block.height,
+        time: env.block.time,
+    };
+    NAME_HISTORY.save(storage, (key, seq), &entry)?;
+    HISTORY_LENGTHS.save(storage, key, &(seq + 1))
+}
+
+/// cooldown_end is when the previous owner of a name released or revoked moments ago stops having it reserved
+fn cooldown_end(storage: &dyn Storage, config: &Config, env: &Env, key: &[u8]) -> StdResult<Option<Timestamp>> {
+    let tombstone = match TOMBSTONES.may_load(storage, key)? {
+        Some(tombstone) if tombstone.reason != RemovalReason::Expired => tombstone,
+        _ => return Ok(None),
+    };
+    let until = tombstone.removed_at.plus_seconds(config.release_cooldown);
+    Ok((env.block.time < until).then_some(until))
+}
+
+/// clear_primary_name drops the primary name of `owner` if it is `name`
+fn clear_primary_name(storage: &mut dyn Storage, owner: &Addr, name: &str) -> StdResult<()> {
+    if PRIMARY_NAME.may_load(storage, owner)?.is_some_and(|primary| primary == name) {
+        PRIMARY_NAME.remove(storage, owner);
+    }
+    Ok(())
+}
+
+/// clear_approvals drops every cw721 approval of the name stored under `key`
+fn clear_approvals(storage: &mut dyn Storage, key: &[u8]) -> StdResult<()> {
+    let spenders = NFT_APPROVALS
+        .prefix(key)
+        .keys(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    for spender in spenders {
+        NFT_APPROVALS.remove(storage, (key, &spender));
+    }
+    Ok(())
+}
+
+/// assert_unlocked returns an error while the admin froze the name or its owner locked it
+fn assert_unlocked(storage: &dyn Storage, name: &str) -> Result<(), ContractError> {
+    if ADMIN_FROZEN_NAMES.has(storage, name.as_bytes()) {
+        return Err(ContractError::NameFrozen { name: name.to_string() });
+    }
+    if LOCKED_NAMES.has(storage, name.as_bytes()) {
+        return Err(ContractError::NameLocked { name: name.to_string() });
+    }
+    Ok(())
+}
+
+/// execute_lock stops transfers, edits and approvals of a name of the sender until it unlocks it
+pub fn execute_lock(deps: DepsMut, env: Env, info: MessageInfo, name: String) -> Result<Response, ContractError> {
+    let record = match name_resolver().may_load(deps.storage, name.as_bytes())? {
+        Some(record) => record,
+        None => return Err(ContractError::NameNotExists { name }),
+    };
+    if record.owner != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+    if is_expired(&record, &env) {
+        return Err(ContractError::NameExpired { name });
+    }
+    LOCKED_NAMES.save(deps.storage, name.as_bytes(), &Empty {})?;
+
+    Ok(Response::new()
+        .add_attribute("action", "lock")
+        .add_attribute("name", name)
+        .add_attribute("owner", info.sender))
+}
+
+/// execute_unlock lifts the lock of a name, only its owner can, operators and grantees can't
+pub fn execute_unlock(deps: DepsMut, _env: Env, info: MessageInfo, name: String) -> Result<Response, ContractError> {
+    let record = match name_resolver().may_load(deps.storage, name.as_bytes())? {
+        Some(record) => record,
+        None => return Err(ContractError::NameNotExists { name }),
+    };
+    if record.owner != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+    LOCKED_NAMES.remove(deps.storage, name.as_bytes());
+
+    Ok(Response::new()
+        .add_attribute("action", "unlock")
+        .add_attribute("name", name)
+        .add_attribute("owner", info.sender))
+}
+
+/// execute_set_name_frozen lets the admin freeze a name for legal or abuse cases, its owner can't lift it
+pub fn execute_set_name_frozen(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    name: String,
+    frozen: bool,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.owner != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+    if frozen {
+        ADMIN_FROZEN_NAMES.save(deps.storage, name.as_bytes(), &Empty {})?;
+    } else {
+        ADMIN_FROZEN_NAMES.remove(deps.storage, name.as_bytes());
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "set_name_frozen")
+        .add_attribute("name", name)
+        .add_attribute("frozen", frozen.to_string()))
+}
+
+/// is_approved tells whether `spender` holds an unexpired cw721 approval on the name stored under `key`
+fn is_approved(storage: &dyn Storage, env: &Env, key: &[u8], spender: &Addr) -> StdResult<bool> {
+    let approval = NFT_APPROVALS.may_load(storage, (key, spender))?;
+    Ok(approval.is_some_and(|expires| !expires.is_expired(&env.block)))
+}
+
+/// clear_name_entries drops every entry `map` holds for the name stored under `key`
+fn clear_name_entries<T: Serialize + DeserializeOwned>(
+    storage: &mut dyn Storage,
+    map: &Map<(&[u8], &str), T>,
+    key: &[u8],
+) -> StdResult<()> {
+    let entry_keys = map
+        .prefix(key)
+        .keys(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    for entry_key in entry_keys {
+        map.remove(storage, (key, &entry_key));
+    }
+    Ok(())
+}
+
+/// clear_transfer_memos drops the memos of up to MAX_MEMOS_CLEARED names transferred to `recipient`, so
+/// the cost of an execute doesn't grow with the names others send to its sender
+fn clear_transfer_memos(storage: &mut dyn Storage, recipient: &Addr) -> StdResult<()> {
+    let names = TRANSFER_MEMOS
+        .prefix(recipient)
+        .keys(storage, None, None, Order::Ascending)
+        .take(MAX_MEMOS_CLEARED)
+        .collect::<StdResult<Vec<_>>>()?;
+    for name in names {
+        TRANSFER_MEMOS.remove(storage, (recipient, &name));
+    }
+    Ok(())
+}
+
+/// is_expired tells whether the registration of `record` has run out
+fn is_expired(record: &NameRecord, env: &Env) -> bool {
+    record.expires_at.is_some_and(|expires_at| env.block.time >= expires_at)
+}
+
+/// is_released tells whether `record` expired more than a grace period ago, so anyone may register it
+fn is_released(record: &NameRecord, config: &Config, env: &Env) -> bool {
+    record
+        .expires_at
+        .is_some_and(|expires_at| env.block.time >= expires_at.plus_seconds(config.grace_period))
+}
+
+/// register_prices are the accepted prices of registering the name for `sender`, premium included
+fn register_prices(deps: Deps, env: &Env, config: &Config, name: &str, sender: &Addr) -> StdResult<Vec<Coin>> {
+    let action = PricedAction::Register { length: name_length(name) };
+    let mut prices = action_prices(deps, config, action, name, sender)?;
+    if let Some(ends_at) = premium_end(deps.storage, config, env, name.as_bytes())? {
+        // the premium decays linearly from premium_multiplier times the price down to the price itself
+        let remaining = ends_at.seconds() - env.block.time.seconds();
+        let extra = u128::from(config.premium_multiplier - 1) * u128::from(remaining);
+        for price in prices.iter_mut() {
+            price.amount += price.amount.multiply_ratio(extra, config.premium_window);
+        }
+    }
+    Ok(prices)
+}
+
+/// premium_end is when the premium on a name that expired and ran out of grace period stops, while one is charged
+fn premium_end(storage: &dyn Storage, config: &Config, env: &Env, key: &[u8]) -> StdResult<Option<Timestamp>> {
+    if config.premium_multiplier <= 1 || config.premium_window == 0 {
+        return Ok(None);
+    }
+    let released_at = match name_resolver().may_load(storage, key)?.and_then(|record| record.expires_at) {
+        Some(expires_at) => expires_at.plus_seconds(config.grace_period),
+        None => return Ok(None),
+    };
+    let ends_at = released_at.plus_seconds(config.premium_window);
+    Ok((released_at <= env.block.time && env.block.time < ends_at).then_some(ends_at))
+}
+
+pub fn execute_transfer(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    name: String,
+    to: String,
+    round_up: bool,
+    memo: Option<String>,
+) -> Result<Response, ContractError> {
+    assert_unlocked(deps.storage, &name)?;
+    let config = CONFIG.load(deps.storage)?;
+    let prices = action_prices(deps.as_ref(), &config, PricedAction::Transfer, &name, &info.sender)?;
+    assert_sent_sufficient_coin(&info.funds, &prices)?;
+    if let Some(memo) = &memo {
+        let memo_length = memo.len() as u64;
+        if memo_length > MAX_MEMO_LENGTH {
+            return Err(ContractError::MemoTooLong {
+                memo_length,
+                max_length: MAX_MEMO_LENGTH,
+            });
+        }
+    }
+    let (paid, surplus) = split_payment(deps.storage, &config, &prices, &info, round_up)?;
+
+    let new_owner = deps.api.addr_validate(&to)?;
+    let key = name.as_bytes();
+    let record = match name_resolver().may_load(deps.storage, key)? {
+        Some(record) => record,
+        None => return Err(ContractError::NameNotExists { name }),
+    };
+    if !is_authorized(deps.storage, &env, &record.owner, &info.sender, GrantAction::Transfer)?
+        && !is_approved(deps.storage, &env, key, &info.sender)?
+    {
+        return Err(ContractError::Unauthorized {});
+    }
+    if is_expired(&record, &env) {
+        return Err(ContractError::NameExpired { name });
+    }
+
+    let owner = record.owner.clone();
+    let hook = move_name(deps.storage, &env, &name, record, &info.sender, &new_owner)?;
+    update_owner_stats(deps.storage, &info.sender, |stats| add_coins(&mut stats.total_spent, &paid))?;
+    let burned = collect_fees(deps.storage, &config, &paid)?;
+
+    let mut res = Response::new()
+        .add_messages(surplus)
+        .add_messages(burn_messages(burned))
+        .add_submessages(hook)
+        .add_attribute("action", "transfer")
+        .add_attribute("name", &name)
+        .add_attribute("owner", owner)
+        .add_attribute("new_owner", &new_owner)
+        .add_attribute("fees", coins_attribute(&paid));
+    if let Some(memo) = memo {
+        let transfer_memo = TransferMemo {
+            from: info.sender,
+            memo: memo.clone(),
+        };
+        TRANSFER_MEMOS.save(deps.storage, (&new_owner, key), &transfer_memo)?;
+        res = res.add_attribute("memo", memo);
+    }
+
+    Ok(res)
+}
+
+/// move_name hands a name over to `new_owner` on behalf of `actor`, dropping everything tied to the previous owner
+fn move_name(
+    storage: &mut dyn Storage,
+    env: &Env,
+    name: &str,
+    mut record: NameRecord,
+    actor: &Addr,
+    new_owner: &Addr,
+) -> StdResult<Vec<SubMsg>> {
+    let key = name.as_bytes();
+    let previous_owner = record.owner;
+    let event = HistoryEvent::Transferred { from: previous_owner.clone(), to: new_owner.clone() };
+    record_history(storage, env, name, actor, event)?;
+    record.owner = new_owner.clone();
+    // verification vouches for the previous owner only
+    record.verified = false;
+    record.accept_attestations = false;
+    name_resolver().save(storage, key, &record)?;
+    OWNERS.save(storage, key, &Some(new_owner.clone()), env.block.height)?;
+    clear_primary_name(storage, &previous_owner, name)?;
+    clear_approvals(storage, key)?;
+    TRANSFER_OFFERS.remove(storage, key);
+    LISTINGS.remove(storage, key);
+    // the lock was the previous owner's
+    LOCKED_NAMES.remove(storage, key);
+    // payments must not keep reaching the previous owner
+    clear_name_entries(storage, &CHAIN_ADDRESSES, key)?;
+    clear_name_entries(storage, &SUBNAMES, key)?;
+
+    update_owner_stats(storage, &previous_owner, |stats| {
+        stats.name_count = stats.name_count.saturating_sub(1);
+    })?;
+    update_owner_stats(storage, new_owner, |stats| stats.name_count += 1)?;
+    update_name_stats(storage, key, |stats| stats.transfers += 1)?;
+
+    // the webhook was set up by the previous owner, it hears about the transfer and is dropped
+    let event = NameEvent::Transferred { from: previous_owner, to: new_owner.clone() };
+    let hook = webhook_message(storage, name, event)?;
+    WEBHOOKS.remove(storage, key);
+    Ok(hook)
+}
+
+/// execute_offer_transfer offers a name to `to`, who takes it with AcceptTransfer. The seller pays transfer_price now
+pub fn execute_offer_transfer(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    name: String,
+    to: String,
+    price: Option<Coin>,
+) -> Result<Response, ContractError> {
+    assert_unlocked(deps.storage, &name)?;
+    let config = CONFIG.load(deps.storage)?;
+    let prices = action_prices(deps.as_ref(), &config, PricedAction::Transfer, &name, &info.sender)?;
+    assert_sent_sufficient_coin(&info.funds, &prices)?;
+
+    let recipient = deps.api.addr_validate(&to)?;
+    let key = name.as_bytes();
+    let record = match name_resolver().may_load(deps.storage, key)? {
+        Some(record) => record,
+        None => return Err(ContractError::NameNotExists { name }),
+    };
+    if !is_authorized(deps.storage, &env, &record.owner, &info.sender, GrantAction::Transfer)?
+        && !is_approved(deps.storage, &env, key, &info.sender)?
+    {
+        return Err(ContractError::Unauthorized {});
+    }
+    if is_expired(&record, &env) {
+        return Err(ContractError::NameExpired { name });
+    }
+
+    let (paid, surplus) = split_payment(deps.storage, &config, &prices, &info, false)?;
+    update_owner_stats(deps.storage, &info.sender, |stats| add_coins(&mut stats.total_spent, &paid))?;
+    let burned = collect_fees(deps.storage, &config, &paid)?;
+    let offer = TransferOffer {
+        from: record.owner,
+        to: recipient,
+        price: price.filter(|price| !price.amount.is_zero()),
+    };
+    TRANSFER_OFFERS.save(deps.storage, key, &offer)?;
+
+    Ok(Response::new()
+        .add_messages(surplus)
+        .add_messages(burn_messages(burned))
+        .add_attribute("action", "offer_transfer")
+        .add_attribute("name", name)
+        .add_attribute("to", offer.to)
+        .add_attribute("price", offer.price.map(|coin| coin.to_string()).unwrap_or_default()))
+}
+
+/// execute_accept_transfer takes a name offered to the sender, paying the asking price to the seller
+pub fn execute_accept_transfer(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    name: String,
+) -> Result<Response, ContractError> {
+    assert_unlocked(deps.storage, &name)?;
+    let key = name.as_bytes();
+    let offer = match TRANSFER_OFFERS.may_load(deps.storage, key)? {
+        Some(offer) if offer.to == info.sender => offer,
+        _ => return Err(ContractError::OfferNotExists { name }),
+    };
+    let record = match name_resolver().may_load(deps.storage, key)? {
+        Some(record) => record,
+        None => return Err(ContractError::NameNotExists { name }),
+    };
+    // the name changed hands since the offer was made
+    if record.owner != offer.from {
+        TRANSFER_OFFERS.remove(deps.storage, key);
+        return Err(ContractError::OfferNotExists { name });
+    }
+    if is_expired(&record, &env) {
+        return Err(ContractError::NameExpired { name });
+    }
+    let Payment { charge, change } = charge(&info.funds, offer.price.as_slice())?;
+
+    let hook = move_name(deps.storage, &env, &name, record, &info.sender, &info.sender)?;
+
+    let mut response = Response::new();
+    if !change.is_empty() {
+        response = response.add_message(BankMsg::Send { to_address: info.sender.to_string(), amount: change });
+    }
+    if let Some(price) = charge {
+        let paid = vec![price];
+        update_owner_stats(deps.storage, &info.sender, |stats| add_coins(&mut stats.total_spent, &paid))?;
+        update_name_stats(deps.storage, key, |stats| {
+            stats.sales += 1;
+            add_coins(&mut stats.sale_volume, &paid);
+        })?;
+        // the seller claims the price with ClaimPayout
+        credit_payout(deps.storage, &offer.from, &paid)?;
+    }
+    Ok(response
+        .add_submessages(hook)
+        .add_attribute("action", "accept_transfer")
+        .add_attribute("name", name)
+        .add_attribute("from", offer.from)
+        .add_attribute("to", info.sender))
+}
+
+/// execute_list_for_sale puts a name of the sender up for sale at `price`
+pub fn execute_list_for_sale(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    name: String,
+    price: Coin,
+) -> Result<Response, ContractError> {
+    assert_unlocked(deps.storage, &name)?;
+    let key = name.as_bytes();
+    let record = match name_resolver().may_load(deps.storage, key)? {
+        Some(record) => record,
+        None => return Err(ContractError::NameNotExists { name }),
+    };
+    if !is_authorized(deps.storage, &env, &record.owner, &info.sender, GrantAction::Transfer)?
+        && !is_approved(deps.storage, &env, key, &info.sender)?
+    {
+        return Err(ContractError::Unauthorized {});
+    }
+    if is_expired(&record, &env) {
+        return Err(ContractError::NameExpired { name });
+    }
+    if price.amount.is_zero() {
+        return Err(ContractError::InvalidListingPrice {});
+    }
+
+    let listing = Listing { seller: record.owner, price, listed_at: env.block.time };
+    LISTINGS.save(deps.storage, key, &listing)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "list_for_sale")
+        .add_attribute("name", name)
+        .add_attribute("seller", listing.seller)
+        .add_attribute("price", listing.price.to_string()))
+}
+
+/// execute_delist takes a name off the marketplace
+pub fn execute_delist(deps: DepsMut, env: Env, info: MessageInfo, name: String) -> Result<Response, ContractError> {
+    let key = name.as_bytes();
+    let listing = match LISTINGS.may_load(deps.storage, key)? {
+        Some(listing) => listing,
+        None => return Err(ContractError::ListingNotExists { name }),
+    };
+    if !is_authorized(deps.storage, &env, &listing.seller, &info.sender, GrantAction::Transfer)?
+        && !is_approved(deps.storage, &env, key, &info.sender)?
+    {
+        return Err(ContractError::Unauthorized {});
+    }
+    LISTINGS.remove(deps.storage, key);
+
+    Ok(Response::new()
+        .add_attribute("action", "delist")
+        .add_attribute("name", name))
+}
+
+/// execute_buy sells a listed name to the sender. The price is split between the marketplace fee,
+/// the royalty of the registrant and the seller, who claims its share with ClaimPayout
+pub fn execute_buy(deps: DepsMut, env: Env, info: MessageInfo, name: String) -> Result<Response, ContractError> {
+    assert_unlocked(deps.storage, &name)?;
+    let config = CONFIG.load(deps.storage)?;
+    let key = name.as_bytes();
+    let listing = match LISTINGS.may_load(deps.storage, key)? {
+        Some(listing) => listing,
+        None => return Err(ContractError::ListingNotExists { name }),
+    };
+    let record = match name_resolver().may_load(deps.storage, key)? {
+        Some(record) => record,
+        None => return Err(ContractError::NameNotExists { name }),
+    };
+    // the name changed hands since it was listed
+    if record.owner != listing.seller {
+        LISTINGS.remove(deps.storage, key);
+        return Err(ContractError::ListingNotExists { name });
+    }
+    if is_expired(&record, &env) {
+        return Err(ContractError::NameExpired { name });
+    }
+    let Payment { change, .. } = charge(&info.funds, std::slice::from_ref(&listing.price))?;
+
+    let price = listing.price;
+    let fee = coin(price.amount.multiply_ratio(config.marketplace_fee_percent, 100u64).u128(), &price.denom);
+    let royalty = match REGISTRANTS.may_load(deps.storage, key)? {
+        Some(registrant) if registrant != listing.seller => {
+            let amount = price.amount.multiply_ratio(config.royalty_percent, 100u64);
+            Some((registrant, coin(amount.u128(), &price.denom))).filter(|(_, royalty)| !royalty.amount.is_zero())
+        }
+        _ => None,
+    };
+    let royalty_amount = royalty.as_ref().map(|(_, royalty)| royalty.amount).unwrap_or_default();
+    let proceeds = coin((price.amount - fee.amount - royalty_amount).u128(), &price.denom);
+
+    let burned = collect_fees(deps.storage, &config, std::slice::from_ref(&fee))?;
+    if let Some((registrant, royalty)) = &royalty {
+        credit_payout(deps.storage, registrant, std::slice::from_ref(royalty))?;
+    }
+    if !proceeds.amount.is_zero() {
+        credit_payout(deps.storage, &listing.seller, &[proceeds])?;
+    }
+    let paid = vec![price];
+    update_owner_stats(deps.storage, &info.sender, |stats| add_coins(&mut stats.total_spent, &paid))?;
+    update_name_stats(deps.storage, key, |stats| {
+        stats.sales += 1;
+        add_coins(&mut stats.sale_volume, &paid);
+    })?;
+
+    let hook = move_name(deps.storage, &env, &name, record, &info.sender, &info.sender)?;
+
+    let mut response = Response::new();
+    if !change.is_empty() {
+        response = response.add_message(BankMsg::Send { to_address: info.sender.to_string(), amount: change });
+    }
+    Ok(response
+        .add_messages(burn_messages(burned))
+        .add_submessages(hook)
+        .add_attribute("action", "buy")
+        .add_attribute("name", name)
+        .add_attribute("seller", listing.seller)
+        .add_attribute("buyer", info.sender)
+        .add_attribute("price", coins_attribute(&paid))
+        .add_attribute("fee", fee.to_string()))
+}
+
+/// execute_set_marketplace_fees sets the percent of marketplace sales collected as a fee and paid as royalty
+pub fn execute_set_marketplace_fees(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    marketplace_fee_percent: u64,
+    royalty_percent: u64,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+    if config.owner != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+    if config.frozen {
+        return Err(ContractError::ConfigFrozen {});
+    }
+    if marketplace_fee_percent + royalty_percent > 100 {
+        return Err(ContractError::InvalidMarketplaceFees {});
+    }
+
+    config.marketplace_fee_percent = marketplace_fee_percent;
+    config.royalty_percent = royalty_percent;
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "set_marketplace_fees")
+        .add_attribute("marketplace_fee_percent", marketplace_fee_percent.to_string())
+        .add_attribute("royalty_percent", royalty_percent.to_string()))
+}
+
+/// execute_set_registration_limits sets how many names an address may hold and how long it waits between
+/// registrations
+pub fn execute_set_registration_limits(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    max_names_per_address: Option<u64>,
+    registration_cooldown_seconds: Option<u64>,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+    if config.owner != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+    if config.frozen {
+        return Err(ContractError::ConfigFrozen {});
+    }
+
+    config.max_names_per_address = max_names_per_address;
+    config.registration_cooldown_seconds = registration_cooldown_seconds;
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "set_registration_limits")
+        .add_attribute("max_names_per_address", max_names_per_address.map(|max| max.to_string()).unwrap_or_default())
+        .add_attribute(
+            "registration_cooldown_seconds",
+            registration_cooldown_seconds.map(|seconds| seconds.to_string()).unwrap_or_default(),
+        ))
+}
+
+/// execute_cancel_transfer withdraws the pending offer of a name
+pub fn execute_cancel_transfer(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    name: String,
+) -> Result<Response, ContractError> {
+    let key = name.as_bytes();
+    let offer = match TRANSFER_OFFERS.may_load(deps.storage, key)? {
+        Some(offer) => offer,
+        None => return Err(ContractError::OfferNotExists { name }),
+    };
+    // the recipient may turn the offer down too
+    if offer.to != info.sender
+        && !is_authorized(deps.storage, &env, &offer.from, &info.sender, GrantAction::Transfer)?
+        && !is_approved(deps.storage, &env, key, &info.sender)?
+    {
+        return Err(ContractError::Unauthorized {});
+    }
+    TRANSFER_OFFERS.remove(deps.storage, key);
+
+    Ok(Response::new()
+        .add_attribute("action", "cancel_transfer")
+        .add_attribute("name", name))
+}
+
+/// execute_transfer_nft is the cw721 transfer, it runs like Transfer without a memo
+pub fn execute_transfer_nft(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    recipient: String,
+    token_id: String,
+) -> Result<Response, ContractError> {
+    let sender = info.sender.clone();
+    let res = execute_transfer(deps, env, info, token_id.clone(), recipient.clone(), false, None)?;
+
+    Ok(rename_action(res, "transfer_nft")
+        .add_attribute("sender", sender)
+        .add_attribute("recipient", recipient)
+        .add_attribute("token_id", token_id))
+}
+
+/// execute_send_nft transfers the name to a contract and notifies it with a ReceiveNft message
+pub fn execute_send_nft(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    contract: String,
+    token_id: String,
+    msg: Binary,
+) -> Result<Response, ContractError> {
+    let sender = info.sender.clone();
+    let res = execute_transfer(deps, env, info, token_id.clone(), contract.clone(), false, None)?;
+
+    let receive = NftReceiverMsg::ReceiveNft(Cw721ReceiveMsg {
+        sender: sender.to_string(),
+        token_id: token_id.clone(),
+        msg,
+    });
+    Ok(rename_action(res, "send_nft")
+        .add_message(WasmMsg::Execute {
+            contract_addr: contract.clone(),
+            msg: to_binary(&receive)?,
+            funds: vec![],
+        })
+        .add_attribute("sender", sender)
+        .add_attribute("recipient", contract)
+        .add_attribute("token_id", token_id))
+}
+
+/// rename_action replaces the action attribute set by the handler `response` comes from, keeping one per response
+fn rename_action(mut response: Response, action: &str) -> Response {
+    for attribute in response.attributes.iter_mut().filter(|attribute| attribute.key == "action") {
+        attribute.value = action.to_string();
+    }
+    response
+}
+
+/// execute_approve lets `spender` transfer a name of the sender
+pub fn execute_approve(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    spender: String,
+    token_id: String,
+    expires: Option<Expiration>,
+) -> Result<Response, ContractError> {
+    assert_unlocked(deps.storage, &token_id)?;
+    let spender_addr = deps.api.addr_validate(&spender)?;
+    let record = match name_resolver().may_load(deps.storage, token_id.as_bytes())? {
+        Some(record) => record,
+        None => return Err(ContractError::NameNotExists { name: token_id }),
+    };
+    if record.owner != info.sender && !OPERATORS.has(deps.storage, (&record.owner, &info.sender)) {
+        return Err(ContractError::Unauthorized {});
+    }
+    if is_expired(&record, &env) {
+        return Err(ContractError::NameExpired { name: token_id });
+    }
+    let expires = expires.unwrap_or_default();
+    if expires.is_expired(&env.block) {
+        return Err(ContractError::ApprovalExpired {});
+    }
+
+    NFT_APPROVALS.save(deps.storage, (token_id.as_bytes(), &spender_addr), &expires)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "approve")
+        .add_attribute("sender", info.sender)
+        .add_attribute("spender", spender)
+        .add_attribute("token_id", token_id))
+}
+
+/// execute_revoke_approval withdraws the approval of `spender` on a name of the sender
+pub fn execute_revoke_approval(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    spender: String,
+    token_id: String,
+) -> Result<Response, ContractError> {
+    let spender_addr = deps.api.addr_validate(&spender)?;
+    let record = match name_resolver().may_load(deps.storage, token_id.as_bytes())? {
+        Some(record) => record,
+        None => return Err(ContractError::NameNotExists { name: token_id }),
+    };
+    if record.owner != info.sender && !OPERATORS.has(deps.storage, (&record.owner, &info.sender)) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    NFT_APPROVALS.remove(deps.storage, (token_id.as_bytes(), &spender_addr));
+
+    Ok(Response::new()
+        .add_attribute("action", "revoke")
+        .add_attribute("sender", info.sender)
+        .add_attribute("spender", spender)
+        .add_attribute("token_id", token_id))
+}
+
+/// execute_set_token_uri points the cw721 metadata of the name at off-chain JSON, None goes back to on-chain metadata
+pub fn execute_set_token_uri(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    name: String,
+    token_uri: Option<String>,
+) -> Result<Response, ContractError> {
+    assert_unlocked(deps.storage, &name)?;
+    let record = match name_resolver().may_load(deps.storage, name.as_bytes())? {
+        Some(record) => record,
+        None => return Err(ContractError::NameNotExists { name }),
+    };
+    if !is_authorized(deps.storage, &env, &record.owner, &info.sender, GrantAction::Edit)?
+        && !is_approved(deps.storage, &env, name.as_bytes(), &info.sender)?
+    {
+        return Err(ContractError::Unauthorized {});
+    }
+    if is_expired(&record, &env) {
+        return Err(ContractError::NameExpired { name });
+    }
+
+    match &token_uri {
+        Some(token_uri) => {
+            let valid = (token_uri.len() as u64) <= MAX_TOKEN_URI_LENGTH
+                && TOKEN_URI_SCHEMES
+                    .iter()
+                    .any(|scheme| token_uri.len() > scheme.len() && token_uri.starts_with(scheme))
+                && !token_uri.contains(char::is_whitespace);
+            if !valid {
+                return Err(ContractError::InvalidTokenUri { token_uri: token_uri.clone() });
+            }
+            TOKEN_URIS.save(deps.storage, name.as_bytes(), token_uri)?;
+        }
+        None => TOKEN_URIS.remove(deps.storage, name.as_bytes()),
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "set_token_uri")
+        .add_attribute("name", name)
+        .add_attribute("token_uri", token_uri.unwrap_or_default()))
+}
+
+/// execute_set_operator lets `operator` manage every name of the sender, like the sender itself
+pub fn execute_set_operator(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    operator: String,
+    approved: bool,
+) -> Result<Response, ContractError> {
+    let operator_addr = deps.api.addr_validate(&operator)?;
+    if approved {
+        OPERATORS.save(deps.storage, (&info.sender, &operator_addr), &Empty {})?;
+    } else {
+        OPERATORS.remove(deps.storage, (&info.sender, &operator_addr));
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "set_operator")
+        .add_attribute("owner", info.sender)
+        .add_attribute("operator", operator)
+        .add_attribute("approved", approved.to_string()))
+}
+
+pub fn execute_edit(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    name: String,
+    bio: String,
+    website: String,
+    round_up: bool,
+) -> Result<Response, ContractError> {
+    assert_unlocked(deps.storage, &name)?;
+    let config = CONFIG.load(deps.storage)?;
+    let action = PricedAction::Edit { bytes: (bio.len() + website.len()) as u64 };
+    let prices = action_prices(deps.as_ref(), &config, action, &name, &info.sender)?;
+    assert_sent_sufficient_coin(&info.funds, &prices)?;
+    let (paid, surplus) = split_payment(deps.storage, &config, &prices, &info, round_up)?;
+
+    let key = name.as_bytes();
+
+    let mut record = match name_resolver().may_load(deps.storage, key)? {
+        Some(record) => record,
+        None => return Err(ContractError::NameNotExists { name }),
+    };
+    if !is_authorized(deps.storage, &env, &record.owner, &info.sender, GrantAction::Edit)?
+        && !is_approved(deps.storage, &env, name.as_bytes(), &info.sender)?
+    {
+        return Err(ContractError::Unauthorized {});
+    }
+    if is_expired(&record, &env) {
+        return Err(ContractError::NameExpired { name });
+    }
+
+    validate_profile(&bio, &website, &config.name_policy)?;
+
+    record.bio = bio;
+    record.website = website;
+    name_resolver().save(deps.storage, key, &record)?;
+
+    update_owner_stats(deps.storage, &info.sender, |stats| add_coins(&mut stats.total_spent, &paid))?;
+    let burned = collect_fees(deps.storage, &config, &paid)?;
+
+    update_name_stats(deps.storage, name.as_bytes(), |stats| stats.edits += 1)?;
+    record_history(deps.storage, &env, &name, &info.sender, HistoryEvent::Edited {})?;
+    let hook = webhook_message(deps.storage, &name, NameEvent::Edited {})?;
+
+    Ok(Response::new()
+        .add_messages(surplus)
+        .add_messages(burn_messages(burned))
+        .add_submessages(hook)
+        .add_attribute("action", "edit")
+        .add_attribute("name", name)
+        .add_attribute("owner", record.owner)
+        .add_attribute("fees", coins_attribute(&paid)))
+}
+
+pub fn execute_set_cache_control(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    name: String,
+    cache_control: CacheControl,
+) -> Result<Response, ContractError> {
+    assert_unlocked(deps.storage, &name)?;
+    let key = name.as_bytes();
+    let mut record = match name_resolver().may_load(deps.storage, key)? {
+        Some(record) => record,
+        None => return Err(ContractError::NameNotExists { name }),
+    };
+    if !is_authorized(deps.storage, &env, &record.owner, &info.sender, GrantAction::Edit)?
+        && !is_approved(deps.storage, &env, name.as_bytes(), &info.sender)?
+    {
+        return Err(ContractError::Unauthorized {});
+    }
+    if is_expired(&record, &env) {
+        return Err(ContractError::NameExpired { name });
+    }
+
+    record.cache_control = cache_control;
+    name_resolver().save(deps.storage, key, &record)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "set_cache_control")
+        .add_attribute("name", name))
+}
+
+/// execute_set_socials replaces the avatar and social accounts of a name, charged like an edit of as many bytes
+pub fn execute_set_socials(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    name: String,
+    socials: Socials,
+) -> Result<Response, ContractError> {
+    assert_unlocked(deps.storage, &name)?;
+    let config = CONFIG.load(deps.storage)?;
+    let bytes = socials.avatar_url.len() + socials.twitter.len() + socials.github.len() + socials.discord.len();
+    let action = PricedAction::Edit { bytes: bytes as u64 };
+    let prices = action_prices(deps.as_ref(), &config, action, &name, &info.sender)?;
+    assert_sent_sufficient_coin(&info.funds, &prices)?;
+    let (paid, surplus) = split_payment(deps.storage, &config, &prices, &info, false)?;
+
+    let key = name.as_bytes();
+    let mut record = match name_resolver().may_load(deps.storage, key)? {
+        Some(record) => record,
+        None => return Err(ContractError::NameNotExists { name }),
+    };
+    if !is_authorized(deps.storage, &env, &record.owner, &info.sender, GrantAction::Edit)?
+        && !is_approved(deps.storage, &env, name.as_bytes(), &info.sender)?
+    {
+        return Err(ContractError::Unauthorized {});
+    }
+    if is_expired(&record, &env) {
+        return Err(ContractError::NameExpired { name });
+    }
+
+    validate_socials(&socials)?;
+
+    record.socials = socials;
+    name_resolver().save(deps.storage, key, &record)?;
+
+    update_owner_stats(deps.storage, &info.sender, |stats| add_coins(&mut stats.total_spent, &paid))?;
+    let burned = collect_fees(deps.storage, &config, &paid)?;
+
+    update_name_stats(deps.storage, name.as_bytes(), |stats| stats.edits += 1)?;
+    record_history(deps.storage, &env, &name, &info.sender, HistoryEvent::Edited {})?;
+    let hook = webhook_message(deps.storage, &name, NameEvent::Edited {})?;
+
+    Ok(Response::new()
+        .add_messages(surplus)
+        .add_messages(burn_messages(burned))
+        .add_submessages(hook)
+        .add_attribute("action", "set_socials")
+        .add_attribute("name", name)
+        .add_attribute("fees", coins_attribute(&paid)))
+}
+
+/// execute_set_content_hash sets or clears the website CID of a name, charged like an edit of as many bytes
+pub fn execute_set_content_hash(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    name: String,
+    value: Option<String>,
+) -> Result<Response, ContractError> {
+    assert_unlocked(deps.storage, &name)?;
+    let config = CONFIG.load(deps.storage)?;
+    let action = PricedAction::Edit { bytes: value.as_ref().map_or(0, |value| value.len() as u64) };
+    let prices = action_prices(deps.as_ref(), &config, action, &name, &info.sender)?;
+    assert_sent_sufficient_coin(&info.funds, &prices)?;
+    let (paid, surplus) = split_payment(deps.storage, &config, &prices, &info, false)?;
+
+    let key = name.as_bytes();
+    let mut record = match name_resolver().may_load(deps.storage, key)? {
+        Some(record) => record,
+        None => return Err(ContractError::NameNotExists { name }),
+    };
+    if !is_authorized(deps.storage, &env, &record.owner, &info.sender, GrantAction::Edit)?
+        && !is_approved(deps.storage, &env, name.as_bytes(), &info.sender)?
+    {
+        return Err(ContractError::Unauthorized {});
+    }
+    if is_expired(&record, &env) {
+        return Err(ContractError::NameExpired { name });
+    }
+
+    if let Some(value) = &value {
+        validate_content_hash(value)?;
+    }
+
+    record.content_hash = value.clone();
+    name_resolver().save(deps.storage, key, &record)?;
+
+    update_owner_stats(deps.storage, &info.sender, |stats| add_coins(&mut stats.total_spent, &paid))?;
+    let burned = collect_fees(deps.storage, &config, &paid)?;
+
+    update_name_stats(deps.storage, name.as_bytes(), |stats| stats.edits += 1)?;
+    record_history(deps.storage, &env, &name, &info.sender, HistoryEvent::Edited {})?;
+    let hook = webhook_message(deps.storage, &name, NameEvent::Edited {})?;
+
+    Ok(Response::new()
+        .add_messages(surplus)
+        .add_messages(burn_messages(burned))
+        .add_submessages(hook)
+        .add_attribute("action", "set_content_hash")
+        .add_attribute("name", name)
+        .add_attribute("content_hash", value.unwrap_or_default())
+        .add_attribute("fees", coins_attribute(&paid)))
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn execute_edit_conf(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    purchase_price: Vec<Coin>,
+    transfer_price: Vec<Coin>,
+    edit_price: Vec<Coin>,
+    length_prices: Option<Vec<PriceTier>>,
+    name_policy: Option<NamePolicy>,
+) -> Result<Response, ContractError> {
+    let get_config = CONFIG.load(deps.storage)?;
+    assert_sent_sufficient_coin(&info.funds, &get_config.transfer_price)?;
+
+    if get_config.owner != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if get_config.frozen {
+        return Err(ContractError::ConfigFrozen {});
+    }
+    if let Some(edit_byte_price) = &get_config.edit_byte_price {
+        if edit_price.iter().any(|coin| coin.denom != edit_byte_price.denom) {
+            return Err(ContractError::EditPriceDenomMismatch {});
+        }
+    }
+
+    if let Some(length_prices) = &length_prices {
+        validate_price_tiers(length_prices)?;
+    }
+    if let Some(name_policy) = &name_policy {
+        validate_name_policy(name_policy)?;
+    }
+
+    // CONFIG.update(deps.storage, FnOnce::<&Config,>);
+    CONFIG.update(deps.storage, |mut config| -> StdResult<_> {
+        config.purchase_price = purchase_price.clone();
+        config.transfer_price = transfer_price.clone();
+        config.edit_price = edit_price.clone();
+        if let Some(length_prices) = length_prices {
+            config.length_prices = length_prices;
+        }
+        if let Some(name_policy) = name_policy {
+            config.name_policy = name_policy;
+        }
+        Ok(config)
+    })?;
+
+    Ok(Response::new()
+        .add_attribute("action", "edit_conf")
+        .add_attribute("purchase_price", coins_attribute(&purchase_price))
+        .add_attribute("transfer_price", coins_attribute(&transfer_price))
+        .add_attribute("edit_price", coins_attribute(&edit_price)))
+}
+
+/// execute_add_category creates a category, or updates its approval policy if it already exists
+pub fn execute_add_category(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    category: String,
+    requires_approval: bool,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.owner != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    validate_category(&category)?;
+    CATEGORIES.save(deps.storage, &category, &Category { requires_approval })?;
+
+    Ok(Response::new()
+        .add_attribute("action", "add_category")
+        .add_attribute("category", category))
+}
+
+pub fn execute_remove_category(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    category: String,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.owner != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if !CATEGORIES.has(deps.storage, &category) {
+        return Err(ContractError::CategoryNotExists { category });
+    }
+    CATEGORIES.remove(deps.storage, &category);
+
+    // drop memberships so a category added again later starts empty
+    let members = CATEGORY_MEMBERS
+        .prefix(&category)
+        .keys(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    for member in members {
+        CATEGORY_MEMBERS.remove(deps.storage, (&category, &member));
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "remove_category")
+        .add_attribute("category", category))
+}
+
+pub fn execute_join_category(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    name: String,
+    category: String,
+) -> Result<Response, ContractError> {
+    let key = name.as_bytes();
+    let record = match name_resolver().may_load(deps.storage, key)? {
+        Some(record) => record,
+        None => return Err(ContractError::NameNotExists { name }),
+    };
+    if !is_authorized(deps.storage, &env, &record.owner, &info.sender, GrantAction::Categories)? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let settings = match CATEGORIES.may_load(deps.storage, &category)? {
+        Some(settings) => settings,
+        None => return Err(ContractError::CategoryNotExists { category }),
+    };
+    if CATEGORY_MEMBERS.has(deps.storage, (&category, key)) {
+        return Err(ContractError::AlreadyInCategory { name, category });
+    }
+
+    let approved = !settings.requires_approval;
+    CATEGORY_MEMBERS.save(deps.storage, (&category, key), &approved)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "join_category")
+        .add_attribute("name", name)
+        .add_attribute("category", category)
+        .add_attribute("approved", approved.to_string()))
+}
+
+/// execute_leave_category lets the name owner leave a category, or the admin remove a member
+pub fn execute_leave_category(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    name: String,
+    category: String,
+) -> Result<Response, ContractError> {
+    let key = name.as_bytes();
+    let config = CONFIG.load(deps.storage)?;
+    let record = match name_resolver().may_load(deps.storage, key)? {
+        Some(record) => record,
+        None => return Err(ContractError::NameNotExists { name }),
+    };
+    if info.sender != config.owner
+        && !is_authorized(deps.storage, &env, &record.owner, &info.sender, GrantAction::Categories)?
+    {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if !CATEGORY_MEMBERS.has(deps.storage, (&category, key)) {
+        return Err(ContractError::NotInCategory { name, category });
+    }
+    CATEGORY_MEMBERS.remove(deps.storage, (&category, key));
+
+    Ok(Response::new()
+        .add_attribute("action", "leave_category")
+        .add_attribute("name", name)
+        .add_attribute("category", category))
+}
+
+pub fn execute_approve_category_member(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    name: String,
+    category: String,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.owner != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let key = name.as_bytes();
+    if !CATEGORY_MEMBERS.has(deps.storage, (&category, key)) {
+        return Err(ContractError::NotInCategory { name, category });
+    }
+    CATEGORY_MEMBERS.save(deps.storage, (&category, key), &true)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "approve_category_member")
+        .add_attribute("name", name)
+        .add_attribute("category", category))
+}
+
+pub fn execute_add_verifier(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    address: String,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.owner != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let verifier = deps.api.addr_validate(&address)?;
+    if VERIFIERS.has(deps.storage, &verifier) {
+        return Err(ContractError::VerifierExists { address });
+    }
+    VERIFIERS.save(deps.storage, &verifier, &Empty {})?;
+
+    Ok(Response::new()
+        .add_attribute("action", "add_verifier")
+        .add_attribute("verifier", verifier))
+}
+
+pub fn execute_remove_verifier(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    address: String,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.owner != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let verifier = deps.api.addr_validate(&address)?;
+    if !VERIFIERS.has(deps.storage, &verifier) {
+        return Err(ContractError::VerifierNotExists { address });
+    }
+    VERIFIERS.remove(deps.storage, &verifier);
+
+    Ok(Response::new()
+        .add_attribute("action", "remove_verifier")
+        .add_attribute("verifier", verifier))
+}
+
+pub fn execute_add_registrar(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    address: String,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.owner != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let registrar = deps.api.addr_validate(&address)?;
+    if REGISTRARS.has(deps.storage, &registrar) {
+        return Err(ContractError::RegistrarExists { address });
+    }
+    REGISTRARS.save(deps.storage, &registrar, &Empty {})?;
+
+    Ok(Response::new()
+        .add_attribute("action", "add_registrar")
+        .add_attribute("registrar", registrar))
+}
+
+pub fn execute_remove_registrar(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    address: String,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.owner != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let registrar = deps.api.addr_validate(&address)?;
+    if !REGISTRARS.has(deps.storage, &registrar) {
+        return Err(ContractError::RegistrarNotExists { address });
+    }
+    REGISTRARS.remove(deps.storage, &registrar);
+
+    Ok(Response::new()
+        .add_attribute("action", "remove_registrar")
+        .add_attribute("registrar", registrar))
+}
+
+/// execute_register_for registers a name to `owner` on behalf of a registrar, without charging the price
+pub fn execute_register_for(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    name: String,
+    owner: String,
+    bio: String,
+    website: String,
+) -> Result<Response, ContractError> {
+    if !REGISTRARS.has(deps.storage, &info.sender) {
+        return Err(ContractError::Unauthorized {});
+    }
+    let owner = deps.api.addr_validate(&owner)?;
+    let paid: Vec<Coin> = info.funds.iter().filter(|fee| !fee.amount.is_zero()).cloned().collect();
+
+    let (expires_at, burned) = register_name(deps.storage, &env, owner.clone(), name.clone(), bio, website, &paid)?;
+
+    Ok(Response::new()
+        .add_messages(burn_messages(burned))
+        .add_attribute("action", "register_for")
+        .add_attribute("name", name)
+        .add_attribute("owner", owner)
+        .add_attribute("registrar", info.sender)
+        .add_attribute("fees", coins_attribute(&paid))
+        .add_attribute("expires_at", expires_at.map(|expires_at| expires_at.seconds().to_string()).unwrap_or_default()))
+}
+
+/// execute_set_verified sets or clears the verified badge, callable by the admin or a verifier
+pub fn execute_set_verified(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    name: String,
+    verified: bool,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.owner != info.sender && !VERIFIERS.has(deps.storage, &info.sender) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let key = name.as_bytes();
+    name_resolver().update(deps.storage, key, |record| {
+        if let Some(mut record) = record {
+            record.verified = verified;
+            Ok(record)
+        } else {
+            Err(ContractError::NameNotExists { name: name.clone() })
+        }
+    })?;
+
+    Ok(Response::new()
+        .add_attribute("action", "set_verified")
+        .add_attribute("name", name)
+        .add_attribute("verified", verified.to_string())
+        .add_attribute("verifier", info.sender))
+}
+
+pub fn execute_add_attestation_issuer(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    address: String,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.owner != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let issuer = deps.api.addr_validate(&address)?;
+    if ATTESTATION_ISSUERS.has(deps.storage, &issuer) {
+        return Err(ContractError::IssuerExists { address });
+    }
+    ATTESTATION_ISSUERS.save(deps.storage, &issuer, &Empty {})?;
+
+    Ok(Response::new()
+        .add_attribute("action", "add_attestation_issuer")
+        .add_attribute("issuer", issuer))
+}
+
+/// execute_remove_attestation_issuer stops an issuer from writing, existing attestations are kept
+pub fn execute_remove_attestation_issuer(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    address: String,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.owner != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let issuer = deps.api.addr_validate(&address)?;
+    if !ATTESTATION_ISSUERS.has(deps.storage, &issuer) {
+        return Err(ContractError::IssuerNotExists { address });
+    }
+    ATTESTATION_ISSUERS.remove(deps.storage, &issuer);
+
+    Ok(Response::new()
+        .add_attribute("action", "remove_attestation_issuer")
+        .add_attribute("issuer", issuer))
+}
+
+pub fn execute_set_attestation_opt_in(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    name: String,
+    accept: bool,
+) -> Result<Response, ContractError> {
+    assert_unlocked(deps.storage, &name)?;
+    let key = name.as_bytes();
+    let mut record = match name_resolver().may_load(deps.storage, key)? {
+        Some(record) => record,
+        None => return Err(ContractError::NameNotExists { name }),
+    };
+    if !is_authorized(deps.storage, &env, &record.owner, &info.sender, GrantAction::Attestations)? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    record.accept_attestations = accept;
+    name_resolver().save(deps.storage, key, &record)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "set_attestation_opt_in")
+        .add_attribute("name", name)
+        .add_attribute("accept", accept.to_string()))
+}
+
+pub fn execute_attest(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    name: String,
+    key: String,
+    value: String,
+) -> Result<Response, ContractError> {
+    if !ATTESTATION_ISSUERS.has(deps.storage, &info.sender) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let record = match name_resolver().may_load(deps.storage, name.as_bytes())? {
+        Some(record) => record,
+        None => return Err(ContractError::NameNotExists { name }),
+    };
+    if !record.accept_attestations {
+        return Err(ContractError::AttestationsNotAccepted { name });
+    }
+
+    let key_length = key.len() as u64;
+    let value_length = value.len() as u64;
+
+    if let Some(c) = key.chars().find(|c| invalid_char(*c)) {
+        return Err(ContractError::InvalidCharacter { c });
+    }
+
+    if (key_length) > MAX_ATTESTATION_KEY_LENGTH {
+        return Err(ContractError::AttestationKeyTooLong {
+            key_length,
+            max_length: MAX_ATTESTATION_KEY_LENGTH,
+        });
+    }
+
+    if (value_length) > MAX_ATTESTATION_VALUE_LENGTH {
+        return Err(ContractError::AttestationValueTooLong {
+            value_length,
+            max_length: MAX_ATTESTATION_VALUE_LENGTH,
+        });
+    }
+
+    ATTESTATIONS.save(deps.storage, (name.as_bytes(), &info.sender, &key), &value)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "attest")
+        .add_attribute("name", name)
+        .add_attribute("issuer", info.sender)
+        .add_attribute("key", key))
+}
+
+/// execute_remove_attestation can be called by the issuer of the attestation or the name owner
+pub fn execute_remove_attestation(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    name: String,
+    issuer: String,
+    key: String,
+) -> Result<Response, ContractError> {
+    let issuer = deps.api.addr_validate(&issuer)?;
+    let record = match name_resolver().may_load(deps.storage, name.as_bytes())? {
+        Some(record) => record,
+        None => return Err(ContractError::NameNotExists { name }),
+    };
+    if info.sender != issuer
+        && !is_authorized(deps.storage, &env, &record.owner, &info.sender, GrantAction::Attestations)?
+    {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let attestation_key = (name.as_bytes(), &issuer, key.as_str());
+    if !ATTESTATIONS.has(deps.storage, attestation_key) {
+        return Err(ContractError::AttestationNotExists { name, key });
+    }
+    ATTESTATIONS.remove(deps.storage, attestation_key);
+
+    Ok(Response::new()
+        .add_attribute("action", "remove_attestation")
+        .add_attribute("name", name)
+        .add_attribute("issuer", issuer)
+        .add_attribute("key", key))
+}
+
+pub fn execute_set_max_supply(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    max_supply: u64,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.owner != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if config.frozen {
+        return Err(ContractError::ConfigFrozen {});
+    }
+
+    if let Some(current) = config.max_supply {
+        if max_supply > current {
+            return Err(ContractError::MaxSupplyIncrease { max_supply, current });
+        }
+    }
+
+    let total = TOTAL_NAMES.may_load(deps.storage)?.unwrap_or_default();
+    if max_supply < total {
+        return Err(ContractError::MaxSupplyBelowTotal { max_supply, total });
+    }
+
+    CONFIG.update(deps.storage, |mut config| -> StdResult<_> {
+        config.max_supply = Some(max_supply);
+        Ok(config)
+    })?;
+
+    Ok(Response::new()
+        .add_attribute("action", "set_max_supply")
+        .add_attribute("max_supply", max_supply.to_string()))
+}
+
+/// execute_set_paused stops or resumes every execute of the contract
+pub fn execute_set_paused(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    paused: bool,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.owner != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    PAUSED.save(deps.storage, &paused)?;
+
+    Ok(Response::new()
+        .add_attribute("action", if paused { "pause" } else { "unpause" })
+        .add_attribute("paused", paused.to_string())
+        .add_attribute("by", info.sender))
+}
+
+pub fn execute_freeze_config(deps: DepsMut, _env: Env, info: MessageInfo) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.owner != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if config.frozen {
+        return Err(ContractError::ConfigFrozen {});
+    }
+
+    CONFIG.update(deps.storage, |mut config| -> StdResult<_> {
+        config.frozen = true;
+        Ok(config)
+    })?;
+
+    Ok(Response::new().add_attribute("action", "freeze_config"))
+}
+
+/// execute_transfer_ownership proposes `new_owner` as admin, proposing again replaces the pending owner
+pub fn execute_transfer_ownership(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    new_owner: String,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.owner != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let new_owner = deps.api.addr_validate(&new_owner)?;
+    PENDING_OWNER.save(deps.storage, &new_owner)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "transfer_ownership")
+        .add_attribute("pending_owner", new_owner))
+}
+
+/// execute_accept_ownership makes the pending owner the admin
+pub fn execute_accept_ownership(deps: DepsMut, _env: Env, info: MessageInfo) -> Result<Response, ContractError> {
+    let pending_owner = match PENDING_OWNER.may_load(deps.storage)? {
+        Some(pending_owner) => pending_owner,
+        None => return Err(ContractError::NoPendingOwner {}),
+    };
+    if pending_owner != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    PENDING_OWNER.remove(deps.storage);
+    let previous_owner = CONFIG.load(deps.storage)?.owner;
+    CONFIG.update(deps.storage, |mut config| -> StdResult<_> {
+        config.owner = pending_owner;
+        Ok(config)
+    })?;
+
+    Ok(Response::new()
+        .add_attribute("action", "accept_ownership")
+        .add_attribute("previous_owner", previous_owner)
+        .add_attribute("owner", info.sender))
+}
+
+/// execute_sunset records the successor contract, calling it again repoints the successor
+pub fn execute_sunset(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    successor: String,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.owner != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let successor = deps.api.addr_validate(&successor)?;
+    SUCCESSOR.save(deps.storage, &successor)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "sunset")
+        .add_attribute("successor", successor))
+}
+
+pub fn execute_continue_migration(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    limit: Option<u32>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.owner != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let limit = limit.unwrap_or(DEFAULT_MIGRATION_BATCH) as usize;
+    let done = migrate_records(deps.storage, &env, Some(limit))?;
+
+    Ok(Response::new()
+        .add_attribute("action", "continue_migration")
+        .add_attribute("done", done.to_string()))
+}
+
+/// execute_rebuild_indexes lets the admin repair the indexes of a batch of records
+pub fn execute_rebuild_indexes(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.owner != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+    rebuild_indexes(deps.storage, &env, start_after, limit)
+}
+
+/// rebuild_indexes writes the owner index entry and the owner snapshot of every record after `start_after`
+/// again, and drops the index entries of their owners pointing at names they no longer hold
+fn rebuild_indexes(
+    storage: &mut dyn Storage,
+    env: &Env,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> Result<Response, ContractError> {
+    let limit = limit.unwrap_or(DEFAULT_MIGRATION_BATCH) as usize;
+    let start = start_after.as_ref().map(|name| Bound::exclusive(name.as_bytes()));
+    let records = name_resolver()
+        .range(storage, start, None, Order::Ascending)
+        .take(limit)
+        .collect::<StdResult<Vec<_>>>()?;
+
+    let mut owners: Vec<&Addr> = records.iter().map(|(_, record)| &record.owner).collect();
+    owners.sort();
+    owners.dedup();
+    let mut dropped = 0;
+    for owner in owners {
+        let indexed = name_resolver()
+            .idx
+            .owner
+            .prefix(owner.clone())
+            .keys_raw(storage, None, None, Order::Ascending)
+            .collect::<Vec<_>>();
+        for key in indexed {
+            match name_resolver().may_load(storage, &key)? {
+                Some(record) if &record.owner == owner => {}
+                _ => {
+                    OWNER_INDEX.remove(storage, (owner, &key));
+                    dropped += 1;
+                }
+            }
+        }
+    }
+    for (key, record) in &records {
+        // saving writes the index entry of the current owner
+        name_resolver().save(storage, key, record)?;
+        if OWNERS.may_load(storage, key)? != Some(Some(record.owner.clone())) {
+            OWNERS.save(storage, key, &Some(record.owner.clone()), env.block.height)?;
+        }
+    }
+
+    let next = match records.last() {
+        Some((key, _)) if records.len() == limit => String::from_utf8_lossy(key).into_owned(),
+        _ => String::new(),
+    };
+    Ok(Response::new()
+        .add_attribute("action", "rebuild_indexes")
+        .add_attribute("rebuilt", records.len().to_string())
+        .add_attribute("dropped", dropped.to_string())
+        .add_attribute("next", next))
+}
+
+/// execute_set_gateway_key registers, rotates or revokes the key a gateway signs resolution payloads with
+pub fn execute_set_gateway_key(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    id: String,
+    pubkey: Option<Binary>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.owner != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    match &pubkey {
+        Some(pubkey) => {
+            // compressed or uncompressed SEC1 encoding
+            let valid = matches!((pubkey.len(), pubkey.first()), (33, Some(2 | 3)) | (65, Some(4)));
+            if !valid {
+                return Err(ContractError::InvalidGatewayKey { id });
+            }
+            GATEWAY_KEYS.save(deps.storage, &id, pubkey)?;
+        }
+        None => GATEWAY_KEYS.remove(deps.storage, &id),
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "set_gateway_key")
+        .add_attribute("id", id)
+        .add_attribute("revoked", pubkey.is_none().to_string()))
+}
+
+pub fn execute_fund_sponsorship(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    open: bool,
+) -> Result<Response, ContractError> {
+    let mut sponsorship = SPONSORSHIPS
+        .may_load(deps.storage, &info.sender)?
+        .unwrap_or(Sponsorship { balance: vec![], open });
+    sponsorship.open = open;
+    add_coins(&mut sponsorship.balance, &info.funds);
+    SPONSORSHIPS.save(deps.storage, &info.sender, &sponsorship)?;
+    hold_escrow(deps.storage, &info.funds)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "fund_sponsorship")
+        .add_attribute("sponsor", info.sender)
+        .add_attribute("open", open.to_string()))
+}
+
+pub fn execute_set_sponsored_addresses(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    add: Vec<String>,
+    remove: Vec<String>,
+) -> Result<Response, ContractError> {
+    if !SPONSORSHIPS.has(deps.storage, &info.sender) {
+        return Err(ContractError::SponsorshipNotExists { sponsor: info.sender.into_string() });
+    }
+
+    for address in &add {
+        let beneficiary = deps.api.addr_validate(address)?;
+        SPONSORED_ADDRESSES.save(deps.storage, (&info.sender, &beneficiary), &Empty {})?;
+    }
+    for address in &remove {
+        let beneficiary = deps.api.addr_validate(address)?;
+        SPONSORED_ADDRESSES.remove(deps.storage, (&info.sender, &beneficiary));
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "set_sponsored_addresses")
+        .add_attribute("sponsor", info.sender)
+        .add_attribute("added", add.len().to_string())
+        .add_attribute("removed", remove.len().to_string()))
+}
+
+/// execute_withdraw_sponsorship closes the sender's sponsorship and returns what is left of it
+pub fn execute_withdraw_sponsorship(deps: DepsMut, _env: Env, info: MessageInfo) -> Result<Response, ContractError> {
+    let sponsorship = match SPONSORSHIPS.may_load(deps.storage, &info.sender)? {
+        Some(sponsorship) => sponsorship,
+        None => return Err(ContractError::SponsorshipNotExists { sponsor: info.sender.into_string() }),
+    };
+    SPONSORSHIPS.remove(deps.storage, &info.sender);
+    release_escrow(deps.storage, &sponsorship.balance)?;
+
+    if sponsorship.balance.is_empty() {
+        return Ok(Response::new()
+            .add_attribute("action", "withdraw_sponsorship")
+            .add_attribute("to", info.sender));
+    }
+    Ok(send_tokens(sponsorship.balance, "withdraw_sponsorship", info.sender))
+}
+
+/// execute_grant lets `grantee` perform `actions` on all names of the sender, replacing any previous grant
+pub fn execute_grant(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    grantee: String,
+    actions: Vec<GrantAction>,
+    expires: Option<Timestamp>,
+) -> Result<Response, ContractError> {
+    let grantee = deps.api.addr_validate(&grantee)?;
+    if grantee == info.sender || actions.is_empty() {
+        return Err(ContractError::InvalidGrant {});
+    }
+    if let Some(expires) = expires {
+        if expires <= env.block.time {
+            return Err(ContractError::InvalidGrant {});
+        }
+    }
+
+    GRANTS.save(deps.storage, (&info.sender, &grantee), &Grant { actions, expires })?;
+
+    Ok(Response::new()
+        .add_attribute("action", "grant")
+        .add_attribute("owner", info.sender)
+        .add_attribute("grantee", grantee))
+}
+
+pub fn execute_revoke(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    grantee: String,
+) -> Result<Response, ContractError> {
+    let grantee = deps.api.addr_validate(&grantee)?;
+    if !GRANTS.has(deps.storage, (&info.sender, &grantee)) {
+        return Err(ContractError::GrantNotExists { grantee: grantee.into_string() });
+    }
+    GRANTS.remove(deps.storage, (&info.sender, &grantee));
+
+    Ok(Response::new()
+        .add_attribute("action", "revoke")
+        .add_attribute("owner", info.sender)
+        .add_attribute("grantee", grantee))
+}
+
+pub fn execute_commit_promo_names(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    hashes: Vec<HexBinary>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.owner != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    for hash in &hashes {
+        if hash.len() != 32 {
+            return Err(StdError::generic_err(format!("Invalid sha256 hash (hash {})", hash)).into());
+        }
+        PROMO_COMMITMENTS.save(deps.storage, hash.as_slice(), &Empty {})?;
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "commit_promo_names")
+        .add_attribute("count", hashes.len().to_string()))
+}
+
+pub fn execute_remove_promo_names(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    hashes: Vec<HexBinary>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.owner != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    for hash in &hashes {
+        PROMO_COMMITMENTS.remove(deps.storage, hash.as_slice());
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "remove_promo_names")
+        .add_attribute("count", hashes.len().to_string()))
+}
+
+/// execute_reserve_names stops the names from being registered, names already taken are kept by their owner
+pub fn execute_reserve_names(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    names: Vec<String>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.owner != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    for name in &names {
+        validate_name(name, &config.name_policy)?;
+        RESERVED.save(deps.storage, name.as_bytes(), &Empty {})?;
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "reserve_names")
+        .add_attribute("names", names.join(",")))
+}
+
+pub fn execute_unreserve_names(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    names: Vec<String>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.owner != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    for name in &names {
+        RESERVED.remove(deps.storage, name.as_bytes());
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "unreserve_names")
+        .add_attribute("names", names.join(",")))
+}
+
+/// execute_set_allowlist adds and removes the addresses that may register during the allowlist phase
+pub fn execute_set_allowlist(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    add: Vec<String>,
+    remove: Vec<String>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.owner != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    for address in &add {
+        ALLOWLIST.save(deps.storage, &deps.api.addr_validate(address)?, &Empty {})?;
+    }
+    for address in &remove {
+        ALLOWLIST.remove(deps.storage, &deps.api.addr_validate(address)?);
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "set_allowlist")
+        .add_attribute("added", add.len().to_string())
+        .add_attribute("removed", remove.len().to_string()))
+}
+
+/// execute_set_allowlist_gate sets the token whose holders may register during the allowlist phase
+pub fn execute_set_allowlist_gate(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    gate: Option<TokenGate>,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+    if config.owner != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+    if config.frozen {
+        return Err(ContractError::ConfigFrozen {});
+    }
+    let gate = match gate {
+        Some(TokenGate::Cw721 { contract }) => {
+            Some(TokenGate::Cw721 { contract: deps.api.addr_validate(contract.as_str())? })
+        }
+        Some(TokenGate::Cw20 { contract, min_balance }) => {
+            Some(TokenGate::Cw20 { contract: deps.api.addr_validate(contract.as_str())?, min_balance })
+        }
+        None => None,
+    };
+
+    config.allowlist_gate = gate;
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new().add_attribute("action", "set_allowlist_gate"))
+}
+
+/// execute_set_launch_phase overrides the launch schedule with `phase`, None follows the schedule again
+pub fn execute_set_launch_phase(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    phase: Option<LaunchPhase>,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+    if config.owner != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+    if config.frozen {
+        return Err(ContractError::ConfigFrozen {});
+    }
+
+    config.launch_phase = phase;
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "set_launch_phase")
+        .add_attribute("phase", phase.map(|phase| format!("{:?}", phase)).unwrap_or_default()))
+}
+
+/// execute_add_dictionary_words adds words to the list the name score looks for
+pub fn execute_add_dictionary_words(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    words: Vec<String>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.owner != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    for word in &words {
+        validate_dictionary_word(word)?;
+        DICTIONARY.save(deps.storage, word, &Empty {})?;
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "add_dictionary_words")
+        .add_attribute("words", words.join(",")))
+}
+
+pub fn execute_remove_dictionary_words(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    words: Vec<String>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.owner != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    for word in &words {
+        DICTIONARY.remove(deps.storage, word);
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "remove_dictionary_words")
+        .add_attribute("words", words.join(",")))
+}
+
+/// execute_assign_reserved_name registers a reserved name to `owner`, only the admin can call it
+pub fn execute_assign_reserved_name(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    name: String,
+    owner: String,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.owner != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+    let owner = deps.api.addr_validate(&owner)?;
+    if !RESERVED.has(deps.storage, name.as_bytes()) {
+        return Err(ContractError::NameNotReserved { name });
+    }
+    RESERVED.remove(deps.storage, name.as_bytes());
+
+    let (_, burned) =
+        register_name(deps.storage, &env, owner.clone(), name.clone(), String::new(), String::new(), &[])?;
+
+    Ok(Response::new()
+        .add_messages(burn_messages(burned))
+        .add_attribute("action", "assign_reserved_name")
+        .add_attribute("name", name)
+        .add_attribute("owner", owner))
+}
+
+/// execute_set_community_fund sets the address receiving round-up donations, None disables rounding up
+pub fn execute_set_community_fund(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    address: Option<String>,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+    if config.owner != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+    if config.frozen {
+        return Err(ContractError::ConfigFrozen {});
+    }
+
+    config.community_fund = address.map(|address| deps.api.addr_validate(&address)).transpose()?;
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "set_community_fund")
+        .add_attribute(
+            "community_fund",
+            config.community_fund.map(Addr::into_string).unwrap_or_default(),
+        ))
+}
+
+/// execute_set_min_delegation sets the stake required for free claims, None lets anyone claim
+pub fn execute_set_min_delegation(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    min_delegation: Option<Coin>,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+    if config.owner != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+    if config.frozen {
+        return Err(ContractError::ConfigFrozen {});
+    }
+
+    config.min_delegation = min_delegation;
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "set_min_delegation")
+        .add_attribute(
+            "min_delegation",
+            config.min_delegation.map(|coin| coin.to_string()).unwrap_or_default(),
+        ))
+}
+
+/// execute_set_edit_byte_price sets the per-byte part of the edit price, None goes back to the flat edit_price
+pub fn execute_set_edit_byte_price(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    edit_byte_price: Option<Coin>,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+    if config.owner != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+    if config.frozen {
+        return Err(ContractError::ConfigFrozen {});
+    }
+    if let Some(edit_byte_price) = &edit_byte_price {
+        if config.edit_price.iter().any(|coin| coin.denom != edit_byte_price.denom) {
+            return Err(ContractError::EditPriceDenomMismatch {});
+        }
+    }
+
+    config.edit_byte_price = edit_byte_price;
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "set_edit_byte_price")
+        .add_attribute(
+            "edit_byte_price",
+            config.edit_byte_price.map(|coin| coin.to_string()).unwrap_or_default(),
+        ))
+}
+
+/// execute_set_expiry_policy changes how long new registrations and renewals last, and what renewing costs
+pub fn execute_set_expiry_policy(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    registration_duration: Option<u64>,
+    grace_period: u64,
+    renewal_price: Option<Coin>,
+    max_renewal_horizon: Option<u64>,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+    if config.owner != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+    if config.frozen {
+        return Err(ContractError::ConfigFrozen {});
+    }
+
+    config.registration_duration = registration_duration;
+    config.grace_period = grace_period;
+    config.renewal_price = renewal_price;
+    config.max_renewal_horizon = max_renewal_horizon;
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "set_expiry_policy")
+        .add_attribute(
+            "registration_duration",
+            registration_duration.map(|duration| duration.to_string()).unwrap_or_default(),
+        )
+        .add_attribute("grace_period", grace_period.to_string())
+        .add_attribute(
+            "max_renewal_horizon",
+            max_renewal_horizon.map(|horizon| horizon.to_string()).unwrap_or_default(),
+        ))
+}
+
+/// execute_set_webhook sets or removes the contract called back on events of a name
+pub fn execute_set_webhook(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    name: String,
+    contract: Option<String>,
+    payload: Option<Binary>,
+) -> Result<Response, ContractError> {
+    assert_unlocked(deps.storage, &name)?;
+    let key = name.as_bytes();
+    let record = match name_resolver().may_load(deps.storage, key)? {
+        Some(record) => record,
+        None => return Err(ContractError::NameNotExists { name }),
+    };
+    if !is_authorized(deps.storage, &env, &record.owner, &info.sender, GrantAction::Edit)?
+        && !is_approved(deps.storage, &env, name.as_bytes(), &info.sender)?
+    {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let contract = match contract {
+        Some(contract) => deps.api.addr_validate(&contract)?,
+        None => {
+            WEBHOOKS.remove(deps.storage, key);
+            return Ok(Response::new()
+                .add_attribute("action", "remove_webhook")
+                .add_attribute("name", name));
+        }
+    };
+    let webhook = Webhook {
+        contract: contract.clone(),
+        payload: payload.unwrap_or_default(),
+        notified_expiry: None,
+    };
+    WEBHOOKS.save(deps.storage, key, &webhook)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "set_webhook")
+        .add_attribute("name", name)
+        .add_attribute("contract", contract))
+}
+
+/// execute_notify_expiring calls the webhook of a name expiring within the notice period, once per expiry
+pub fn execute_notify_expiring(
+    deps: DepsMut,
+    env: Env,
+    _info: MessageInfo,
+    name: String,
+) -> Result<Response, ContractError> {
+    let key = name.as_bytes();
+    let expires_at = match name_resolver().may_load(deps.storage, key)? {
+        Some(record) => record.expires_at,
+        None => return Err(ContractError::NameNotExists { name }),
+    };
+    let mut webhook = match WEBHOOKS.may_load(deps.storage, key)? {
+        Some(webhook) => webhook,
+        None => return Err(ContractError::WebhookNotExists { name }),
+    };
+
+    let expires_at = match expires_at {
+        Some(expires_at) if env.block.time.plus_seconds(EXPIRY_NOTICE_PERIOD) >= expires_at => expires_at,
+        _ => return Err(ContractError::NotExpiringSoon { name }),
+    };
+    if webhook.notified_expiry == Some(expires_at) {
+        return Err(ContractError::NotExpiringSoon { name });
+    }
+    webhook.notified_expiry = Some(expires_at);
+    WEBHOOKS.save(deps.storage, key, &webhook)?;
+
+    let hook = webhook_message(deps.storage, &name, NameEvent::Expiring { expires_at })?;
+
+    Ok(Response::new()
+        .add_submessages(hook)
+        .add_attribute("action", "notify_expiring")
+        .add_attribute("name", name))
+}
+
+/// execute_set_text_record stores or replaces a text record of the name
+pub fn execute_set_text_record(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    name: String,
+    key: String,
+    value: String,
+) -> Result<Response, ContractError> {
+    assert_unlocked(deps.storage, &name)?;
+    let config = CONFIG.load(deps.storage)?;
+    let action = PricedAction::Edit { bytes: (key.len() + value.len()) as u64 };
+    let prices = action_prices(deps.as_ref(), &config, action, &name, &info.sender)?;
+    assert_sent_sufficient_coin(&info.funds, &prices)?;
+    let (paid, surplus) = split_payment(deps.storage, &config, &prices, &info, false)?;
+
+    let record = match name_resolver().may_load(deps.storage, name.as_bytes())? {
+        Some(record) => record,
+        None => return Err(ContractError::NameNotExists { name }),
+    };
+    if !is_authorized(deps.storage, &env, &record.owner, &info.sender, GrantAction::Edit)?
+        && !is_approved(deps.storage, &env, name.as_bytes(), &info.sender)?
+    {
+        return Err(ContractError::Unauthorized {});
+    }
+    if is_expired(&record, &env) {
+        return Err(ContractError::NameExpired { name });
+    }
+
+    let key_length = key.len() as u64;
+    let value_length = value.len() as u64;
+
+    if let Some(c) = key.chars().find(|c| invalid_char(*c)) {
+        return Err(ContractError::InvalidCharacter { c });
+    }
+
+    if (key_length) > MAX_TEXT_KEY_LENGTH {
+        return Err(ContractError::TextKeyTooLong {
+            key_length,
+            max_length: MAX_TEXT_KEY_LENGTH,
+        });
+    }
+
+    if (value_length) > MAX_TEXT_VALUE_LENGTH {
+        return Err(ContractError::TextValueTooLong {
+            value_length,
+            max_length: MAX_TEXT_VALUE_LENGTH,
+        });
+    }
+
+    let storage_key = (name.as_bytes(), key.as_str());
+    if !TEXT_RECORDS.has(deps.storage, storage_key) {
+        let count = TEXT_RECORDS
+            .prefix(name.as_bytes())
+            .keys(deps.storage, None, None, Order::Ascending)
+            .count() as u64;
+        if count >= MAX_TEXT_RECORDS {
+            return Err(ContractError::TooManyTextRecords { max_records: MAX_TEXT_RECORDS });
+        }
+    }
+    TEXT_RECORDS.save(deps.storage, storage_key, &value)?;
+
+    update_owner_stats(deps.storage, &info.sender, |stats| add_coins(&mut stats.total_spent, &paid))?;
+    let burned = collect_fees(deps.storage, &config, &paid)?;
+
+    update_name_stats(deps.storage, name.as_bytes(), |stats| stats.edits += 1)?;
+    record_history(deps.storage, &env, &name, &info.sender, HistoryEvent::Edited {})?;
+    let hook = webhook_message(deps.storage, &name, NameEvent::Edited {})?;
+
+    Ok(Response::new()
+        .add_messages(surplus)
+        .add_messages(burn_messages(burned))
+        .add_submessages(hook)
+        .add_attribute("action", "set_text_record")
+        .add_attribute("name", name)
+        .add_attribute("key", key))
+}
+
+/// execute_remove_text_record deletes a text record of the name
+pub fn execute_remove_text_record(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    name: String,
+    key: String,
+) -> Result<Response, ContractError> {
+    assert_unlocked(deps.storage, &name)?;
+    let record = match name_resolver().may_load(deps.storage, name.as_bytes())? {
+        Some(record) => record,
+        None => return Err(ContractError::NameNotExists { name }),
+    };
+    if !is_authorized(deps.storage, &env, &record.owner, &info.sender, GrantAction::Edit)?
+        && !is_approved(deps.storage, &env, name.as_bytes(), &info.sender)?
+    {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let storage_key = (name.as_bytes(), key.as_str());
+    if !TEXT_RECORDS.has(deps.storage, storage_key) {
+        return Err(ContractError::TextRecordNotExists { name, key });
+    }
+    TEXT_RECORDS.remove(deps.storage, storage_key);
+
+    update_name_stats(deps.storage, name.as_bytes(), |stats| stats.edits += 1)?;
+    record_history(deps.storage, &env, &name, &info.sender, HistoryEvent::Edited {})?;
+    let hook = webhook_message(deps.storage, &name, NameEvent::Edited {})?;
+
+    Ok(Response::new()
+        .add_submessages(hook)
+        .add_attribute("action", "remove_text_record")
+        .add_attribute("name", name)
+        .add_attribute("key", key))
+}
+
+/// authorize_parent loads the parent of a subname, failing unless `sender` may edit it
+fn authorize_parent(deps: Deps, env: &Env, sender: &Addr, parent: String) -> Result<NameRecord, ContractError> {
+    assert_unlocked(deps.storage, &parent)?;
+    let record = match name_resolver().may_load(deps.storage, parent.as_bytes())? {
+        Some(record) => record,
+        None => return Err(ContractError::NameNotExists { name: parent }),
+    };
+    if !is_authorized(deps.storage, env, &record.owner, sender, GrantAction::Edit)?
+        && !is_approved(deps.storage, env, parent.as_bytes(), sender)?
+    {
+        return Err(ContractError::Unauthorized {});
+    }
+    if is_expired(&record, env) {
+        return Err(ContractError::NameExpired { name: parent });
+    }
+    Ok(record)
+}
+
+/// execute_register_subname creates or updates `label.parent`
+#[allow(clippy::too_many_arguments)]
+pub fn execute_register_subname(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    parent: String,
+    label: String,
+    owner: String,
+    bio: String,
+    website: String,
+) -> Result<Response, ContractError> {
+    authorize_parent(deps.as_ref(), &env, &info.sender, parent.clone())?;
+    let config = CONFIG.load(deps.storage)?;
+    validate_name(&label, &config.name_policy)?;
+    // a dot in the label would make the subname unreachable
+    if label.contains('.') {
+        return Err(ContractError::InvalidCharacter { c: '.' });
+    }
+    validate_profile(&bio, &website, &config.name_policy)?;
+
+    let owner = deps.api.addr_validate(&owner)?;
+    SUBNAMES.save(deps.storage, (parent.as_bytes(), &label), &Subname { owner: owner.clone(), bio, website })?;
+
+    Ok(Response::new()
+        .add_attribute("action", "register_subname")
+        .add_attribute("name", format!("{}.{}", label, parent))
+        .add_attribute("owner", owner))
+}
+
+pub fn execute_remove_subname(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    parent: String,
+    label: String,
+) -> Result<Response, ContractError> {
+    authorize_parent(deps.as_ref(), &env, &info.sender, parent.clone())?;
+    let name = format!("{}.{}", label, parent);
+    if !SUBNAMES.has(deps.storage, (parent.as_bytes(), &label)) {
+        return Err(ContractError::SubnameNotExists { name });
+    }
+    SUBNAMES.remove(deps.storage, (parent.as_bytes(), &label));
+
+    Ok(Response::new()
+        .add_attribute("action", "remove_subname")
+        .add_attribute("name", name))
+}
+
+/// execute_set_address sets or removes the address of the name on another chain
+pub fn execute_set_address(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    name: String,
+    chain_id: String,
+    address: Option<String>,
+) -> Result<Response, ContractError> {
+    assert_unlocked(deps.storage, &name)?;
+    let record = match name_resolver().may_load(deps.storage, name.as_bytes())? {
+        Some(record) => record,
+        None => return Err(ContractError::NameNotExists { name }),
+    };
+    if !is_authorized(deps.storage, &env, &record.owner, &info.sender, GrantAction::Edit)?
+        && !is_approved(deps.storage, &env, name.as_bytes(), &info.sender)?
+    {
+        return Err(ContractError::Unauthorized {});
+    }
+    if is_expired(&record, &env) {
+        return Err(ContractError::NameExpired { name });
+    }
+
+    // addresses of other chains cannot be validated here, only their shape is checked
+    let valid_chain_id = !chain_id.is_empty()
+        && (chain_id.len() as u64) <= MAX_CHAIN_ID_LENGTH
+        && !chain_id.contains(invalid_char);
+    let valid_address = address.as_ref().is_none_or(|address| {
+        !address.is_empty()
+            && (address.len() as u64) <= MAX_CHAIN_ADDRESS_LENGTH
+            && address.chars().all(|c| c.is_ascii_alphanumeric())
+    });
+    if !valid_chain_id || !valid_address {
+        return Err(ContractError::InvalidChainAddress { chain_id });
+    }
+
+    let key = (name.as_bytes(), chain_id.as_str());
+    match &address {
+        Some(address) => CHAIN_ADDRESSES.save(deps.storage, key, address)?,
+        None => CHAIN_ADDRESSES.remove(deps.storage, key),
+    }
+
+    update_name_stats(deps.storage, name.as_bytes(), |stats| stats.edits += 1)?;
+    record_history(deps.storage, &env, &name, &info.sender, HistoryEvent::Edited {})?;
+    let hook = webhook_message(deps.storage, &name, NameEvent::Edited {})?;
+
+    Ok(Response::new()
+        .add_submessages(hook)
+        .add_attribute("action", "set_address")
+        .add_attribute("name", name)
+        .add_attribute("chain_id", chain_id)
+        .add_attribute("address", address.unwrap_or_default()))
+}
+
+/// execute_set_primary_name sets the name the address of the sender resolves back to
+pub fn execute_set_primary_name(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    name: String,
+) -> Result<Response, ContractError> {
+    let record = match name_resolver().may_load(deps.storage, name.as_bytes())? {
+        Some(record) => record,
+        None => return Err(ContractError::NameNotExists { name }),
+    };
+    if record.owner != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+    if is_expired(&record, &env) {
+        return Err(ContractError::NameExpired { name });
+    }
+
+    PRIMARY_NAME.save(deps.storage, &info.sender, &name)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "set_primary_name")
+        .add_attribute("name", name)
+        .add_attribute("owner", info.sender))
+}
+
+/// execute_reserve holds an available name for the sender against hold_deposit, replacing the previous
+/// hold of the name once it expired and the sender's own hold at any time
+pub fn execute_reserve(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    name: String,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    validate_name(&name, &config.name_policy)?;
+    assert_registration_open(deps.as_ref(), &env, &info.sender)?;
+    let deposit = match &config.hold_deposit {
+        Some(deposit) => deposit.clone(),
+        None => return Err(ContractError::HoldsDisabled {}),
+    };
+    let Payment { change, .. } = charge(&info.funds, std::slice::from_ref(&deposit))?;
+
+    let key = name.as_bytes();
+    if AUCTIONS.has(deps.storage, key) {
+        return Err(ContractError::NameInAuction { name });
+    }
+    if RESERVED.has(deps.storage, key) {
+        return Err(ContractError::NameReserved { name });
+    }
+    if let Some(until) = cooldown_end(deps.storage, &config, &env, key)? {
+        if TOMBSTONES.load(deps.storage, key)?.previous_owner != info.sender {
+            return Err(ContractError::NameInCooldown { name, until });
+        }
+    }
+    if let Some(record) = name_resolver().may_load(deps.storage, key)? {
+        if !is_released(&record, &config, &env) {
+            return Err(ContractError::NameTaken { name });
+        }
+    }
+    let mut burned = vec![];
+    if let Some(hold) = HOLDS.may_load(deps.storage, key)? {
+        if hold.holder != info.sender && env.block.time < hold.expires_at {
+            return Err(ContractError::NameHeld { name, until: hold.expires_at });
+        }
+        // holding again costs a new deposit, the previous one is forfeited
+        burned = settle_hold(deps.storage, &config, key, hold)?;
+    }
+
+    let hold = Hold {
+        holder: info.sender.clone(),
+        deposit,
+        expires_at: env.block.time.plus_seconds(config.hold_duration),
+    };
+    hold_escrow(deps.storage, std::slice::from_ref(&hold.deposit))?;
+    HOLDS.save(deps.storage, key, &hold)?;
+
+    let mut response = Response::new().add_messages(burn_messages(burned));
+    if !change.is_empty() {
+        response = response.add_message(BankMsg::Send { to_address: info.sender.to_string(), amount: change });
+    }
+    Ok(response
+        .add_attribute("action", "reserve")
+        .add_attribute("name", name)
+        .add_attribute("holder", info.sender)
+        .add_attribute("expires_at", hold.expires_at.to_string()))
+}
+
+/// live_hold is the hold on the name at `env`, if it has not expired
+fn live_hold(storage: &dyn Storage, env: &Env, key: &[u8]) -> StdResult<Option<Hold>> {
+    Ok(HOLDS.may_load(storage, key)?.filter(|hold| env.block.time < hold.expires_at))
+}
+
+/// discount_hold takes the deposit of the sender's hold on the name off the `prices` of the same denom
+fn discount_hold(
+    storage: &dyn Storage,
+    env: &Env,
+    name: &str,
+    sender: &Addr,
+    mut prices: Vec<Coin>,
+) -> StdResult<Vec<Coin>> {
+    if let Some(hold) = live_hold(storage, env, name.as_bytes())?.filter(|hold| &hold.holder == sender) {
+        for price in prices.iter_mut().filter(|price| price.denom == hold.deposit.denom) {
+            price.amount = price.amount.saturating_sub(hold.deposit.amount);
+        }
+    }
+    Ok(prices)
+}
+
+/// settle_hold removes the hold, its deposit is collected as a fee. Returns what is to be burned
+fn settle_hold(storage: &mut dyn Storage, config: &Config, key: &[u8], hold: Hold) -> StdResult<Vec<Coin>> {
+    HOLDS.remove(storage, key);
+    release_escrow(storage, std::slice::from_ref(&hold.deposit))?;
+    collect_fees(storage, config, &[hold.deposit])
+}
+
+/// execute_set_hold_terms sets the deposit and duration of holds, no deposit disables Reserve
+pub fn execute_set_hold_terms(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    hold_deposit: Option<Coin>,
+    hold_duration: u64,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+    if config.owner != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+    if config.frozen {
+        return Err(ContractError::ConfigFrozen {});
+    }
+
+    config.hold_deposit = hold_deposit;
+    config.hold_duration = hold_duration;
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "set_hold_terms")
+        .add_attribute("hold_deposit", config.hold_deposit.map(|coin| coin.to_string()).unwrap_or_default())
+        .add_attribute("hold_duration", hold_duration.to_string()))
+}
+
+/// execute_set_premium_decay sets the premium charged for names right after they expired and ran out of grace period
+pub fn execute_set_premium_decay(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    premium_multiplier: u64,
+    premium_window: u64,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+    if config.owner != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+    if config.frozen {
+        return Err(ContractError::ConfigFrozen {});
+    }
+
+    config.premium_multiplier = premium_multiplier;
+    config.premium_window = premium_window;
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "set_premium_decay")
+        .add_attribute("premium_multiplier", premium_multiplier.to_string())
+        .add_attribute("premium_window", premium_window.to_string()))
+}
+
+/// execute_set_referral_percent sets the percent of registration prices paid to referrers
+pub fn execute_set_referral_percent(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    referral_percent: u64,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+    if config.owner != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+    if config.frozen {
+        return Err(ContractError::ConfigFrozen {});
+    }
+    if referral_percent > 100 {
+        return Err(ContractError::InvalidReferralPercent {});
+    }
+
+    config.referral_percent = referral_percent;
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "set_referral_percent")
+        .add_attribute("referral_percent", referral_percent.to_string()))
+}
+
+/// execute_watch_name holds the deposit of the sender until the name becomes available
+pub fn execute_watch_name(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    name: String,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    validate_name(&name, &config.name_policy)?;
+    assert_sent_sufficient_coin(&info.funds, config.watch_deposit.as_slice())?;
+
+    // watching again tops up the deposit
+    NAME_WATCHERS.update(deps.storage, (name.as_bytes(), &info.sender), |deposit| -> StdResult<_> {
+        let mut deposit = deposit.unwrap_or_default();
+        add_coins(&mut deposit, &info.funds);
+        Ok(deposit)
+    })?;
+    hold_escrow(deps.storage, &info.funds)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "watch_name")
+        .add_attribute("name", name)
+        .add_attribute("watcher", info.sender))
+}
+
+/// execute_unwatch_name refunds the deposit of the sender and stops watching the name
+pub fn execute_unwatch_name(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    name: String,
+) -> Result<Response, ContractError> {
+    let key = (name.as_bytes(), &info.sender);
+    let deposit = match NAME_WATCHERS.may_load(deps.storage, key)? {
+        Some(deposit) => deposit,
+        None => return Err(ContractError::NotWatching { name }),
+    };
+    NAME_WATCHERS.remove(deps.storage, key);
+    release_escrow(deps.storage, &deposit)?;
+
+    let mut response = Response::new();
+    if !deposit.is_empty() {
+        response = response.add_message(BankMsg::Send { to_address: info.sender.to_string(), amount: deposit });
+    }
+    Ok(response
+        .add_attribute("action", "unwatch_name")
+        .add_attribute("name", name)
+        .add_attribute("watcher", info.sender))
+}
+
+/// execute_notify_watchers notifies and refunds the watchers of a name nobody holds anymore
+pub fn execute_notify_watchers(
+    deps: DepsMut,
+    env: Env,
+    _info: MessageInfo,
+    name: String,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if let Some(record) = name_resolver().may_load(deps.storage, name.as_bytes())? {
+        if !is_released(&record, &config, &env) {
+            return Err(ContractError::NameTaken { name });
+        }
+    }
+
+    let response = notify_watchers(deps.storage, &name, Response::new())?;
+    Ok(response.add_attribute("action", "notify_watchers").add_attribute("name", name))
+}
+
+/// notify_watchers adds a name_available event and the deposit refund of every watcher of the name
+fn notify_watchers(storage: &mut dyn Storage, name: &str, mut response: Response) -> StdResult<Response> {
+    let key = name.as_bytes();
+    let watchers = NAME_WATCHERS
+        .prefix(key)
+        .range(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    for (watcher, deposit) in watchers {
+        NAME_WATCHERS.remove(storage, (key, &watcher));
+        release_escrow(storage, &deposit)?;
+        response = response.add_event(
+            Event::new("name_available").add_attribute("name", name).add_attribute("watcher", &watcher),
+        );
+        if !deposit.is_empty() {
+            response = response.add_message(BankMsg::Send { to_address: watcher.into_string(), amount: deposit });
+        }
+    }
+    Ok(response)
+}
+
+/// execute_set_watch_deposit sets the deposit required to watch a name
+pub fn execute_set_watch_deposit(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    watch_deposit: Option<Coin>,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+    if config.owner != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+    if config.frozen {
+        return Err(ContractError::ConfigFrozen {});
+    }
+
+    config.watch_deposit = watch_deposit;
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "set_watch_deposit")
+        .add_attribute(
+            "watch_deposit",
+            config.watch_deposit.map(|coin| coin.to_string()).unwrap_or_default(),
+        ))
+}
+
+/// execute_set_release_cooldown sets how long released and revoked names are kept for their previous owner
+pub fn execute_set_release_cooldown(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    release_cooldown: u64,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+    if config.owner != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+    if config.frozen {
+        return Err(ContractError::ConfigFrozen {});
+    }
+
+    config.release_cooldown = release_cooldown;
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "set_release_cooldown")
+        .add_attribute("release_cooldown", release_cooldown.to_string()))
+}
+
+/// webhook_message builds the callback telling the webhook of `name` about `event`, if it has one
+fn webhook_message(storage: &dyn Storage, name: &str, event: NameEvent) -> StdResult<Vec<SubMsg>> {
+    let webhook = match WEBHOOKS.may_load(storage, name.as_bytes())? {
+        Some(webhook) => webhook,
+        None => return Ok(vec![]),
+    };
+
+    let msg = NameHookMsg::NameHook {
+        name: name.to_string(),
+        event,
+        payload: webhook.payload,
+    };
+    let callback = WasmMsg::Execute {
+        contract_addr: webhook.contract.into_string(),
+        msg: to_binary(&msg)?,
+        funds: vec![],
+    };
+    Ok(vec![SubMsg::reply_on_error(callback, WEBHOOK_REPLY_ID)])
+}
+
+/// action_prices lists the coins `sender` can pay `action` on `name` with, asking the fee calculator
+/// when one is configured instead of the built-in pricing
+fn action_prices(
+    deps: Deps,
+    config: &Config,
+    action: PricedAction,
+    name: &str,
+    sender: &Addr,
+) -> StdResult<Vec<Coin>> {
+    let calculator = match &config.fee_calculator {
+        Some(calculator) => calculator,
+        None => return Ok(accepted_prices(config, action)),
+    };
+    let query = FeeQueryMsg::Fee {
+        action: action.name().to_string(),
+        name: name.to_string(),
+        sender: sender.to_string(),
+    };
+    let response: FeeResponse = deps.querier.query_wasm_smart(calculator, &query)?;
+    Ok(response.prices)
+}
+
+/// split_payment returns which of the accepted `prices` the sender pays, and the message sending everything sent
+/// above that price back to the sender, or with `round_up` donating it to the community fund
+fn split_payment(
+    storage: &mut dyn Storage,
+    config: &Config,
+    prices: &[Coin],
+    info: &MessageInfo,
+    round_up: bool,
+) -> Result<(Vec<Coin>, Vec<BankMsg>), ContractError> {
+    let Payment { charge, change: remainder } = charge(&info.funds, prices)?;
+    let paid: Vec<Coin> = charge.into_iter().collect();
+    let community_fund = match (&config.community_fund, round_up) {
+        (Some(community_fund), true) => community_fund,
+        (None, true) => return Err(ContractError::NoCommunityFund {}),
+        _ if remainder.is_empty() => return Ok((paid, vec![])),
+        // without round_up the overpayment goes back to the sender
+        _ => {
+            let refund = BankMsg::Send { to_address: info.sender.to_string(), amount: remainder };
+            return Ok((paid, vec![refund]));
+        }
+    };
+    if remainder.is_empty() {
+        return Ok((paid, vec![]));
+    }
+
+    let mut total = DONATIONS.may_load(storage)?.unwrap_or_default();
+    add_coins(&mut total, &remainder);
+    DONATIONS.save(storage, &total)?;
+    let mut donated = DONOR_TOTALS.may_load(storage, &info.sender)?.unwrap_or_default();
+    add_coins(&mut donated, &remainder);
+    DONOR_TOTALS.save(storage, &info.sender, &donated)?;
+
+    let donation = BankMsg::Send {
+        to_address: community_fund.to_string(),
+        amount: remainder,
+    };
+    Ok((paid, vec![donation]))
+}
+
+fn execute_refund(deps: DepsMut, env: Env, info: MessageInfo) -> Result<Response, ContractError> {
+    let balance = revenue(deps.as_ref(), &env)?;
+    let config = CONFIG.load(deps.storage)?;
+
+    if config.owner != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    // funds held for users and beneficiary shares stay in the contract
+    let collector = config.fee_collector.unwrap_or(config.owner);
+    if balance.is_empty() {
+        return Ok(Response::new()
+            .add_attribute("action", "refund")
+            .add_attribute("to", collector));
+    }
+    Ok(send_tokens(balance, "refund", collector))
+}
+
+/// execute_set_fee_calculator sets the contract queried for the price of every paid action
+pub fn execute_set_fee_calculator(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    address: Option<String>,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+    if config.owner != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+    if config.frozen {
+        return Err(ContractError::ConfigFrozen {});
+    }
+
+    config.fee_calculator = address.map(|address| deps.api.addr_validate(&address)).transpose()?;
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "set_fee_calculator")
+        .add_attribute(
+            "fee_calculator",
+            config.fee_calculator.map(Addr::into_string).unwrap_or_default(),
+        ))
+}
+
+/// execute_set_ibc_channels sets the channels other chains may open and resolve names over
+pub fn execute_set_ibc_channels(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    channels: Vec<String>,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+    if config.owner != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+    if config.frozen {
+        return Err(ContractError::ConfigFrozen {});
+    }
+
+    config.ibc_channels = channels;
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "set_ibc_channels")
+        .add_attribute("ibc_channels", config.ibc_channels.join(",")))
+}
+
+/// execute_set_treasury sets where revenue goes and which share of every fee is set aside for each beneficiary
+pub fn execute_set_treasury(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    fee_collector: Option<String>,
+    revenue_split: Vec<RevenueShare>,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+    if config.owner != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+    if config.frozen {
+        return Err(ContractError::ConfigFrozen {});
+    }
+
+    let revenue_split = revenue_split
+        .into_iter()
+        .map(|share| {
+            Ok(Beneficiary {
+                address: deps.api.addr_validate(&share.address)?,
+                percent: share.percent,
+            })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+    if revenue_split.iter().map(|beneficiary| beneficiary.percent).sum::<u64>() > 100 {
+        return Err(ContractError::InvalidRevenueSplit {});
+    }
+    config.fee_collector = fee_collector.map(|address| deps.api.addr_validate(&address)).transpose()?;
+    config.revenue_split = revenue_split;
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new().add_attribute("action", "set_treasury"))
+}
+
+/// collect_fees keeps `fees` as revenue, or returns them to be burned when the deployment burns its fees
+fn collect_fees(storage: &mut dyn Storage, config: &Config, fees: &[Coin]) -> StdResult<Vec<Coin>> {
+    let paid: Vec<Coin> = fees.iter().filter(|fee| !fee.amount.is_zero()).cloned().collect();
+    let mut collected = FEES_COLLECTED.may_load(storage)?.unwrap_or_default();
+    add_coins(&mut collected, &paid);
+    FEES_COLLECTED.save(storage, &collected)?;
+    if config.burn_fees {
+        return Ok(paid);
+    }
+    split_revenue(storage, config, fees)?;
+    Ok(vec![])
+}
+
+/// burn_messages is the message burning `coins`, if there is anything to burn
+fn burn_messages(coins: Vec<Coin>) -> Vec<BankMsg> {
+    if coins.is_empty() {
+        return vec![];
+    }
+    vec![BankMsg::Burn { amount: coins }]
+}
+
+/// split_revenue sets aside each beneficiary's share of `fees`, the rest stays revenue
+fn split_revenue(storage: &mut dyn Storage, config: &Config, fees: &[Coin]) -> StdResult<()> {
+    for beneficiary in &config.revenue_split {
+        let share: Vec<Coin> = fees
+            .iter()
+            .map(|fee| coin(fee.amount.multiply_ratio(beneficiary.percent, 100u64).u128(), &fee.denom))
+            .filter(|share| !share.amount.is_zero())
+            .collect();
+        if share.is_empty() {
+            continue;
+        }
+        TREASURY.update(storage, &beneficiary.address, |balance| -> StdResult<_> {
+            let mut balance = balance.unwrap_or_default();
+            add_coins(&mut balance, &share);
+            Ok(balance)
+        })?;
+        hold_escrow(storage, &share)?;
+    }
+    Ok(())
+}
+
+/// execute_withdraw sends `amount` of `denom` to a beneficiary out of its share,
+/// or to the fee collector out of the revenue
+pub fn execute_withdraw(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    amount: Uint128,
+    denom: String,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let requested = coin(amount.u128(), &denom);
+
+    if *config.fee_collector.as_ref().unwrap_or(&config.owner) == info.sender {
+        let available = revenue(deps.as_ref(), &env)?
+            .into_iter()
+            .find(|balance| balance.denom == denom)
+            .unwrap_or_else(|| coin(0, &denom));
+        if available.amount < amount {
+            return Err(ContractError::WithdrawTooLarge { available });
+        }
+    } else {
+        let mut balance = TREASURY.may_load(deps.storage, &info.sender)?.unwrap_or_default();
+        if !deduct_coin(&mut balance, &requested) {
+            let available = balance
+                .into_iter()
+                .find(|balance| balance.denom == denom)
+                .unwrap_or_else(|| coin(0, &denom));
+            return Err(ContractError::WithdrawTooLarge { available });
+        }
+        TREASURY.save(deps.storage, &info.sender, &balance)?;
+        release_escrow(deps.storage, std::slice::from_ref(&requested))?;
+    }
+
+    Ok(send_tokens(vec![requested], "withdraw", info.sender))
+}
+
+/// revenue is the contract balance minus the funds held for users
+fn revenue(deps: Deps, env: &Env) -> StdResult<Vec<Coin>> {
+    let mut balance = deps.querier.query_all_balances(&env.contract.address)?;
+    subtract_coins(&mut balance, &ESCROWED.may_load(deps.storage)?.unwrap_or_default());
+    Ok(balance)
+}
+
+/// coins_attribute renders `coins` as an attribute value, e.g. 5token,2earth
+fn coins_attribute(coins: &[Coin]) -> String {
+    coins.iter().map(Coin::to_string).collect::<Vec<_>>().join(",")
+}
+
+/// hold_escrow records `coins` as held for a user
+fn hold_escrow(storage: &mut dyn Storage, coins: &[Coin]) -> StdResult<()> {
+    let mut escrowed = ESCROWED.may_load(storage)?.unwrap_or_default();
+    add_coins(&mut escrowed, coins);
+    ESCROWED.save(storage, &escrowed)
+}
+
+/// release_escrow records `coins` as no longer held for a user, because they were paid out or spent
+fn release_escrow(storage: &mut dyn Storage, coins: &[Coin]) -> StdResult<()> {
+    let mut escrowed = ESCROWED.may_load(storage)?.unwrap_or_default();
+    subtract_coins(&mut escrowed, coins);
+    ESCROWED.save(storage, &escrowed)
+}
+
+/// credit_payout adds `coins` to what `address` can claim, they are held for it until then
+fn credit_payout(storage: &mut dyn Storage, address: &Addr, coins: &[Coin]) -> StdResult<()> {
+    PAYOUTS.update(storage, address, |claimable| -> StdResult<_> {
+        let mut claimable = claimable.unwrap_or_default();
+        add_coins(&mut claimable, coins);
+        Ok(claimable)
+    })?;
+    hold_escrow(storage, coins)
+}
+
+/// execute_claim_payout sends the sender everything credited to it
+pub fn execute_claim_payout(deps: DepsMut, _env: Env, info: MessageInfo) -> Result<Response, ContractError> {
+    let claimable = PAYOUTS.may_load(deps.storage, &info.sender)?.unwrap_or_default();
+    if claimable.is_empty() {
+        return Err(ContractError::NothingToClaim {});
+    }
+    PAYOUTS.remove(deps.storage, &info.sender);
+    release_escrow(deps.storage, &claimable)?;
+
+    Ok(send_tokens(claimable, "claim_payout", info.sender))
+}
+
+/// is_authorized tells whether `sender` may perform `action` on a name held by `owner`,
+/// either as the owner itself, as one of its operators or through an unexpired grant covering that action
+fn is_authorized(
+    storage: &dyn Storage,
+    env: &Env,
+    owner: &Addr,
+    sender: &Addr,
+    action: GrantAction,
+) -> StdResult<bool> {
+    if owner == sender || OPERATORS.has(storage, (owner, sender)) {
+        return Ok(true);
+    }
+
+    let authorized = GRANTS.may_load(storage, (owner, sender))?.is_some_and(|grant| {
+        grant.actions.contains(&action) && grant.expires.is_none_or(|expires| env.block.time < expires)
+    });
+    Ok(authorized)
+}
+
+/// update_owner_stats applies `action` to the portfolio summary of `owner`
+fn update_owner_stats(
+    storage: &mut dyn Storage,
+    owner: &Addr,
+    action: impl FnOnce(&mut OwnerStats),
+) -> StdResult<()> {
+    let mut stats = OWNER_STATS.may_load(storage, owner)?.unwrap_or_default();
+    action(&mut stats);
+    OWNER_STATS.save(storage, owner, &stats)
+}
+
+/// update_name_stats applies `action` to the counters of the name stored under `key`
+fn update_name_stats(storage: &mut dyn Storage, key: &[u8], action: impl FnOnce(&mut NameStats)) -> StdResult<()> {
+    let mut stats = NAME_STATS.may_load(storage, key)?.unwrap_or_default();
+    action(&mut stats);
+    NAME_STATS.save(storage, key, &stats)
+}
+
+fn send_tokens(amount: Vec<Coin>, action: &str, address: Addr) -> Response {
+    Response::new()
+        .add_message(BankMsg::Send {
+            to_address: address.to_string(),
+            amount,
+        })
+        .add_attribute("action", action)
+        .add_attribute("to", address.to_string())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::ResolveRecord { name } => query_resolver(deps, env, name),
+        QueryMsg::ResolveRecords { names } => query_resolve_records(deps, env, names),
+        QueryMsg::RecordStats { name } => query_record_stats(deps, env, name),
+        QueryMsg::Config {} => query_config(deps),
+        QueryMsg::Supply {} => query_supply(deps),
+        QueryMsg::Stats {} => query_stats(deps),
+        QueryMsg::Sunset {} => query_sunset(deps),
+        QueryMsg::PendingOwner {} => query_pending_owner(deps),
+        QueryMsg::Invariants {} => query_invariants(deps, env),
+        QueryMsg::Phase {} => query_phase(deps, env),
+        QueryMsg::MigrationStatus {} => query_migration_status(deps),
+        QueryMsg::MigrationPlan { batch_size } => query_migration_plan(deps, batch_size),
+        QueryMsg::Sponsorship { sponsor, address } => query_sponsorship(deps, sponsor, address),
+        QueryMsg::Donations { address } => query_donations(deps, address),
+        QueryMsg::Webhook { name } => query_webhook(deps, name),
+        QueryMsg::OwnerOf { token_id, include_expired } => query_owner_of(deps, env, token_id, include_expired),
+        QueryMsg::NftInfo { token_id } => query_nft_info(deps, env, token_id),
+        QueryMsg::AllOperators { owner, start_after, limit } => query_all_operators(deps, owner, start_after, limit),
+        QueryMsg::Tokens { owner, start_after, limit } => query_tokens(deps, env, owner, start_after, limit),
+        QueryMsg::AllTokens { start_after, limit } => query_all_tokens(deps, env, start_after, limit),
+        QueryMsg::TransferOffer { name } => query_transfer_offer(deps, name),
+        QueryMsg::Listings { start_after, limit } => query_listings(deps, start_after, limit),
+        QueryMsg::Auction { name } => query_auction(deps, name),
+        QueryMsg::Hold { name } => query_hold(deps, env, name),
+        QueryMsg::RegistrationPrice { name, duration } => query_registration_price(deps, env, name, duration),
+        QueryMsg::Tombstone { name } => query_tombstone(deps, name),
+        QueryMsg::NameScore { name } => query_name_score(deps, name),
+        QueryMsg::Payout { address } => query_payout(deps, address),
+        QueryMsg::Treasury { address } => query_treasury(deps, address),
+        QueryMsg::Balances {} => query_balances(deps, env),
+        QueryMsg::PausedActions {} => query_paused_actions(deps),
+        QueryMsg::ResolveAddress { name, chain_id } => query_resolve_address(deps, env, name, chain_id),
+        QueryMsg::TextRecords { name, start_after, limit } => query_text_records(deps, name, start_after, limit),
+        QueryMsg::PrimaryName { address } => query_primary_name(deps, env, address),
+        QueryMsg::Watchers { name, start_after, limit } => query_watchers(deps, name, start_after, limit),
+        QueryMsg::NamesOfOwner { owner, start_after, limit } => {
+            query_names_of_owner(deps, env, owner, start_after, limit)
+        }
+        QueryMsg::ReservedNames { start_after, limit } => query_reserved_names(deps, start_after, limit),
+        QueryMsg::OwnersSnapshot { at_height, start_after, limit } => {
+            query_owners_snapshot(deps, at_height, start_after, limit)
+        }
+        QueryMsg::ResolveRecordAt { name, height } => query_resolve_record_at(deps, name, height),
+        QueryMsg::AllNames { start_after, limit } => query_all_names(deps, env, start_after, limit),
+        QueryMsg::NamesWithPrefix { prefix, start_after, limit } => {
+            query_names_with_prefix(deps, env, prefix, start_after, limit)
+        }
+        QueryMsg::TransferMemos { address, start_after, limit } => {
+            query_transfer_memos(deps, address, start_after, limit)
+        }
+        QueryMsg::CheckAvailability { names } => query_check_availability(deps, env, names),
+        QueryMsg::Grants { owner, start_after, limit } => query_grants(deps, owner, start_after, limit),
+        QueryMsg::OwnerStats { address } => query_owner_stats(deps, env, address),
+        QueryMsg::RegistrationQuota { address } => query_registration_quota(deps, env, address),
+        QueryMsg::Categories {} => query_categories(deps),
+        QueryMsg::CategoryMembers { category, pending, start_after, limit } => {
+            query_category_members(deps, category, pending, start_after, limit)
+        }
+        QueryMsg::Verifiers {} => query_verifiers(deps),
+        QueryMsg::Registrars {} => query_registrars(deps),
+        QueryMsg::AttestationIssuers {} => query_attestation_issuers(deps),
+        QueryMsg::ResolvePayload { name } => query_resolve_payload(deps, env, name),
+        QueryMsg::GatewayKeys {} => query_gateway_keys(deps),
+        QueryMsg::NameHistory { name, start_after, limit } => query_name_history(deps, name, start_after, limit),
+        QueryMsg::VerifyGatewaySignature { id, payload, signature } => {
+            query_verify_gateway_signature(deps, id, payload, signature)
+        }
+        QueryMsg::Attestations { name, start_after, limit } => query_attestations(deps, name, start_after, limit),
+        QueryMsg::DnsZone { name, tld } => query_dns_zone(deps, env, name, tld),
+    }
+}
+
+/// page_limit is the page size asked for, requests above MAX_LIMIT fail instead of being cut short
+fn page_limit(limit: Option<u32>) -> StdResult<usize> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT);
+    if limit > MAX_LIMIT {
+        return Err(StdError::generic_err(format!(
+            "Limit too large (limit {} max_limit {})",
+            limit, MAX_LIMIT
+        )));
+    }
+    Ok(limit as usize)
+}
+
+/// filtered_page collects up to `limit` entries passing `keep`, giving up after MAX_SCAN entries
+/// so a page hiding many skipped entries fails instead of looking like the last one
+fn filtered_page<T>(
+    entries: impl Iterator<Item = StdResult<(Vec<u8>, T)>>,
+    limit: usize,
+    keep: impl Fn(&T) -> bool,
+) -> StdResult<Vec<(Vec<u8>, T)>> {
+    let mut page = vec![];
+    let mut last_scanned = vec![];
+    for (scanned, entry) in entries.enumerate() {
+        if page.len() == limit {
+            break;
+        }
+        if scanned == MAX_SCAN {
+            return Err(StdError::generic_err(format!(
+                "Too many entries skipped, continue with start_after {}",
+                String::from_utf8_lossy(&last_scanned)
+            )));
+        }
+        let (key, value) = entry?;
+        last_scanned.clone_from(&key);
+        if keep(&value) {
+            page.push((key, value));
+        }
+    }
+    Ok(page)
+}
+
+fn query_resolver(deps: Deps, env: Env, name: String) -> StdResult<Binary> {
+    to_binary(&resolve_record(deps, env, name)?)
+}
+
+fn query_resolve_records(deps: Deps, env: Env, names: Vec<String>) -> StdResult<Binary> {
+    if names.len() > MAX_RESOLVE_NAMES {
+        return Err(StdError::generic_err(format!(
+            "Too many names (count {} max {})",
+            names.len(),
+            MAX_RESOLVE_NAMES
+        )));
+    }
+
+    let records = names
+        .into_iter()
+        .map(|name| {
+            let record = resolve_record(deps, env.clone(), name)?;
+            Ok(Some(record).filter(|record| record.address.is_some()))
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+    to_binary(&ResolveRecordsResponse { records })
+}
+
+fn query_record_stats(deps: Deps, env: Env, name: String) -> StdResult<Binary> {
+    let stats = NAME_STATS.may_load(deps.storage, name.as_bytes())?.unwrap_or_default();
+    to_binary(&RecordStatsResponse { record: resolve_record(deps, env, name)?, stats })
+}
+
+pub(crate) fn resolve_record(deps: Deps, env: Env, name: String) -> StdResult<ResolveRecordResponse> {
+    let key = name.as_bytes();
+    let record = name_resolver().may_load(deps.storage, key)?;
+    if record.is_none() {
+        // names registered with a dot are not subnames
+        if let Some((label, parent)) = name.split_once('.') {
+            return resolve_subname(deps, env, label, parent);
+        }
+    }
+
+    let successor = SUCCESSOR.may_load(deps.storage)?;
+    let frozen = ADMIN_FROZEN_NAMES.has(deps.storage, key) || FROZEN_NAMES.has(deps.storage, key);
+    let record = match record {
+        Some(record) => record,
+        None => {
+            // names unknown here may already live in the successor contract
+            if let Some(successor) = successor {
+                return Ok(query_successor_record(deps, successor, name));
+            }
+            return Ok(ResolveRecordResponse { frozen, ..unresolved_record(None) });
+        }
+    };
+
+    // expired names no longer resolve, also during their grace period
+    if is_expired(&record, &env) {
+        return Ok(ResolveRecordResponse {
+            exists: true,
+            owner: Some(record.owner),
+            expires_at: record.expires_at,
+            frozen,
+            ..unresolved_record(successor)
+        });
+    }
+
+    let text_records = TEXT_RECORDS.prefix(key).keys_raw(deps.storage, None, None, Order::Ascending).count() as u64;
+    Ok(ResolveRecordResponse {
+        exists: true,
+        address: Some(record.owner.to_string()),
+        owner: Some(record.owner),
+        bio: Some(record.bio),
+        website: Some(record.website),
+        verified: Some(record.verified),
+        cache_control: Some(record.cache_control),
+        expires_at: record.expires_at,
+        socials: Some(record.socials),
+        content_hash: record.content_hash,
+        text_records,
+        locked: LOCKED_NAMES.has(deps.storage, key),
+        frozen,
+        successor,
+    })
+}
+
+/// unresolved_record is the answer for a name that resolves to nothing
+fn unresolved_record(successor: Option<Addr>) -> ResolveRecordResponse {
+    ResolveRecordResponse {
+        exists: false,
+        address: None,
+        owner: None,
+        bio: None,
+        website: None,
+        verified: None,
+        cache_control: None,
+        expires_at: None,
+        socials: None,
+        content_hash: None,
+        text_records: 0,
+        locked: false,
+        frozen: false,
+        successor,
+    }
+}
+
+/// resolve_subname resolves `label.parent` for as long as the parent itself resolves
+fn resolve_subname(deps: Deps, env: Env, label: &str, parent: &str) -> StdResult<ResolveRecordResponse> {
+    let successor = SUCCESSOR.may_load(deps.storage)?;
+    let record = name_resolver().may_load(deps.storage, parent.as_bytes())?;
+    let expires_at = record.as_ref().and_then(|record| record.expires_at);
+    let subname = match &record {
+        Some(record) if !is_expired(record, &env) => SUBNAMES.may_load(deps.storage, (parent.as_bytes(), label))?,
+        _ => None,
+    };
+    let subname = match subname {
+        Some(subname) => subname,
+        None => return Ok(ResolveRecordResponse { expires_at, ..unresolved_record(successor) }),
+    };
+
+    Ok(ResolveRecordResponse {
+        exists: true,
+        address: Some(subname.owner.to_string()),
+        owner: Some(subname.owner),
+        bio: Some(subname.bio),
+        website: Some(subname.website),
+        cache_control: record.map(|record| record.cache_control),
+        expires_at,
+        ..unresolved_record(successor)
+    })
+}
+
+/// The part of a successor's ResolveRecord answer we forward. Unknown fields are
+/// ignored so a successor with a newer response layout keeps resolving.
+#[derive(Deserialize)]
+#[serde(crate = "cosmwasm_schema::serde")]
+struct SuccessorRecord {
+    address: Option<String>,
+    bio: Option<String>,
+    website: Option<String>,
+    verified: Option<bool>,
+    #[serde(default)]
+    socials: Option<Socials>,
+    #[serde(default)]
+    content_hash: Option<String>,
+}
+
+fn query_successor_record(deps: Deps, successor: Addr, name: String) -> ResolveRecordResponse {
+    // a failing successor must not break resolution, treat it as not found
+    let record = deps
+        .querier
+        .query_wasm_smart::<SuccessorRecord>(&successor, &QueryMsg::ResolveRecord { name })
+        .ok();
+
+    match record {
+        Some(record) => ResolveRecordResponse {
+            exists: record.address.is_some(),
+            address: record.address,
+            bio: record.bio,
+            website: record.website,
+            verified: record.verified,
+            socials: record.socials,
+            content_hash: record.content_hash,
+            ..unresolved_record(Some(successor))
+        },
+        None => unresolved_record(Some(successor)),
+    }
+}
+
+fn query_config(deps: Deps) -> StdResult<Binary> {
+    let mut resp: ConfigResponse = CONFIG.load(deps.storage)?.into();
+    resp.successor = SUCCESSOR.may_load(deps.storage)?;
+
+    to_binary(&resp)
+}
+
+fn query_phase(deps: Deps, env: Env) -> StdResult<Binary> {
+    let (phase, next) = launch_phase(deps.storage, &env)?;
+    to_binary(&PhaseResponse { phase, next })
+}
+
+fn query_invariants(deps: Deps, env: Env) -> StdResult<Binary> {
+    let mut violations = vec![];
+    if MIGRATION.may_load(deps.storage)?.is_some() {
+        violations.push("migration in progress, counters and indexes are not complete yet".to_string());
+    }
+
+    // counters, indexes and snapshots against the records
+    let mut held: BTreeMap<Addr, u64> = BTreeMap::new();
+    for item in name_resolver().range(deps.storage, None, None, Order::Ascending) {
+        let (key, record) = item?;
+        *held.entry(record.owner.clone()).or_default() += 1;
+        if OWNERS.may_load(deps.storage, &key)?.flatten().as_ref() != Some(&record.owner) {
+            violations.push(format!("owner snapshot of {} is not {}", String::from_utf8_lossy(&key), record.owner));
+        }
+    }
+    let total: u64 = held.values().sum();
+    let counted = TOTAL_NAMES.may_load(deps.storage)?.unwrap_or_default();
+    if counted != total {
+        violations.push(format!("total_names is {} but {} names are registered", counted, total));
+    }
+    let indexed = name_resolver().idx.owner.keys_raw(deps.storage, None, None, Order::Ascending).count() as u64;
+    if indexed != total {
+        violations.push(format!("owner index has {} entries for {} names", indexed, total));
+    }
+    for (owner, count) in &held {
+        let indexed = name_resolver()
+            .idx
+            .owner
+            .prefix(owner.clone())
+            .keys_raw(deps.storage, None, None, Order::Ascending)
+            .count() as u64;
+        if indexed != *count {
+            violations.push(format!("owner index lists {} names of {} instead of {}", indexed, owner, count));
+        }
+    }
+    for item in OWNER_STATS.range(deps.storage, None, None, Order::Ascending) {
+        let (owner, stats) = item?;
+        let count = held.get(&owner).copied().unwrap_or_default();
+        if stats.name_count != count {
+            violations.push(format!("name_count of {} is {} but it holds {} names", owner, stats.name_count, count));
+        }
+    }
+
+    // escrow against what is owed to users and against the balance
+    let mut owed = vec![];
+    for item in SPONSORSHIPS.range(deps.storage, None, None, Order::Ascending) {
+        add_coins(&mut owed, &item?.1.balance);
+    }
+    for item in NAME_WATCHERS.range(deps.storage, None, None, Order::Ascending) {
+        add_coins(&mut owed, &item?.1);
+    }
+    for item in BIDS.range(deps.storage, None, None, Order::Ascending) {
+        add_coins(&mut owed, std::slice::from_ref(&item?.1.amount));
+    }
+    for item in TREASURY.range(deps.storage, None, None, Order::Ascending) {
+        add_coins(&mut owed, &item?.1);
+    }
+    for item in PAYOUTS.range(deps.storage, None, None, Order::Ascending) {
+        add_coins(&mut owed, &item?.1);
+    }
+    for item in HOLDS.range(deps.storage, None, None, Order::Ascending) {
+        add_coins(&mut owed, std::slice::from_ref(&item?.1.deposit));
+    }
+    let mut escrowed = ESCROWED.may_load(deps.storage)?.unwrap_or_default();
+    owed.sort_by(|a, b| a.denom.cmp(&b.denom));
+    escrowed.sort_by(|a, b| a.denom.cmp(&b.denom));
+    if owed != escrowed {
+        violations.push(format!("escrow records {:?} but {:?} is owed to users", escrowed, owed));
+    }
+    let balance = deps.querier.query_all_balances(&env.contract.address)?;
+    for coin in &escrowed {
+        let available = balance.iter().find(|c| c.denom == coin.denom).map(|c| c.amount).unwrap_or_default();
+        if available < coin.amount {
+            violations.push(format!("escrow holds {} but the balance is only {}{}", coin, available, coin.denom));
+        }
+    }
+
+    to_binary(&InvariantsResponse { holds: violations.is_empty(), violations })
+}
+
+fn query_pending_owner(deps: Deps) -> StdResult<Binary> {
+    to_binary(&PendingOwnerResponse { pending_owner: PENDING_OWNER.may_load(deps.storage)? })
+}
+
+fn query_sunset(deps: Deps) -> StdResult<Binary> {
+    let successor = SUCCESSOR.may_load(deps.storage)?;
+
+    to_binary(&SunsetResponse {
+        sunset: successor.is_some(),
+        successor,
+    })
+}
+
+fn query_migration_plan(deps: Deps, batch_size: Option<u32>) -> StdResult<Binary> {
+    let batch_size = batch_size.unwrap_or(DEFAULT_MIGRATION_BATCH).max(1) as u64;
+    let pending = MIGRATION.may_load(deps.storage)?;
+    let start = pending
+        .as_ref()
+        .and_then(|migration| migration.last_name.as_ref())
+        .map(|name| Bound::exclusive(name.as_bytes()));
+
+    let mut records_to_rewrite = 0;
+    let mut records_missing_fields = 0;
+    for item in STORED_FIELDS.range(deps.storage, start, None, Order::Ascending) {
+        let (_, fields) = item?;
+        records_to_rewrite += 1;
+        if !fields.complete() {
+            records_missing_fields += 1;
+        }
+    }
+
+    let backfill_counters = match &pending {
+        Some(migration) => migration.backfill_counters,
+        None => TOTAL_NAMES.may_load(deps.storage)?.is_none(),
+    };
+
+    to_binary(&MigrationPlanResponse {
+        resumes_pending: pending.is_some(),
+        records_to_rewrite,
+        records_missing_fields,
+        backfill_counters,
+        // a pass is only known to be the last once it rewrites less than a full batch
+        passes: records_to_rewrite / batch_size + 1,
+    })
+}
+
+fn query_migration_status(deps: Deps) -> StdResult<Binary> {
+    let resp = match MIGRATION.may_load(deps.storage)? {
+        Some(migration) => MigrationStatusResponse {
+            in_progress: true,
+            migrated: migration.migrated,
+            last_name: migration.last_name,
+        },
+        None => MigrationStatusResponse {
+            in_progress: false,
+            migrated: 0,
+            last_name: None,
+        },
+    };
+
+    to_binary(&resp)
+}
+
+fn query_sponsorship(deps: Deps, sponsor: String, address: Option<String>) -> StdResult<Binary> {
+    let sponsor = deps.api.addr_validate(&sponsor)?;
+    let sponsorship = SPONSORSHIPS
+        .may_load(deps.storage, &sponsor)?
+        .unwrap_or(Sponsorship { balance: vec![], open: false });
+
+    let eligible = match address {
+        Some(address) => {
+            let address = deps.api.addr_validate(&address)?;
+            Some(is_sponsored(deps.storage, &sponsor, &sponsorship, &address))
+        }
         None => None,
     };
-    let bio = match NAME_RESOLVER.may_load(deps.storage, key)? {
-        Some(record) => Some(String::from(&record.bio)),
+
+    to_binary(&SponsorshipResponse {
+        balance: sponsorship.balance,
+        open: sponsorship.open,
+        eligible,
+    })
+}
+
+fn query_names_of_owner(
+    deps: Deps,
+    env: Env,
+    owner: String,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Binary> {
+    let owner = deps.api.addr_validate(&owner)?;
+    let limit = page_limit(limit)?;
+    let start = start_after.as_ref().map(|name| Bound::exclusive(name.as_bytes()));
+
+    let names = name_resolver()
+        .idx
+        .owner
+        .prefix(owner)
+        .range(deps.storage, start, None, Order::Ascending);
+    let names = filtered_page(names, limit, |record| !is_expired(record, &env))?
+        .into_iter()
+        .map(|(name, _)| String::from_utf8_lossy(&name).into_owned())
+        .collect();
+
+    to_binary(&NamesResponse { names })
+}
+
+fn query_all_names(deps: Deps, env: Env, start_after: Option<String>, limit: Option<u32>) -> StdResult<Binary> {
+    let limit = page_limit(limit)?;
+    let start = start_after.as_ref().map(|name| Bound::exclusive(name.as_bytes()));
+
+    let records = name_resolver().range(deps.storage, start, None, Order::Ascending);
+    let names = filtered_page(records, limit, |record| !is_expired(record, &env))?
+        .into_iter()
+        .map(|(name, record)| NameInfo {
+            name: String::from_utf8_lossy(&name).into_owned(),
+            owner: record.owner,
+            expires_at: record.expires_at,
+        })
+        .collect();
+
+    to_binary(&AllNamesResponse { names })
+}
+
+fn query_names_with_prefix(
+    deps: Deps,
+    env: Env,
+    prefix: String,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Binary> {
+    let limit = page_limit(limit)?;
+    // names with the prefix sort right after the prefix itself, the scan stops at the first other name
+    let start = match &start_after {
+        Some(name) if *name >= prefix => Bound::exclusive(name.as_bytes()),
+        _ => Bound::inclusive(prefix.as_bytes()),
+    };
+
+    let records = name_resolver()
+        .range(deps.storage, Some(start), None, Order::Ascending)
+        .take_while(|item| item.as_ref().map_or(true, |(name, _)| name.starts_with(prefix.as_bytes())));
+    let names = filtered_page(records, limit, |record| !is_expired(record, &env))?
+        .into_iter()
+        .map(|(name, record)| NameInfo {
+            name: String::from_utf8_lossy(&name).into_owned(),
+            owner: record.owner,
+            expires_at: record.expires_at,
+        })
+        .collect();
+
+    to_binary(&AllNamesResponse { names })
+}
+
+fn query_owners_snapshot(
+    deps: Deps,
+    at_height: u64,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Binary> {
+    let limit = page_limit(limit)?;
+    let start = start_after.as_ref().map(|name| Bound::exclusive(name.as_bytes()));
+
+    // every name ever registered is a key, those without an owner at that height are skipped
+    let entries = OWNERS.keys(deps.storage, start, None, Order::Ascending).map(|key| {
+        let key = key?;
+        let owner = OWNERS.may_load_at_height(deps.storage, &key, at_height)?.flatten();
+        Ok((key, owner))
+    });
+    let owners = filtered_page(entries, limit, Option::is_some)?
+        .into_iter()
+        .filter_map(|(key, owner)| Some(NameOwner { name: String::from_utf8(key).ok()?, owner: owner? }))
+        .collect();
+
+    to_binary(&OwnersSnapshotResponse { owners })
+}
+
+fn query_resolve_record_at(deps: Deps, name: String, height: u64) -> StdResult<Binary> {
+    let owner = OWNERS.may_load_at_height(deps.storage, name.as_bytes(), height)?.flatten();
+
+    to_binary(&ResolveRecordAtResponse { owner })
+}
+
+fn query_reserved_names(deps: Deps, start_after: Option<String>, limit: Option<u32>) -> StdResult<Binary> {
+    let limit = page_limit(limit)?;
+    let start = start_after.as_ref().map(|name| Bound::exclusive(name.as_bytes()));
+
+    let names = RESERVED
+        .keys(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|name| name.map(|name| String::from_utf8_lossy(&name).into_owned()))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    to_binary(&NamesResponse { names })
+}
+
+fn query_check_availability(deps: Deps, env: Env, names: Vec<String>) -> StdResult<Binary> {
+    if names.len() > MAX_AVAILABILITY_NAMES {
+        return Err(StdError::generic_err(format!(
+            "Too many names (count {} max {})",
+            names.len(),
+            MAX_AVAILABILITY_NAMES
+        )));
+    }
+    let config = CONFIG.load(deps.storage)?;
+
+    let names = names
+        .into_iter()
+        .map(|name| {
+            let status = availability(deps, &env, &config, &name)?;
+            Ok(Availability { name, status })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    to_binary(&AvailabilityResponse { names })
+}
+
+/// availability tells whether the name can be registered at `env`, or why not
+fn availability(deps: Deps, env: &Env, config: &Config, name: &str) -> StdResult<AvailabilityStatus> {
+    if validate_name(name, &config.name_policy).is_err() {
+        return Ok(AvailabilityStatus::Invalid);
+    }
+    let status = match name_resolver().may_load(deps.storage, name.as_bytes())? {
+        _ if AUCTIONS.has(deps.storage, name.as_bytes()) => AvailabilityStatus::InAuction,
+        _ if RESERVED.has(deps.storage, name.as_bytes()) => AvailabilityStatus::Reserved,
+        _ if live_hold(deps.storage, env, name.as_bytes())?.is_some() => AvailabilityStatus::Held,
+        None if cooldown_end(deps.storage, config, env, name.as_bytes())?.is_some() => AvailabilityStatus::InCooldown,
+        None => AvailabilityStatus::Available,
+        Some(record) if is_released(&record, config, env) => AvailabilityStatus::Available,
+        Some(record) if is_expired(&record, env) => AvailabilityStatus::InGracePeriod,
+        Some(_) => AvailabilityStatus::Taken,
+    };
+    Ok(status)
+}
+
+fn query_transfer_memos(
+    deps: Deps,
+    address: String,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Binary> {
+    let address = deps.api.addr_validate(&address)?;
+    let limit = page_limit(limit)?;
+    let start = start_after.as_ref().map(|name| Bound::exclusive(name.as_bytes()));
+
+    let memos = TRANSFER_MEMOS
+        .prefix(&address)
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            item.map(|(name, memo)| ReceivedMemo {
+                name: String::from_utf8_lossy(&name).into_owned(),
+                from: memo.from,
+                memo: memo.memo,
+            })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    to_binary(&TransferMemosResponse { memos })
+}
+
+fn query_name_history(deps: Deps, name: String, start_after: Option<u64>, limit: Option<u32>) -> StdResult<Binary> {
+    let limit = page_limit(limit)?;
+    let start = start_after.map(Bound::exclusive);
+
+    let entries = NAME_HISTORY
+        .prefix(name.as_bytes())
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            item.map(|(seq, entry)| NameHistoryEntry {
+                seq,
+                event: entry.event,
+                actor: entry.actor,
+                height: entry.height,
+                time: entry.time,
+            })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    to_binary(&NameHistoryResponse { entries })
+}
+
+fn query_webhook(deps: Deps, name: String) -> StdResult<Binary> {
+    let webhook = WEBHOOKS.may_load(deps.storage, name.as_bytes())?;
+
+    to_binary(&WebhookResponse {
+        contract: webhook.as_ref().map(|webhook| webhook.contract.clone()),
+        payload: webhook.map(|webhook| webhook.payload),
+    })
+}
+
+fn query_donations(deps: Deps, address: Option<String>) -> StdResult<Binary> {
+    let config = CONFIG.load(deps.storage)?;
+    let total = DONATIONS.may_load(deps.storage)?.unwrap_or_default();
+    let donated = match address {
+        Some(address) => {
+            let address = deps.api.addr_validate(&address)?;
+            Some(DONOR_TOTALS.may_load(deps.storage, &address)?.unwrap_or_default())
+        }
         None => None,
     };
-    let website = match NAME_RESOLVER.may_load(deps.storage, key)? {
-        Some(record) => Some(String::from(&record.website)),
+
+    to_binary(&DonationsResponse {
+        community_fund: config.community_fund,
+        total,
+        donated,
+    })
+}
+
+/// load_token loads the record of a name as a cw721 token, expired names do not exist as tokens
+fn load_token(deps: Deps, env: &Env, token_id: &str) -> StdResult<NameRecord> {
+    match name_resolver().may_load(deps.storage, token_id.as_bytes())? {
+        Some(record) if !is_expired(&record, env) => Ok(record),
+        _ => Err(StdError::not_found(format!("token {}", token_id))),
+    }
+}
+
+fn query_owner_of(deps: Deps, env: Env, token_id: String, include_expired: Option<bool>) -> StdResult<Binary> {
+    let record = load_token(deps, &env, &token_id)?;
+    let include_expired = include_expired.unwrap_or(false);
+
+    let approvals = NFT_APPROVALS
+        .prefix(token_id.as_bytes())
+        .range(deps.storage, None, None, Order::Ascending)
+        .filter(|item| item.as_ref().map_or(true, |(_, expires)| include_expired || !expires.is_expired(&env.block)))
+        .map(|item| {
+            item.map(|(spender, expires)| NftApproval {
+                spender: spender.into_string(),
+                expires,
+            })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    to_binary(&OwnerOfResponse {
+        owner: record.owner.into_string(),
+        approvals,
+    })
+}
+
+fn query_all_operators(
+    deps: Deps,
+    owner: String,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Binary> {
+    let owner = deps.api.addr_validate(&owner)?;
+    let limit = page_limit(limit)?;
+    let start_after = match start_after {
+        Some(operator) => Some(deps.api.addr_validate(&operator)?),
         None => None,
     };
+    let start = start_after.as_ref().map(Bound::exclusive);
 
-    let resp = ResolveRecordResponse { address, bio, website };
+    let operators = OPERATORS
+        .prefix(&owner)
+        .keys(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            item.map(|operator| NftApproval {
+                spender: operator.into_string(),
+                expires: Expiration::Never {},
+            })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
 
-    to_binary(&resp)
+    to_binary(&OperatorsResponse { operators })
+}
+
+fn query_nft_info(deps: Deps, env: Env, token_id: String) -> StdResult<Binary> {
+    let record = load_token(deps, &env, &token_id)?;
+
+    to_binary(&NftInfoResponse {
+        token_uri: TOKEN_URIS.may_load(deps.storage, token_id.as_bytes())?,
+        extension: NftExtension {
+            bio: record.bio,
+            website: record.website,
+            expires_at: record.expires_at,
+        },
+    })
+}
+
+fn query_tokens(
+    deps: Deps,
+    env: Env,
+    owner: String,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Binary> {
+    let res: NamesResponse = from_binary(&query_names_of_owner(deps, env, owner, start_after, limit)?)?;
+
+    to_binary(&TokensResponse { tokens: res.names })
+}
+
+fn query_all_tokens(deps: Deps, env: Env, start_after: Option<String>, limit: Option<u32>) -> StdResult<Binary> {
+    let limit = page_limit(limit)?;
+    let start = start_after.as_ref().map(|name| Bound::exclusive(name.as_bytes()));
+
+    let names = name_resolver().range(deps.storage, start, None, Order::Ascending);
+    let tokens = filtered_page(names, limit, |record| !is_expired(record, &env))?
+        .into_iter()
+        .map(|(name, _)| String::from_utf8_lossy(&name).into_owned())
+        .collect();
+
+    to_binary(&TokensResponse { tokens })
+}
+
+fn query_name_score(deps: Deps, name: String) -> StdResult<Binary> {
+    let config = CONFIG.load(deps.storage)?;
+    validate_name(&name, &config.name_policy).map_err(|err| StdError::generic_err(err.to_string()))?;
+    // the longest word wins, "dogecoin" starts with "doge" rather than "dog"
+    let dictionary_word = name
+        .char_indices()
+        .map(|(start, c)| start + c.len_utf8())
+        .rev()
+        .map(|end| &name[..end])
+        .find(|prefix| DICTIONARY.has(deps.storage, prefix))
+        .map(str::to_string);
+
+    to_binary(&NameScoreResponse {
+        score: score_name(&name, dictionary_word.as_deref()),
+        length: name_length(&name),
+        digits: name.chars().filter(char::is_ascii_digit).count() as u64,
+        hyphens: name.matches('-').count() as u64,
+        dictionary_word,
+    })
+}
+
+fn query_tombstone(deps: Deps, name: String) -> StdResult<Binary> {
+    to_binary(&TombstoneResponse { tombstone: TOMBSTONES.may_load(deps.storage, name.as_bytes())? })
+}
+
+fn query_auction(deps: Deps, name: String) -> StdResult<Binary> {
+    let key = name.as_bytes();
+    to_binary(&AuctionResponse {
+        auction: AUCTIONS.may_load(deps.storage, key)?,
+        highest_bid: BIDS.may_load(deps.storage, key)?,
+    })
+}
+
+fn query_registration_price(deps: Deps, env: Env, name: String, duration: Option<u64>) -> StdResult<Binary> {
+    let config = CONFIG.load(deps.storage)?;
+    let status = availability(deps, &env, &config, &name)?;
+    // whole registration periods covering `duration`, names that never expire are bought once
+    let periods = match (config.registration_duration, duration) {
+        (Some(period), Some(duration)) if period > 0 => duration.div_ceil(period).max(1),
+        _ => 1,
+    };
+    let sender = &env.contract.address;
+    let renewal = action_prices(deps, &config, PricedAction::Renew, &name, sender)?;
+    let renew = matches!(status, AvailabilityStatus::Taken | AvailabilityStatus::InGracePeriod);
+
+    let (prices, premium_ends_at) = if renew {
+        (renewal.clone(), None)
+    } else {
+        let prices = register_prices(deps, &env, &config, &name, sender)?;
+        (prices, premium_end(deps.storage, &config, &env, name.as_bytes())?)
+    };
+    // every further period is a renewal, paid in the same denom
+    let extra_periods = periods - 1;
+    let prices = if extra_periods == 0 || renewal.is_empty() {
+        prices
+    } else {
+        prices
+            .into_iter()
+            .filter_map(|price| {
+                let renewal = renewal.iter().find(|renewal| renewal.denom == price.denom)?;
+                Some(coin((price.amount + renewal.amount * Uint128::from(extra_periods)).u128(), &price.denom))
+            })
+            .collect()
+    };
+
+    to_binary(&RegistrationPriceResponse { status, renew, periods, prices, premium_ends_at })
+}
+
+fn query_hold(deps: Deps, env: Env, name: String) -> StdResult<Binary> {
+    to_binary(&HoldResponse { hold: live_hold(deps.storage, &env, name.as_bytes())? })
 }
 
-// let's not import a regexp library and just do these checks by hand
-fn invalid_char(c: char) -> bool {
-    let is_valid =
-        c.is_ascii_digit() || c.is_ascii_lowercase() || (c == '-' /*|| c == '.' || c == '_'*/);
-    !is_valid
+fn query_transfer_offer(deps: Deps, name: String) -> StdResult<Binary> {
+    let offer = TRANSFER_OFFERS.may_load(deps.storage, name.as_bytes())?;
+
+    to_binary(&TransferOfferResponse { offer })
 }
 
-/// validate_name returns an error if the name is invalid
-fn validate_name(name: &str) -> Result<(), ContractError> {
-    let length = name.len() as u64;
-    if (name.len() as u64) < MIN_NAME_LENGTH {
-        Err(ContractError::NameTooShort {
-            length,
-            min_length: MIN_NAME_LENGTH,
+fn query_listings(deps: Deps, start_after: Option<String>, limit: Option<u32>) -> StdResult<Binary> {
+    let limit = page_limit(limit)?;
+    let start = start_after.as_ref().map(|name| Bound::exclusive(name.as_bytes()));
+
+    let listings = LISTINGS
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            item.map(|(name, listing)| ListingInfo {
+                name: String::from_utf8_lossy(&name).into_owned(),
+                seller: listing.seller,
+                price: listing.price,
+                listed_at: listing.listed_at,
+            })
         })
-    } else if (name.len() as u64) > MAX_NAME_LENGTH {
-        Err(ContractError::NameTooLong {
-            length,
-            max_length: MAX_NAME_LENGTH,
+        .collect::<StdResult<Vec<_>>>()?;
+
+    to_binary(&ListingsResponse { listings })
+}
+
+fn query_treasury(deps: Deps, address: String) -> StdResult<Binary> {
+    let address = deps.api.addr_validate(&address)?;
+    let balance = TREASURY.may_load(deps.storage, &address)?.unwrap_or_default();
+    to_binary(&TreasuryResponse { balance })
+}
+
+fn query_payout(deps: Deps, address: String) -> StdResult<Binary> {
+    let address = deps.api.addr_validate(&address)?;
+    let claimable = PAYOUTS.may_load(deps.storage, &address)?.unwrap_or_default();
+    to_binary(&PayoutResponse { claimable })
+}
+
+fn query_balances(deps: Deps, env: Env) -> StdResult<Binary> {
+    to_binary(&BalancesResponse {
+        escrowed: ESCROWED.may_load(deps.storage)?.unwrap_or_default(),
+        revenue: revenue(deps, &env)?,
+    })
+}
+
+fn query_paused_actions(deps: Deps) -> StdResult<Binary> {
+    let actions = PAUSED_ACTIONS
+        .keys(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+
+    let paused = PAUSED.may_load(deps.storage)?.unwrap_or_default();
+
+    to_binary(&PausedActionsResponse { actions, paused })
+}
+
+fn query_resolve_address(deps: Deps, env: Env, name: String, chain_id: String) -> StdResult<Binary> {
+    let record = name_resolver().may_load(deps.storage, name.as_bytes())?;
+    let address = match record {
+        Some(record) if !is_expired(&record, &env) => {
+            match CHAIN_ADDRESSES.may_load(deps.storage, (name.as_bytes(), &chain_id))? {
+                Some(address) => Some(address),
+                None if chain_id == env.block.chain_id => Some(record.owner.into_string()),
+                None => None,
+            }
+        }
+        _ => None,
+    };
+
+    to_binary(&ResolveAddressResponse { address })
+}
+
+fn query_text_records(deps: Deps, name: String, start_after: Option<String>, limit: Option<u32>) -> StdResult<Binary> {
+    let limit = page_limit(limit)?;
+    let start = start_after.as_deref().map(Bound::exclusive);
+
+    let records = TEXT_RECORDS
+        .prefix(name.as_bytes())
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(|(key, value)| TextRecord { key, value }))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    to_binary(&TextRecordsResponse { records })
+}
+
+fn query_primary_name(deps: Deps, env: Env, address: String) -> StdResult<Binary> {
+    let address = deps.api.addr_validate(&address)?;
+    let mut name = PRIMARY_NAME.may_load(deps.storage, &address)?;
+    // an expired name no longer resolves, in either direction
+    if let Some(primary) = &name {
+        let record = name_resolver().may_load(deps.storage, primary.as_bytes())?;
+        if !record.is_some_and(|record| record.owner == address && !is_expired(&record, &env)) {
+            name = None;
+        }
+    }
+
+    to_binary(&PrimaryNameResponse { name })
+}
+
+fn query_watchers(deps: Deps, name: String, start_after: Option<String>, limit: Option<u32>) -> StdResult<Binary> {
+    let limit = page_limit(limit)?;
+    let start_after = match start_after {
+        Some(watcher) => Some(deps.api.addr_validate(&watcher)?),
+        None => None,
+    };
+    let start = start_after.as_ref().map(Bound::exclusive);
+
+    let watchers = NAME_WATCHERS
+        .prefix(name.as_bytes())
+        .keys(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .collect::<StdResult<Vec<_>>>()?;
+
+    to_binary(&WatchersResponse { watchers })
+}
+
+fn query_grants(deps: Deps, owner: String, start_after: Option<String>, limit: Option<u32>) -> StdResult<Binary> {
+    let owner = deps.api.addr_validate(&owner)?;
+    let limit = page_limit(limit)?;
+    let start_after = match start_after {
+        Some(grantee) => Some(deps.api.addr_validate(&grantee)?),
+        None => None,
+    };
+    let start = start_after.as_ref().map(Bound::exclusive);
+
+    let grants = GRANTS
+        .prefix(&owner)
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            item.map(|(grantee, grant)| GrantInfo {
+                grantee,
+                actions: grant.actions,
+                expires: grant.expires,
+            })
         })
-    } else {
-        match name.find(invalid_char) {
-            None => Ok(()),
-            Some(bytepos_invalid_char_start) => {
-                let c = name[bytepos_invalid_char_start..].chars().next().unwrap();
-                Err(ContractError::InvalidCharacter { c })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    to_binary(&GrantsResponse { grants })
+}
+
+fn query_stats(deps: Deps) -> StdResult<Binary> {
+    to_binary(&StatsResponse {
+        total_registered: TOTAL_REGISTRATIONS.may_load(deps.storage)?.unwrap_or_default(),
+        total_active: TOTAL_NAMES.may_load(deps.storage)?.unwrap_or_default(),
+        fees_collected: FEES_COLLECTED.may_load(deps.storage)?.unwrap_or_default(),
+    })
+}
+
+fn query_supply(deps: Deps) -> StdResult<Binary> {
+    let config = CONFIG.load(deps.storage)?;
+    let total = TOTAL_NAMES.may_load(deps.storage)?.unwrap_or_default();
+    let remaining = config.max_supply.map(|max_supply| max_supply.saturating_sub(total));
+
+    to_binary(&SupplyResponse {
+        total,
+        max_supply: config.max_supply,
+        remaining,
+    })
+}
+
+fn query_owner_stats(deps: Deps, env: Env, address: String) -> StdResult<Binary> {
+    let owner = deps.api.addr_validate(&address)?;
+    let stats = OWNER_STATS.may_load(deps.storage, &owner)?.unwrap_or_default();
+    // like the list queries, an address is summarized from its first MAX_SCAN names
+    let records = name_resolver()
+        .idx
+        .owner
+        .prefix(owner)
+        .range(deps.storage, None, None, Order::Ascending)
+        .take(MAX_SCAN)
+        .collect::<StdResult<Vec<_>>>()?;
+    let nearest_expiry = records
+        .into_iter()
+        .filter(|(_, record)| !is_expired(record, &env))
+        .filter_map(|(_, record)| record.expires_at)
+        .min();
+
+    to_binary(&OwnerStatsResponse {
+        name_count: stats.name_count,
+        first_registration: stats.first_registration,
+        nearest_expiry,
+        total_spent: stats.total_spent,
+    })
+}
+
+fn query_registration_quota(deps: Deps, env: Env, address: String) -> StdResult<Binary> {
+    let owner = deps.api.addr_validate(&address)?;
+    let config = CONFIG.load(deps.storage)?;
+    let stats = OWNER_STATS.may_load(deps.storage, &owner)?.unwrap_or_default();
+
+    to_binary(&RegistrationQuotaResponse {
+        name_count: stats.name_count,
+        max_names_per_address: config.max_names_per_address,
+        remaining: config.max_names_per_address.map(|max| max.saturating_sub(stats.name_count)),
+        next_registration_at: next_registration_at(&stats, &config, &env),
+    })
+}
+
+fn query_verifiers(deps: Deps) -> StdResult<Binary> {
+    let verifiers = VERIFIERS
+        .keys(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+
+    to_binary(&VerifiersResponse { verifiers })
+}
+
+fn query_registrars(deps: Deps) -> StdResult<Binary> {
+    let registrars = REGISTRARS
+        .keys(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+
+    to_binary(&RegistrarsResponse { registrars })
+}
+
+fn query_dns_zone(deps: Deps, env: Env, name: String, tld: String) -> StdResult<Binary> {
+    if tld.is_empty() || tld.contains(invalid_char) {
+        return Err(StdError::generic_err(format!("Invalid tld (tld {})", tld)));
+    }
+
+    let origin = format!("{}.{}.", name, tld);
+    let mut records = vec![];
+    // expired names are left out of the zone like they stop resolving
+    let record = name_resolver().may_load(deps.storage, name.as_bytes())?;
+    if let Some(record) = record.filter(|record| !is_expired(record, &env)) {
+        records.push(txt_record(&origin, "owner", record.owner.as_str()));
+        if !record.website.is_empty() {
+            records.push(txt_record(&origin, "website", &record.website));
+        }
+        if !record.bio.is_empty() {
+            records.push(txt_record(&origin, "bio", &record.bio));
+        }
+        for item in TEXT_RECORDS.prefix(name.as_bytes()).range(deps.storage, None, None, Order::Ascending) {
+            let (key, value) = item?;
+            records.push(txt_record(&origin, &key, &value));
+        }
+    }
+
+    let lines = records
+        .iter()
+        .map(|record| format!("{} IN {} {}", record.host, record.record_type, record.value))
+        .collect();
+
+    to_binary(&DnsZoneResponse { origin, records, lines })
+}
+
+/// txt_record builds a `key=value` TXT entry, split into quoted character-strings of at most
+/// MAX_TXT_STRING_LENGTH bytes
+fn txt_record(host: &str, key: &str, value: &str) -> DnsRecord {
+    let data = format!("{}={}", key, value);
+    let strings: Vec<_> = data
+        .as_bytes()
+        .chunks(MAX_TXT_STRING_LENGTH)
+        .map(|chunk| format!("\"{}\"", escape_zone_bytes(chunk)))
+        .collect();
+    DnsRecord {
+        host: host.to_string(),
+        record_type: "TXT".to_string(),
+        value: strings.join(" "),
+    }
+}
+
+/// escape_zone_bytes escapes quotes and backslashes and writes every byte outside printable ascii as `\DDD`
+/// (RFC 1035), so a value can't break out of its line of the zone file
+fn escape_zone_bytes(bytes: &[u8]) -> String {
+    let mut escaped = String::new();
+    for &byte in bytes {
+        match byte {
+            b'"' | b'\\' => {
+                escaped.push('\\');
+                escaped.push(byte as char);
             }
+            b' '..=b'~' => escaped.push(byte as char),
+            _ => escaped.push_str(&format!("\\{:03}", byte)),
         }
     }
+    escaped
+}
+
+fn query_resolve_payload(deps: Deps, env: Env, name: String) -> StdResult<Binary> {
+    let height = env.block.height;
+    let record = resolve_record(deps, env, name.clone())?;
+    let payload = to_binary(&GatewayPayload { name, record, height })?;
+    let digest = HexBinary::from(Sha256::digest(payload.as_slice()).to_vec());
+
+    to_binary(&ResolvePayloadResponse { payload, digest })
+}
+
+fn query_gateway_keys(deps: Deps) -> StdResult<Binary> {
+    let keys = GATEWAY_KEYS
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|item| item.map(|(id, pubkey)| GatewayKey { id, pubkey }))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    to_binary(&GatewayKeysResponse { keys })
+}
+
+fn query_verify_gateway_signature(deps: Deps, id: String, payload: Binary, signature: Binary) -> StdResult<Binary> {
+    let valid = match GATEWAY_KEYS.may_load(deps.storage, &id)? {
+        Some(pubkey) => {
+            let digest = Sha256::digest(payload.as_slice());
+            // malformed signatures are just not valid
+            deps.api.secp256k1_verify(&digest, &signature, &pubkey).unwrap_or(false)
+        }
+        None => false,
+    };
+
+    to_binary(&VerifyGatewaySignatureResponse { valid })
+}
+
+fn query_attestation_issuers(deps: Deps) -> StdResult<Binary> {
+    let issuers = ATTESTATION_ISSUERS
+        .keys(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+
+    to_binary(&AttestationIssuersResponse { issuers })
+}
+
+fn query_attestations(
+    deps: Deps,
+    name: String,
+    start_after: Option<(String, String)>,
+    limit: Option<u32>,
+) -> StdResult<Binary> {
+    let limit = page_limit(limit)?;
+    let start_after = match start_after {
+        Some((issuer, key)) => Some((deps.api.addr_validate(&issuer)?, key)),
+        None => None,
+    };
+    let start = start_after
+        .as_ref()
+        .map(|(issuer, key)| Bound::exclusive((issuer, key.as_str())));
+
+    let attestations = ATTESTATIONS
+        .sub_prefix(name.as_bytes())
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(|((issuer, key), value)| Attestation { issuer, key, value }))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    to_binary(&AttestationsResponse { attestations })
+}
+
+fn query_categories(deps: Deps) -> StdResult<Binary> {
+    let categories = CATEGORIES
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|item| {
+            item.map(|(category, settings)| CategoryInfo {
+                category,
+                requires_approval: settings.requires_approval,
+            })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    to_binary(&CategoriesResponse { categories })
+}
+
+fn query_category_members(
+    deps: Deps,
+    category: String,
+    pending: Option<bool>,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Binary> {
+    let limit = page_limit(limit)?;
+    let approved = !pending.unwrap_or(false);
+    let start = start_after.as_ref().map(|name| Bound::exclusive(name.as_bytes()));
+
+    let names = CATEGORY_MEMBERS
+        .prefix(&category)
+        .range(deps.storage, start, None, Order::Ascending);
+    let names = filtered_page(names, limit, |member_approved| *member_approved == approved)?
+        .into_iter()
+        .map(|(name, _)| String::from_utf8_lossy(&name).into_owned())
+        .collect();
+
+    to_binary(&CategoryMembersResponse { names })
 }
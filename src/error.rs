@@ -1,6 +1,8 @@
-use cosmwasm_std::StdError;
+use cosmwasm_std::{Coin, StdError, Timestamp};
 use thiserror::Error;
 
+use crate::state::LaunchPhase;
+
 #[derive(Error, Debug)]
 pub enum ContractError {
     #[error("{0}")]
@@ -12,9 +14,24 @@ pub enum ContractError {
     #[error("Insufficient funds sent")]
     InsufficientFundsSend {},
 
+    #[error("Denom not accepted for this payment (denom {denom})")]
+    UnacceptedDenom { denom: String },
+
     #[error("Name does not exist (name {name})")]
     NameNotExists { name: String },
 
+    #[error("Name is frozen (name {name})")]
+    NameFrozen { name: String },
+
+    #[error("Name is not frozen (name {name})")]
+    NameNotFrozen { name: String },
+
+    #[error("Name is locked by its owner (name {name})")]
+    NameLocked { name: String },
+
+    #[error("Subname does not exist (name {name})")]
+    SubnameNotExists { name: String },
+
     #[error("Name has been taken (name {name})")]
     NameTaken { name: String },
 
@@ -30,6 +47,261 @@ pub enum ContractError {
     #[error("Website too long (website_length {website_length} max_length {max_length})")]
     WebsiteTooLong { website_length: u64, max_length: u64 },
 
+    #[error("Social field too long (field {field} length {length} max_length {max_length})")]
+    SocialTooLong { field: String, length: u64, max_length: u64 },
+
+    #[error("Invalid social field (field {field} value {value})")]
+    InvalidSocial { field: String, value: String },
+
+    #[error("Content hash too long (length {length} max_length {max_length})")]
+    ContentHashTooLong { length: u64, max_length: u64 },
+
+    #[error("Content hash must be an ipfs:// or ar:// CID (value {value})")]
+    InvalidContentHash { value: String },
+
     #[error("Invalid character(char {c}")]
     InvalidCharacter { c: char },
+
+    #[error("Name is not in composed normal form (name {name})")]
+    NameNotNormalized { name: String },
+
+    #[error("Name mixes alphabets with letters that look alike (name {name})")]
+    ConfusableName { name: String },
+
+    #[error("Invalid category (category {category})")]
+    InvalidCategory { category: String },
+
+    #[error("Category does not exist (category {category})")]
+    CategoryNotExists { category: String },
+
+    #[error("Name already in category (name {name} category {category})")]
+    AlreadyInCategory { name: String, category: String },
+
+    #[error("Name not in category (name {name} category {category})")]
+    NotInCategory { name: String, category: String },
+
+    #[error("Verifier already registered (address {address})")]
+    VerifierExists { address: String },
+
+    #[error("Verifier not registered (address {address})")]
+    VerifierNotExists { address: String },
+
+    #[error("Registrar already registered (address {address})")]
+    RegistrarExists { address: String },
+
+    #[error("Registrar not registered (address {address})")]
+    RegistrarNotExists { address: String },
+
+    #[error("Gateway keys must be secp256k1 public keys (id {id})")]
+    InvalidGatewayKey { id: String },
+
+    #[error("Attestation issuer already registered (address {address})")]
+    IssuerExists { address: String },
+
+    #[error("Attestation issuer not registered (address {address})")]
+    IssuerNotExists { address: String },
+
+    #[error("Name does not accept attestations (name {name})")]
+    AttestationsNotAccepted { name: String },
+
+    #[error("Attestation key too long (key_length {key_length} max_length {max_length})")]
+    AttestationKeyTooLong { key_length: u64, max_length: u64 },
+
+    #[error("Attestation value too long (value_length {value_length} max_length {max_length})")]
+    AttestationValueTooLong { value_length: u64, max_length: u64 },
+
+    #[error("Attestation does not exist (name {name} key {key})")]
+    AttestationNotExists { name: String, key: String },
+
+    #[error("Maximum supply reached (max_supply {max_supply})")]
+    MaxSupplyReached { max_supply: u64 },
+
+    #[error("Maximum supply can only be lowered (max_supply {max_supply} current {current})")]
+    MaxSupplyIncrease { max_supply: u64, current: u64 },
+
+    #[error("Maximum supply below registered names (max_supply {max_supply} total {total})")]
+    MaxSupplyBelowTotal { max_supply: u64, total: u64 },
+
+    #[error("Config is frozen")]
+    ConfigFrozen {},
+
+    #[error("Registration is not open to the sender in this launch phase (phase {phase:?})")]
+    RegistrationNotOpen { phase: LaunchPhase },
+
+    #[error("No ownership transfer pending")]
+    NoPendingOwner {},
+
+    #[error("Contract has been sunset (successor {successor})")]
+    Sunset { successor: String },
+
+    #[error("State migration in progress")]
+    MigrationInProgress {},
+
+    #[error("Sponsorship does not exist (sponsor {sponsor})")]
+    SponsorshipNotExists { sponsor: String },
+
+    #[error("Not eligible for sponsorship (sponsor {sponsor})")]
+    NotSponsored { sponsor: String },
+
+    #[error("Insufficient sponsorship funds (sponsor {sponsor})")]
+    InsufficientSponsorFunds { sponsor: String },
+
+    #[error("Invalid grant")]
+    InvalidGrant {},
+
+    #[error("Grant does not exist (grantee {grantee})")]
+    GrantNotExists { grantee: String },
+
+    #[error("No promo commitment matches this name and secret")]
+    InvalidPromoSecret {},
+
+    #[error("No community fund configured")]
+    NoCommunityFund {},
+
+    #[error("Insufficient delegation (min_delegation {min_delegation})")]
+    InsufficientDelegation { min_delegation: Coin },
+
+    #[error("Name has expired (name {name})")]
+    NameExpired { name: String },
+
+    #[error("Renewals must be for at least one year")]
+    InvalidRenewalYears {},
+
+    #[error("Renewal goes past the renewal horizon (max_expires_at {max_expires_at})")]
+    RenewalBeyondHorizon { max_expires_at: Timestamp },
+
+    #[error("Name does not expire (name {name})")]
+    NameNotExpiring { name: String },
+
+    #[error("Webhook does not exist (name {name})")]
+    WebhookNotExists { name: String },
+
+    #[error("Name is not about to expire (name {name})")]
+    NotExpiringSoon { name: String },
+
+    #[error("Edit prices must use the same denom")]
+    EditPriceDenomMismatch {},
+
+    #[error("Name policy must accept names of at least one length")]
+    InvalidNamePolicy {},
+
+    #[error("Price tiers must be sorted by strictly increasing max_length")]
+    InvalidPriceTiers {},
+
+    #[error("Memo too long (memo_length {memo_length} max_length {max_length})")]
+    MemoTooLong { memo_length: u64, max_length: u64 },
+
+    #[error("Text record key too long (key_length {key_length} max_length {max_length})")]
+    TextKeyTooLong { key_length: u64, max_length: u64 },
+
+    #[error("Text record value too long (value_length {value_length} max_length {max_length})")]
+    TextValueTooLong { value_length: u64, max_length: u64 },
+
+    #[error("Too many text records (max_records {max_records})")]
+    TooManyTextRecords { max_records: u64 },
+
+    #[error("Text record does not exist (name {name} key {key})")]
+    TextRecordNotExists { name: String, key: String },
+
+    #[error("Invalid chain address (chain_id {chain_id})")]
+    InvalidChainAddress { chain_id: String },
+
+    #[error("Contract is paused")]
+    Paused {},
+
+    #[error("Action paused by governance (action {action})")]
+    ActionPaused { action: String },
+
+    #[error("Unknown action (action {action})")]
+    UnknownAction { action: String },
+
+    #[error("Token URI must be an https or ipfs URI (token_uri {token_uri})")]
+    InvalidTokenUri { token_uri: String },
+
+    #[error("Approval has already expired")]
+    ApprovalExpired {},
+
+    #[error("Too many names (count {count} max {max})")]
+    TooManyNames { count: u64, max: u64 },
+
+    #[error("Address holds too many names (max_names_per_address {max_names_per_address})")]
+    NameLimitReached { max_names_per_address: u64 },
+
+    #[error("Address registered a name too recently (next registration at {until})")]
+    RegistrationCooldown { until: Timestamp },
+
+    #[error("No single denom pays for every name of the batch")]
+    BatchDenomMismatch {},
+
+    #[error("No transfer offered (name {name})")]
+    OfferNotExists { name: String },
+
+    #[error("Name is not listed for sale (name {name})")]
+    ListingNotExists { name: String },
+
+    #[error("Listing price must not be zero")]
+    InvalidListingPrice {},
+
+    #[error("Marketplace fee and royalty percentages must add up to at most 100")]
+    InvalidMarketplaceFees {},
+
+    #[error("Name is held by another address (name {name} until {until})")]
+    NameHeld { name: String, until: Timestamp },
+
+    #[error("Holds are disabled")]
+    HoldsDisabled {},
+
+    #[error("Name is reserved (name {name})")]
+    NameReserved { name: String },
+
+    #[error("Name is not reserved (name {name})")]
+    NameNotReserved { name: String },
+
+    #[error("Dictionary words must be 1 to 30 lowercase letters (word {word})")]
+    InvalidDictionaryWord { word: String },
+
+    #[error("Name was just released, only its previous owner can register it (name {name} until {until})")]
+    NameInCooldown { name: String, until: Timestamp },
+
+    #[error("Name is sold by auction (name {name})")]
+    NameInAuction { name: String },
+
+    #[error("Auction does not exist (name {name})")]
+    AuctionNotExists { name: String },
+
+    #[error("Auction has ended (name {name})")]
+    AuctionEnded { name: String },
+
+    #[error("Auction has not ended yet (name {name} ends_at {ends_at})")]
+    AuctionNotEnded { name: String, ends_at: Timestamp },
+
+    #[error("Bid too low (min_bid {min_bid})")]
+    BidTooLow { min_bid: Coin },
+
+    #[error("Revenue split percentages must add up to at most 100")]
+    InvalidRevenueSplit {},
+
+    #[error("Referral percentage must be at most 100")]
+    InvalidReferralPercent {},
+
+    #[error("Registrants can't refer themselves")]
+    SelfReferral {},
+
+    #[error("Withdrawal exceeds your share (available {available})")]
+    WithdrawTooLarge { available: Coin },
+
+    #[error("Nothing to claim")]
+    NothingToClaim {},
+
+    #[error("IBC channel not allowed to resolve names (channel {channel})")]
+    IbcChannelNotAllowed { channel: String },
+
+    #[error("Resolver channels must be unordered")]
+    InvalidIbcOrder {},
+
+    #[error("Invalid resolver channel version (version {version})")]
+    InvalidIbcVersion { version: String },
+
+    #[error("Not watching name (name {name})")]
+    NotWatching { name: String },
 }
@@ -0,0 +1,194 @@
+//! App runs the contract through whole flows the way a chain would. Funds attached to an execute move
+//! from the sender to the contract, the bank messages of its response move them on, and a failing execute
+//! leaves state and balances untouched. Balances are kept for every address, so tests assert what each
+//! party ends up with.
+//!
+//! ```ignore
+//! let mut app = App::new(msg);
+//! app.fund("alice_key", &coins(10, "token"));
+//! app.execute("alice_key", &coins(10, "token"), register).unwrap();
+//! assert_eq!(app.balance(CONTRACT, "token"), Uint128::new(10));
+//! ```
+//!
+//! Bank messages move funds and wasm executes reach stand-in contracts, which take their funds and go through
+//! unless the test made them fail with `fail_contract`. Submessages reply to the contract through `reply` the
+//! way they ask to, and a failure nothing replies to fails the whole transaction. Staking messages of a response
+//! are left to the test to check.
+//!
+//! This stands in for cw-multi-test, which isn't a dependency of the crate.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info, MockApi, MockQuerier, MockStorage};
+use cosmwasm_std::{
+    from_binary, BankMsg, Coin, CosmosMsg, Empty, Env, Order, OwnedDeps, Reply, ReplyOn, Response, StdError, StdResult,
+    Storage, SubMsgResponse, SubMsgResult, Uint128, WasmMsg,
+};
+use cosmwasm_schema::serde::de::DeserializeOwned;
+
+use crate::coin_helpers::{add_coins, deduct_coin};
+use crate::contract::{execute, instantiate, query, reply};
+use crate::error::ContractError;
+use crate::msg::{ExecuteMsg, InstantiateMsg, QueryMsg};
+
+pub const ADMIN: &str = "creator";
+pub const CONTRACT: &str = cosmwasm_std::testing::MOCK_CONTRACT_ADDR;
+
+pub struct App {
+    deps: OwnedDeps<MockStorage, MockApi, MockQuerier, Empty>,
+    env: Env,
+    balances: BTreeMap<String, Vec<Coin>>,
+    failing: BTreeSet<String>,
+    replies: Vec<Response>,
+}
+
+impl App {
+    /// new instantiates the contract with `msg`, sent by ADMIN
+    pub fn new(msg: InstantiateMsg) -> Self {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        instantiate(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]), msg).expect("contract instantiates");
+
+        App { deps, env, balances: BTreeMap::new(), failing: BTreeSet::new(), replies: vec![] }
+    }
+
+    /// fund mints `coins` to `address`
+    pub fn fund(&mut self, address: &str, coins: &[Coin]) -> &mut Self {
+        add_coins(self.balances.entry(address.to_string()).or_default(), coins);
+        self.sync_balances();
+        self
+    }
+
+    /// fail_contract makes every wasm execute sent to `address` fail
+    pub fn fail_contract(&mut self, address: &str) -> &mut Self {
+        self.failing.insert(address.to_string());
+        self
+    }
+
+    /// replies are the responses of the contract to the replies of the last transaction
+    pub fn replies(&self) -> &[Response] {
+        &self.replies
+    }
+
+    /// balance is what `address` holds of `denom`
+    pub fn balance(&self, address: &str, denom: &str) -> Uint128 {
+        self.balances
+            .get(address)
+            .and_then(|coins| coins.iter().find(|coin| coin.denom == denom))
+            .map(|coin| coin.amount)
+            .unwrap_or_default()
+    }
+
+    /// advance moves to the next block, `seconds` later
+    pub fn advance(&mut self, seconds: u64) -> &mut Self {
+        self.env.block.height += 1;
+        self.env.block.time = self.env.block.time.plus_seconds(seconds);
+        self
+    }
+
+    pub fn env(&self) -> Env {
+        self.env.clone()
+    }
+
+    /// execute sends `msg` from `sender` with `funds` attached. Sending more than the sender holds fails
+    /// like the bank module would, before the contract is called
+    pub fn execute(&mut self, sender: &str, funds: &[Coin], msg: ExecuteMsg) -> Result<Response, ContractError> {
+        let balances = self.balances.clone();
+        let storage: Vec<_> = self.deps.storage.range(None, None, Order::Ascending).collect();
+
+        self.replies.clear();
+        let res = self.run_transaction(sender, funds, msg);
+        if res.is_err() {
+            // a failed transaction is reverted as a whole
+            self.balances = balances;
+            let keys: Vec<_> = self.deps.storage.range(None, None, Order::Ascending).map(|(key, _)| key).collect();
+            for key in keys {
+                self.deps.storage.remove(&key);
+            }
+            for (key, value) in storage {
+                self.deps.storage.set(&key, &value);
+            }
+        }
+        self.sync_balances();
+        res
+    }
+
+    pub fn query<T: DeserializeOwned>(&self, msg: QueryMsg) -> StdResult<T> {
+        from_binary(&query(self.deps.as_ref(), self.env.clone(), msg)?)
+    }
+
+    fn run_transaction(&mut self, sender: &str, funds: &[Coin], msg: ExecuteMsg) -> Result<Response, ContractError> {
+        self.transfer(sender, CONTRACT, funds)?;
+        self.sync_balances();
+        let response = execute(self.deps.as_mut(), self.env.clone(), mock_info(sender, funds), msg)?;
+        self.run_messages(&response)?;
+        Ok(response)
+    }
+
+    /// run_messages runs the messages of `response` in order and replies to the contract the way each asks to.
+    /// The messages of a reply run before the next message of `response`
+    fn run_messages(&mut self, response: &Response) -> Result<(), ContractError> {
+        for sub_msg in &response.messages {
+            let balances = self.balances.clone();
+            let result = self.run_message(&sub_msg.msg);
+            if result.is_err() {
+                // a failed submessage is reverted on its own, the contract may still go on
+                self.balances = balances;
+            }
+            let result = match (result, &sub_msg.reply_on) {
+                (Ok(()), ReplyOn::Success | ReplyOn::Always) => {
+                    SubMsgResult::Ok(SubMsgResponse { events: vec![], data: None })
+                }
+                (Err(error), ReplyOn::Error | ReplyOn::Always) => SubMsgResult::Err(error.to_string()),
+                (result, _) => {
+                    result?;
+                    continue;
+                }
+            };
+            self.sync_balances();
+            let response = reply(self.deps.as_mut(), self.env.clone(), Reply { id: sub_msg.id, result })?;
+            self.run_messages(&response)?;
+            self.replies.push(response);
+        }
+        Ok(())
+    }
+
+    fn run_message(&mut self, msg: &CosmosMsg) -> StdResult<()> {
+        match msg {
+            CosmosMsg::Bank(BankMsg::Send { to_address, amount }) => self.transfer(CONTRACT, to_address, amount),
+            CosmosMsg::Bank(BankMsg::Burn { amount }) => self.burn(CONTRACT, amount),
+            CosmosMsg::Wasm(WasmMsg::Execute { contract_addr, funds, .. }) => {
+                self.transfer(CONTRACT, contract_addr, funds)?;
+                if self.failing.contains(contract_addr) {
+                    return Err(StdError::generic_err(format!("{} failed", contract_addr)));
+                }
+                Ok(())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn transfer(&mut self, from: &str, to: &str, coins: &[Coin]) -> StdResult<()> {
+        self.burn(from, coins)?;
+        add_coins(self.balances.entry(to.to_string()).or_default(), coins);
+        Ok(())
+    }
+
+    fn burn(&mut self, from: &str, coins: &[Coin]) -> StdResult<()> {
+        let balance = self.balances.entry(from.to_string()).or_default();
+        for coin in coins {
+            if !deduct_coin(balance, coin) {
+                let msg = format!("{} {} is more than {} holds", coin.amount, coin.denom, from);
+                return Err(StdError::generic_err(msg));
+            }
+        }
+        Ok(())
+    }
+
+    /// sync_balances lets the contract query the balances kept here
+    fn sync_balances(&mut self) {
+        for (address, coins) in &self.balances {
+            self.deps.querier.update_balance(address, coins.clone());
+        }
+    }
+}
@@ -0,0 +1,108 @@
+use cosmwasm_std::{
+    from_binary, to_binary, Deps, DepsMut, Env, IbcBasicResponse, IbcChannel, IbcChannelCloseMsg, IbcChannelConnectMsg,
+    IbcChannelOpenMsg, IbcOrder, IbcPacket, IbcPacketAckMsg, IbcPacketReceiveMsg, IbcPacketTimeoutMsg,
+    IbcReceiveResponse,
+};
+
+use crate::contract::resolve_record;
+use crate::error::ContractError;
+use crate::msg::{ResolveAck, ResolvePacket, ResolveRecordResponse};
+use crate::state::CONFIG;
+
+// version both ends of a resolver channel must speak
+pub const IBC_VERSION: &str = "huahua-name-1";
+
+/// ibc_channel_open accepts unordered channels of the resolver version on whitelisted channels
+#[cfg_attr(not(feature = "library"), cosmwasm_std::entry_point)]
+pub fn ibc_channel_open(deps: DepsMut, _env: Env, msg: IbcChannelOpenMsg) -> Result<(), ContractError> {
+    check_channel(deps.as_ref(), msg.channel(), msg.counterparty_version())
+}
+
+/// ibc_channel_connect checks the channel once more, the counterparty version is only known now when we opened it
+#[cfg_attr(not(feature = "library"), cosmwasm_std::entry_point)]
+pub fn ibc_channel_connect(
+    deps: DepsMut,
+    _env: Env,
+    msg: IbcChannelConnectMsg,
+) -> Result<IbcBasicResponse, ContractError> {
+    let channel = msg.channel();
+    check_channel(deps.as_ref(), channel, msg.counterparty_version())?;
+
+    Ok(IbcBasicResponse::new()
+        .add_attribute("action", "ibc_connect")
+        .add_attribute("channel", &channel.endpoint.channel_id))
+}
+
+/// ibc_channel_close lets channels close, no state is kept per channel
+#[cfg_attr(not(feature = "library"), cosmwasm_std::entry_point)]
+pub fn ibc_channel_close(
+    _deps: DepsMut,
+    _env: Env,
+    msg: IbcChannelCloseMsg,
+) -> Result<IbcBasicResponse, ContractError> {
+    Ok(IbcBasicResponse::new()
+        .add_attribute("action", "ibc_close")
+        .add_attribute("channel", &msg.channel().endpoint.channel_id))
+}
+
+/// ibc_packet_receive resolves the name of a ResolvePacket. Failures are acknowledged as errors
+/// rather than returned, so the sender always gets an answer
+#[cfg_attr(not(feature = "library"), cosmwasm_std::entry_point)]
+pub fn ibc_packet_receive(
+    deps: DepsMut,
+    env: Env,
+    msg: IbcPacketReceiveMsg,
+) -> Result<IbcReceiveResponse, ContractError> {
+    let ack = match resolve_packet(deps.as_ref(), env, &msg.packet) {
+        Ok(record) => ResolveAck::Result(Box::new(record)),
+        Err(err) => ResolveAck::Error(err.to_string()),
+    };
+
+    Ok(IbcReceiveResponse::new()
+        .set_ack(to_binary(&ack)?)
+        .add_attribute("action", "ibc_resolve")
+        .add_attribute("channel", &msg.packet.dest.channel_id))
+}
+
+/// ibc_packet_ack is never called, the resolver sends no packets
+#[cfg_attr(not(feature = "library"), cosmwasm_std::entry_point)]
+pub fn ibc_packet_ack(_deps: DepsMut, _env: Env, _msg: IbcPacketAckMsg) -> Result<IbcBasicResponse, ContractError> {
+    Ok(IbcBasicResponse::new())
+}
+
+/// ibc_packet_timeout is never called, the resolver sends no packets
+#[cfg_attr(not(feature = "library"), cosmwasm_std::entry_point)]
+pub fn ibc_packet_timeout(
+    _deps: DepsMut,
+    _env: Env,
+    _msg: IbcPacketTimeoutMsg,
+) -> Result<IbcBasicResponse, ContractError> {
+    Ok(IbcBasicResponse::new())
+}
+
+/// check_channel returns an error unless `channel` is a whitelisted unordered channel of the resolver version
+fn check_channel(deps: Deps, channel: &IbcChannel, counterparty_version: Option<&str>) -> Result<(), ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if !config.ibc_channels.contains(&channel.endpoint.channel_id) {
+        return Err(ContractError::IbcChannelNotAllowed { channel: channel.endpoint.channel_id.clone() });
+    }
+    if channel.order != IbcOrder::Unordered {
+        return Err(ContractError::InvalidIbcOrder {});
+    }
+    for version in std::iter::once(channel.version.as_str()).chain(counterparty_version) {
+        if version != IBC_VERSION {
+            return Err(ContractError::InvalidIbcVersion { version: version.to_string() });
+        }
+    }
+    Ok(())
+}
+
+/// resolve_packet resolves the name asked for by `packet`, the channel may have been removed from the whitelist since
+fn resolve_packet(deps: Deps, env: Env, packet: &IbcPacket) -> Result<ResolveRecordResponse, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if !config.ibc_channels.contains(&packet.dest.channel_id) {
+        return Err(ContractError::IbcChannelNotAllowed { channel: packet.dest.channel_id.clone() });
+    }
+    let ResolvePacket::Resolve { name } = from_binary(&packet.data)?;
+    Ok(resolve_record(deps, env, name)?)
+}
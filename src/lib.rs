@@ -1,8 +1,14 @@
 pub mod coin_helpers;
 pub mod contract;
 mod error;
+#[cfg(test)]
+mod harness;
+pub mod ibc;
+#[cfg(any(test, feature = "mock"))]
+pub mod mock;
 pub mod msg;
 pub mod state;
+pub mod validation;
 
 #[cfg(test)]
 mod tests;
@@ -0,0 +1,221 @@
+//! Test helpers for contracts integrating with the name service.
+//!
+//! `MockNameService` runs this contract on mock storage, so a querier built from it
+//! answers smart queries exactly like a deployed instance would:
+//!
+//! ```ignore
+//! let mut service = MockNameService::new("name_service");
+//! service.register("alice", "alice_key");
+//! let deps = service.into_dependencies();
+//! ```
+
+use cosmwasm_std::testing::{
+    mock_dependencies, mock_env, mock_info, MockApi, MockQuerier, MockStorage,
+};
+use cosmwasm_std::{
+    from_binary, Addr, Coin, ContractResult, Empty, OwnedDeps, QuerierResult, Response,
+    SystemError, SystemResult, WasmQuery,
+};
+
+use crate::contract::{execute, instantiate, query};
+use crate::error::ContractError;
+use crate::msg::{ExecuteMsg, InstantiateMsg, QueryMsg};
+use crate::state::{CacheControl, NameRecord, Socials, CONFIG};
+
+pub const MOCK_ADMIN: &str = "admin";
+
+/// mock_instantiate_msg returns an InstantiateMsg without any fees
+pub fn mock_instantiate_msg() -> InstantiateMsg {
+    InstantiateMsg {
+        admin: Some(MOCK_ADMIN.to_string()),
+        purchase_price: vec![],
+        transfer_price: vec![],
+        edit_price: vec![],
+        max_supply: None,
+        registration_duration: None,
+        grace_period: None,
+        renewal_price: None,
+        preset: None,
+        launch_schedule: vec![],
+        burn_fees: false,
+    }
+}
+
+/// mock_instantiate_msg_with_price returns an InstantiateMsg charging the given coin for every action
+pub fn mock_instantiate_msg_with_price(price: Coin) -> InstantiateMsg {
+    InstantiateMsg {
+        admin: Some(MOCK_ADMIN.to_string()),
+        purchase_price: vec![price.clone()],
+        transfer_price: vec![price.clone()],
+        edit_price: vec![price],
+        max_supply: None,
+        registration_duration: None,
+        grace_period: None,
+        renewal_price: None,
+        preset: None,
+        launch_schedule: vec![],
+        burn_fees: false,
+    }
+}
+
+/// mock_name_record returns the record a fresh registration by `owner` produces
+pub fn mock_name_record(owner: &str) -> NameRecord {
+    NameRecord {
+        owner: Addr::unchecked(owner),
+        bio: "".to_string(),
+        website: "".to_string(),
+        verified: false,
+        accept_attestations: false,
+        cache_control: CacheControl::Standard,
+        expires_at: None,
+        socials: Socials::default(),
+        content_hash: None,
+    }
+}
+
+/// MockNameService is an instance of this contract living at `contract_addr`
+pub struct MockNameService {
+    pub contract_addr: String,
+    deps: OwnedDeps<MockStorage, MockApi, MockQuerier, Empty>,
+}
+
+impl MockNameService {
+    pub fn new(contract_addr: &str) -> Self {
+        Self::with_instantiate_msg(contract_addr, mock_instantiate_msg())
+    }
+
+    pub fn with_instantiate_msg(contract_addr: &str, msg: InstantiateMsg) -> Self {
+        let mut deps = mock_dependencies();
+        instantiate(deps.as_mut(), mock_env(), mock_info(MOCK_ADMIN, &[]), msg)
+            .expect("mock name service instantiates");
+
+        MockNameService {
+            contract_addr: contract_addr.to_string(),
+            deps,
+        }
+    }
+
+    /// register registers `name` for `owner`, paying the first configured purchase price
+    pub fn register(&mut self, name: &str, owner: &str) -> &mut Self {
+        let config = CONFIG
+            .load(&self.deps.storage)
+            .expect("mock name service is instantiated");
+        let funds: Vec<Coin> = config.purchase_price.into_iter().take(1).collect();
+        let msg = ExecuteMsg::Register {
+            name: name.to_string(),
+            bio: "".to_string(),
+            website: "".to_string(),
+            round_up: false,
+            referrer: None,
+        };
+        self.execute(owner, &funds, msg)
+            .expect("mock name registers");
+        self
+    }
+
+    /// execute runs any execute message against the mocked instance
+    pub fn execute(
+        &mut self,
+        sender: &str,
+        funds: &[Coin],
+        msg: ExecuteMsg,
+    ) -> Result<Response, ContractError> {
+        execute(
+            self.deps.as_mut(),
+            mock_env(),
+            mock_info(sender, funds),
+            msg,
+        )
+    }
+
+    /// handle_wasm_query answers smart queries sent to this instance, for use with `MockQuerier::update_wasm`
+    pub fn handle_wasm_query(&self, request: &WasmQuery) -> QuerierResult {
+        match request {
+            WasmQuery::Smart { contract_addr, msg } if *contract_addr == self.contract_addr => {
+                let msg: QueryMsg = match from_binary(msg) {
+                    Ok(msg) => msg,
+                    Err(e) => {
+                        return SystemResult::Err(SystemError::InvalidRequest {
+                            error: e.to_string(),
+                            request: msg.clone(),
+                        })
+                    }
+                };
+                SystemResult::Ok(ContractResult::from(query(
+                    self.deps.as_ref(),
+                    mock_env(),
+                    msg,
+                )))
+            }
+            WasmQuery::Smart { contract_addr, .. } | WasmQuery::Raw { contract_addr, .. } => {
+                SystemResult::Err(SystemError::NoSuchContract {
+                    addr: contract_addr.clone(),
+                })
+            }
+            _ => SystemResult::Err(SystemError::UnsupportedRequest {
+                kind: "wasm".to_string(),
+            }),
+        }
+    }
+
+    /// into_dependencies returns mock dependencies whose querier resolves against this instance
+    pub fn into_dependencies(self) -> OwnedDeps<MockStorage, MockApi, MockQuerier, Empty> {
+        let mut deps = mock_dependencies();
+        deps.querier
+            .update_wasm(move |request| self.handle_wasm_query(request));
+        deps
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use cosmwasm_std::coin;
+    use cosmwasm_std::QueryRequest;
+
+    use crate::msg::ResolveRecordResponse;
+
+    #[test]
+    fn mocked_querier_resolves_registered_names() {
+        let mut service = MockNameService::with_instantiate_msg(
+            "name_service",
+            mock_instantiate_msg_with_price(coin(5, "token")),
+        );
+        service.register("alice", "alice_key");
+        let deps = service.into_dependencies();
+
+        let resp: ResolveRecordResponse = deps
+            .as_ref()
+            .querier
+            .query(&QueryRequest::Wasm(WasmQuery::Smart {
+                contract_addr: "name_service".to_string(),
+                msg: cosmwasm_std::to_binary(&QueryMsg::ResolveRecord {
+                    name: "alice".to_string(),
+                })
+                .unwrap(),
+            }))
+            .unwrap();
+        assert_eq!(resp.address, Some("alice_key".to_string()));
+
+        let err = deps
+            .as_ref()
+            .querier
+            .query::<ResolveRecordResponse>(&QueryRequest::Wasm(WasmQuery::Smart {
+                contract_addr: "elsewhere".to_string(),
+                msg: cosmwasm_std::to_binary(&QueryMsg::Config {}).unwrap(),
+            }))
+            .unwrap_err();
+        assert!(err.to_string().contains("No such contract"));
+    }
+
+    #[test]
+    fn record_fixture_matches_registration() {
+        let mut service = MockNameService::new("name_service");
+        service.register("alice", "alice_key");
+
+        let record = crate::state::name_resolver()
+            .load(&service.deps.storage, b"alice")
+            .unwrap();
+        assert_eq!(record, mock_name_record("alice_key"));
+    }
+}
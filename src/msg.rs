@@ -1,26 +1,374 @@
-use crate::state::Config;
+use crate::state::{
+    Auction, Beneficiary, Bid, CacheControl, Config, Expiration, GrantAction, HistoryEvent, Hold, LaunchPhase,
+    NamePolicy, NameStats, PhaseStart, PriceTier, Socials, Tombstone, TokenGate, TransferOffer, one_or_many,
+};
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::{Addr, Coin};
+use cosmwasm_std::{Addr, Binary, Coin, HexBinary, Timestamp, Uint128};
 
 #[cw_serde]
 pub struct InstantiateMsg {
     pub admin: Option<String>,
-    pub purchase_price: Option<Coin>,
-    pub transfer_price: Option<Coin>,
-    pub edit_price: Option<Coin>,
+    // a single coin or a list of accepted coins, paying any one of them is enough
+    #[serde(default, deserialize_with = "one_or_many")]
+    pub purchase_price: Vec<Coin>,
+    #[serde(default, deserialize_with = "one_or_many")]
+    pub transfer_price: Vec<Coin>,
+    #[serde(default, deserialize_with = "one_or_many")]
+    pub edit_price: Vec<Coin>,
+    pub max_supply: Option<u64>,
+    pub registration_duration: Option<u64>,
+    pub grace_period: Option<u64>,
+    pub renewal_price: Option<Coin>,
+    // fields left unset take the value of the preset
+    #[serde(default)]
+    pub preset: Option<Preset>,
+    // the launch phases by start time, before the first one only the admin hands out names
+    #[serde(default)]
+    pub launch_schedule: Vec<PhaseStart>,
+    // burns every fee paid instead of collecting it
+    #[serde(default)]
+    pub burn_fees: bool,
+}
+
+#[cw_serde]
+pub struct RegistrationData {
+    pub name: String,
+    pub bio: String,
+    pub website: String,
+}
+
+/// Preset is a bundle of policies for a common kind of registry
+#[cw_serde]
+pub enum Preset {
+    // yearly registrations with a 30 day grace period, renewed at the purchase price
+    Identity,
+    // names never expire and short names cost 10x (4-5 characters) or 100x (3 characters) the purchase price
+    Collectible,
+    // yearly registrations with a 90 day grace period
+    Enterprise,
 }
 
 #[cw_serde]
 pub enum ExecuteMsg {
-    Register { name: String, bio: String, website: String },
-    Transfer { name: String, to: String },
+    // round_up donates everything sent above the price to the community fund
+    // referrer is paid referral_percent of the price
+    Register {
+        name: String,
+        bio: String,
+        website: String,
+        #[serde(default)]
+        round_up: bool,
+        #[serde(default)]
+        referrer: Option<String>,
+    },
+    // RegisterBatch registers all of the names or none, paid in a single denom
+    RegisterBatch { registrations: Vec<RegistrationData> },
+    // memo is shown to the recipient until their next execute
+    Transfer { name: String, to: String, #[serde(default)] round_up: bool, #[serde(default)] memo: Option<String> },
+    // OfferTransfer offers the name to `to`, who must AcceptTransfer it and pay `price`, credited to the
+    // seller's payout. A new offer replaces the previous one
+    OfferTransfer { name: String, to: String, price: Option<Coin> },
+    AcceptTransfer { name: String },
+    // CancelTransfer is sent by the seller to withdraw the offer, or by the recipient to decline it
+    CancelTransfer { name: String },
+    // ListForSale puts the name up for sale to anyone at `price`, a new listing replaces the previous one.
+    // Transfers of the name end the listing
+    ListForSale { name: String, price: Coin },
+    Delist { name: String },
+    // Buy takes a listed name for its price. The marketplace fee goes to the revenue, the royalty to the
+    // registrant of the name and the rest to the seller's payout
+    Buy { name: String },
+    // SetMarketplaceFees sets the percent of sales collected as a fee and paid as royalty
+    SetMarketplaceFees { marketplace_fee_percent: u64, royalty_percent: u64 },
+    // SetRegistrationLimits caps the names an address may hold and sets how long it waits between
+    // registrations, None lifts the limit
+    SetRegistrationLimits { max_names_per_address: Option<u64>, registration_cooldown_seconds: Option<u64> },
+    // Refund sends the revenue left after the revenue split to the fee collector
     Refund {},
-    Edit { name: String, bio: String, website: String },
-    Editconf { purchase_price: Option<Coin>, transfer_price: Option<Coin>, edit_price: Option<Coin> },
+    // SetTreasury sets who receives the fees, beneficiaries are credited their percent of every fee paid
+    SetTreasury { fee_collector: Option<String>, revenue_split: Vec<RevenueShare> },
+    // SetFeeCalculator sets the contract pricing every paid action, None restores the built-in prices
+    SetFeeCalculator { address: Option<String> },
+    // SetIbcChannels replaces the channels other chains may resolve names over
+    SetIbcChannels { channels: Vec<String> },
+    // Withdraw sends the sender `amount` of its share: the fee collector draws from the revenue,
+    // beneficiaries from what the split credited them
+    Withdraw { amount: Uint128, denom: String },
+    // ClaimPayout sends the sender what sales credited to it
+    ClaimPayout {},
+    // StartAuction flags an available name as premium, it can only be won by bidding until `duration`
+    // seconds from now
+    StartAuction { name: String, min_bid: Coin, duration: u64 },
+    // PlaceBid bids the sent funds, the previous highest bidder is refunded
+    PlaceBid { name: String },
+    // CloseAuction lets anyone register an ended auction's name to its highest bidder
+    CloseAuction { name: String },
+    // Release gives a name up, it can be registered again right away. The unused part of the
+    // registration period is refunded at the current registration price
+    Release { name: String },
+    ReleaseBatch { names: Vec<String> },
+    // ForceRelease lets the admin take down an abusive name
+    ForceRelease { name: String },
+    // Renew extends a name by one registration period, also during its grace period
+    Renew { name: String },
+    // RenewFor lets anyone pay to extend a name by `years` registration periods, the name stays with its owner
+    RenewFor { name: String, years: u64 },
+    Edit { name: String, bio: String, website: String, #[serde(default)] round_up: bool },
+    // length_prices are left untouched when omitted
+    Editconf {
+        #[serde(default, deserialize_with = "one_or_many")]
+        purchase_price: Vec<Coin>,
+        #[serde(default, deserialize_with = "one_or_many")]
+        transfer_price: Vec<Coin>,
+        #[serde(default, deserialize_with = "one_or_many")]
+        edit_price: Vec<Coin>,
+        #[serde(default)]
+        length_prices: Option<Vec<PriceTier>>,
+        // name_policy is left untouched when omitted
+        #[serde(default)]
+        name_policy: Option<NamePolicy>,
+    },
+    AddCategory { category: String, requires_approval: bool },
+    RemoveCategory { category: String },
+    JoinCategory { name: String, category: String },
+    LeaveCategory { name: String, category: String },
+    ApproveCategoryMember { name: String, category: String },
+    AddVerifier { address: String },
+    RemoveVerifier { address: String },
+    // AddRegistrar lets a contract register names for others with RegisterFor
+    AddRegistrar { address: String },
+    RemoveRegistrar { address: String },
+    // RegisterFor registers a name to `owner`, only registrars may send it. Nothing has to be paid
+    // and the launch phase does not apply, funds sent along are collected as fees
+    RegisterFor { name: String, owner: String, bio: String, website: String },
+    SetVerified { name: String, verified: bool },
+    AddAttestationIssuer { address: String },
+    RemoveAttestationIssuer { address: String },
+    // SetGatewayKey registers or rotates the secp256k1 public key of a gateway, None revokes it
+    SetGatewayKey { id: String, pubkey: Option<Binary> },
+    SetAttestationOptIn { name: String, accept: bool },
+    Attest { name: String, key: String, value: String },
+    RemoveAttestation { name: String, issuer: String, key: String },
+    SetCacheControl { name: String, cache_control: CacheControl },
+    // SetSocials replaces the avatar and social accounts of the name, empty fields clear them
+    SetSocials { name: String, socials: Socials },
+    // SetContentHash points the name at an ipfs:// or ar:// website, None clears it
+    SetContentHash { name: String, value: Option<String> },
+    // SetMaxSupply caps the number of names, the cap can only ever be lowered
+    SetMaxSupply { max_supply: u64 },
+    // Pause stops every execute but Unpause during an incident, queries keep working
+    Pause {},
+    Unpause {},
+    // FreezeConfig locks prices and supply policy for good, only governance can lift it with SudoMsg::UpdateConfig
+    FreezeConfig {},
+    // Sunset stops new registrations and points clients at a successor contract
+    Sunset { successor: String },
+    // TransferOwnership proposes a new admin, the current admin stays in charge until it sends AcceptOwnership
+    TransferOwnership { new_owner: String },
+    AcceptOwnership {},
+    // ContinueMigration rewrites the next batch of records of a pending migration
+    ContinueMigration { limit: Option<u32> },
+    // RebuildIndexes rebuilds the owner index and snapshots of up to `limit` records after `start_after`,
+    // the `next` attribute is where the following batch starts
+    RebuildIndexes { start_after: Option<String>, limit: Option<u32> },
+    // FundSponsorship deposits the sent funds into the sender's sponsorship
+    FundSponsorship { open: bool },
+    SetSponsoredAddresses { add: Vec<String>, remove: Vec<String> },
+    WithdrawSponsorship {},
+    RegisterSponsored { name: String, bio: String, website: String, sponsor: String },
+    // Grant lets another address run the given actions on all of the sender's names
+    Grant { grantee: String, actions: Vec<GrantAction>, expires: Option<Timestamp> },
+    RevokeGrant { grantee: String },
+    // CommitPromoNames stores sha256("<name>:<secret>") hashes of names claimable for free
+    CommitPromoNames { hashes: Vec<HexBinary> },
+    RemovePromoNames { hashes: Vec<HexBinary> },
+    ClaimPromoName { name: String, secret: String, bio: String, website: String },
+    // ReserveNames holds names back from registration, e.g. brand names or offensive terms
+    ReserveNames { names: Vec<String> },
+    UnreserveNames { names: Vec<String> },
+    // SetAllowlist picks who may register during the allowlist phase of the launch
+    SetAllowlist { add: Vec<String>, remove: Vec<String> },
+    // SetAllowlistGate also lets the holders of a cw721 or cw20 token register during the allowlist phase
+    SetAllowlistGate { gate: Option<TokenGate> },
+    // SetLaunchPhase switches to `phase` right away, e.g. to open registration to the public early.
+    // None goes back to the launch schedule
+    SetLaunchPhase { phase: Option<LaunchPhase> },
+    // AssignReservedName registers a reserved name for free to `owner`
+    AssignReservedName { name: String, owner: String },
+    // AddDictionaryWords adds lowercase words that make names starting with them score higher
+    AddDictionaryWords { words: Vec<String> },
+    RemoveDictionaryWords { words: Vec<String> },
+    SetCommunityFund { address: Option<String> },
+    // SetMinDelegation requires free claims to come from addresses staking at least `min_delegation`
+    SetMinDelegation { min_delegation: Option<Coin> },
+    // SetEditBytePrice makes edits cost edit_price plus `edit_byte_price` for every byte of bio and website
+    SetEditBytePrice { edit_byte_price: Option<Coin> },
+    // max_renewal_horizon caps how far ahead of now names may be renewed, None leaves renewals uncapped
+    SetExpiryPolicy {
+        registration_duration: Option<u64>,
+        grace_period: u64,
+        renewal_price: Option<Coin>,
+        #[serde(default)]
+        max_renewal_horizon: Option<u64>,
+    },
+    // SetWebhook calls `contract` with a NameHookMsg on every transfer, edit and upcoming expiry of the name,
+    // a None contract removes the webhook
+    SetWebhook { name: String, contract: Option<String>, payload: Option<Binary> },
+    // NotifyExpiring lets anyone warn the webhook of a name that expires soon
+    NotifyExpiring { name: String },
+    // WatchName deposits at least watch_deposit to be notified, and refunded, when the name becomes available
+    WatchName { name: String },
+    // UnwatchName stops watching the name and refunds the deposit
+    UnwatchName { name: String },
+    // NotifyWatchers lets anyone notify and refund the watchers of a name that is available
+    NotifyWatchers { name: String },
+    SetWatchDeposit { watch_deposit: Option<Coin> },
+    // Reserve locks hold_deposit to keep an available name for the sender for hold_duration seconds,
+    // the deposit counts towards its price when the sender registers it
+    Reserve { name: String },
+    SetHoldTerms { hold_deposit: Option<Coin>, hold_duration: u64 },
+    // SetPremiumDecay makes names that ran out of grace period cost `premium_multiplier` times their price,
+    // decaying linearly to the price over `premium_window` seconds. A multiplier of 0 or 1 disables the premium
+    SetPremiumDecay { premium_multiplier: u64, premium_window: u64 },
+    // SetReferralPercent sets the share of the price of a registration paid to its referrer
+    SetReferralPercent { referral_percent: u64 },
+    // SetReleaseCooldown keeps released and revoked names for their previous owner for `release_cooldown` seconds
+    SetReleaseCooldown { release_cooldown: u64 },
+    // SetPrimaryName makes one of the names of the sender the name its address resolves back to
+    SetPrimaryName { name: String },
+    // SetTextRecord stores a free-form key/value record on the name, charged like an edit
+    SetTextRecord { name: String, key: String, value: String },
+    RemoveTextRecord { name: String, key: String },
+    // RegisterSubname points `label.parent` at `owner`, registering again updates the subname
+    RegisterSubname { parent: String, label: String, owner: String, bio: String, website: String },
+    RemoveSubname { parent: String, label: String },
+    // SetAddress sets the address the name resolves to on `chain_id`, a None address removes it
+    SetAddress { name: String, chain_id: String, address: Option<String> },
+    // cw721 messages, the name is the token_id. TransferNft and SendNft charge the transfer_price
+    TransferNft { recipient: String, token_id: String },
+    SendNft { contract: String, token_id: String, msg: Binary },
+    // Approve lets `spender` edit and transfer the name until it is transferred or the approval expires
+    Approve { spender: String, token_id: String, expires: Option<Expiration> },
+    Revoke { spender: String, token_id: String },
+    // SetOperator lets `operator` edit and transfer all names of the sender, including later ones
+    SetOperator { operator: String, approved: bool },
+    // SetTokenUri sets the https:// or ipfs:// metadata URI NftInfo reports, None removes it
+    SetTokenUri { name: String, token_uri: Option<String> },
+    // Lock stops transfers, edits and approvals of the name until its owner sends Unlock,
+    // e.g. to keep names away from a hot wallet that could be compromised
+    Lock { name: String },
+    Unlock { name: String },
+    // SetNameFrozen freezes a name like Lock, for legal or abuse cases. Only the admin can lift it
+    SetNameFrozen { name: String, frozen: bool },
+}
+
+#[cw_serde]
+pub struct RevenueShare {
+    pub address: String,
+    pub percent: u64,
+}
+
+/// NftReceiverMsg is sent to the contract receiving a name through SendNft
+#[cw_serde]
+pub enum NftReceiverMsg {
+    ReceiveNft(Cw721ReceiveMsg),
+}
+
+#[cw_serde]
+pub struct Cw721ReceiveMsg {
+    pub sender: String,
+    pub token_id: String,
+    pub msg: Binary,
+}
+
+/// FeeQueryMsg is the query a fee calculator answers with a FeeResponse, `action` is one of
+/// register, transfer, edit and renew
+#[cw_serde]
+pub enum FeeQueryMsg {
+    Fee { action: String, name: String, sender: String },
+}
+
+#[cw_serde]
+pub struct FeeResponse {
+    // accepted prices, paying any one of them is enough. Empty makes the action free
+    pub prices: Vec<Coin>,
+}
+
+/// ResolvePacket is the packet other chains send over a resolver channel, it is acknowledged with a ResolveAck
+#[cw_serde]
+pub enum ResolvePacket {
+    Resolve { name: String },
+}
+
+#[cw_serde]
+pub enum ResolveAck {
+    // boxed, the record is much larger than an error. The JSON is the same
+    Result(Box<ResolveRecordResponse>),
+    Error(String),
+}
+
+/// NameHookMsg is the message webhook contracts receive, it must be part of their ExecuteMsg
+#[cw_serde]
+pub enum NameHookMsg {
+    NameHook { name: String, event: NameEvent, payload: Binary },
+}
+
+#[cw_serde]
+pub enum NameEvent {
+    Transferred { from: Addr, to: Addr },
+    Edited {},
+    Expiring { expires_at: Timestamp },
 }
 
 #[cw_serde]
 pub struct MigrateMsg {
+    // records rewritten during migrate itself, the rest is left to ContinueMigration.
+    // None rewrites the whole registry at once.
+    pub batch_size: Option<u32>,
+}
+
+/// SudoMsg is sent by chain governance, it works even when the admin key is unavailable
+#[cw_serde]
+pub enum SudoMsg {
+    // actions are the snake_case names of ExecuteMsg variants, e.g. "transfer"
+    PauseActions { actions: Vec<String> },
+    UnpauseActions { actions: Vec<String> },
+    RebuildIndexes { start_after: Option<String>, limit: Option<u32> },
+    // ForceTransfer hands a name over to `to`, frozen or not
+    ForceTransfer { name: String, to: String },
+    // FreezeName stops every execute acting on a registered name, registering it again included, until UnfreezeName
+    FreezeName { name: String },
+    UnfreezeName { name: String },
+    // UpdateConfig sets the given parameters, a frozen config only takes a new admin
+    UpdateConfig(ConfigUpdate),
+    // EndBlock is sent by the chain at the end of every block, it removes names that ran out and settles ended
+    // auctions, a bounded batch per block
+    EndBlock {},
+}
+
+/// ConfigUpdate lists the parameters governance may set, those left unset keep their value
+#[cw_serde]
+#[derive(Default)]
+pub struct ConfigUpdate {
+    #[serde(default)]
+    pub admin: Option<String>,
+    #[serde(default)]
+    pub purchase_price: Option<Vec<Coin>>,
+    #[serde(default)]
+    pub transfer_price: Option<Vec<Coin>>,
+    #[serde(default)]
+    pub edit_price: Option<Vec<Coin>>,
+    #[serde(default)]
+    pub length_prices: Option<Vec<PriceTier>>,
+    #[serde(default)]
+    pub renewal_price: Option<Coin>,
+    #[serde(default)]
+    pub grace_period: Option<u64>,
+    #[serde(default)]
+    pub name_policy: Option<NamePolicy>,
+    // freezes the config like FreezeConfig, a frozen config can't be unfrozen
+    #[serde(default)]
+    pub frozen: Option<bool>,
 }
 
 #[cw_serde]
@@ -29,24 +377,682 @@ pub enum QueryMsg {
     // ResolveAddress returns the current address that the name resolves to
     #[returns(ResolveRecordResponse)]
     ResolveRecord { name: String },
+    // ResolveRecords resolves up to 30 names at once, in the order given, None for names that don't resolve
+    #[returns(ResolveRecordsResponse)]
+    ResolveRecords { names: Vec<String> },
+    // RecordStats adds the activity counters of the name to its record, as pricing signals for marketplaces
+    #[returns(RecordStatsResponse)]
+    RecordStats { name: String },
     #[returns(ConfigResponse)]
     Config {},
+    #[returns(SupplyResponse)]
+    Supply {},
+    // Stats counts registrations and collected fees, so operators need no indexer for them
+    #[returns(StatsResponse)]
+    Stats {},
+    #[returns(SunsetResponse)]
+    Sunset {},
+    #[returns(PendingOwnerResponse)]
+    PendingOwner {},
+    // Phase tells the current launch phase and when the next one starts
+    #[returns(PhaseResponse)]
+    Phase {},
+    #[returns(MigrationStatusResponse)]
+    MigrationStatus {},
+    // Invariants cross-checks counters, indexes and escrow against the records and the contract balance.
+    // It reads the whole state, run it against a node allowing expensive queries
+    #[returns(InvariantsResponse)]
+    Invariants {},
+    // MigrationPlan reports what migrating with `batch_size` would do, without changing anything
+    #[returns(MigrationPlanResponse)]
+    MigrationPlan { batch_size: Option<u32> },
+    // Sponsorship returns a sponsor's balance, and whether `address` may still use it when given
+    #[returns(SponsorshipResponse)]
+    Sponsorship { sponsor: String, address: Option<String> },
+    // Donations returns the total given to the community fund, and what `address` gave when set
+    #[returns(DonationsResponse)]
+    Donations { address: Option<String> },
+    #[returns(WebhookResponse)]
+    Webhook { name: String },
+    // cw721 queries, the name is the token_id and expired names are not found
+    #[returns(OwnerOfResponse)]
+    OwnerOf { token_id: String, include_expired: Option<bool> },
+    #[returns(NftInfoResponse)]
+    NftInfo { token_id: String },
+    #[returns(OperatorsResponse)]
+    AllOperators { owner: String, start_after: Option<String>, limit: Option<u32> },
+    #[returns(TokensResponse)]
+    Tokens { owner: String, start_after: Option<String>, limit: Option<u32> },
+    #[returns(TokensResponse)]
+    AllTokens { start_after: Option<String>, limit: Option<u32> },
+    #[returns(TransferOfferResponse)]
+    TransferOffer { name: String },
+    // Listings lists the names for sale in alphabetical order
+    #[returns(ListingsResponse)]
+    Listings { start_after: Option<String>, limit: Option<u32> },
+    #[returns(AuctionResponse)]
+    Auction { name: String },
+    // Hold returns the hold on the name while it lasts
+    #[returns(HoldResponse)]
+    Hold { name: String },
+    // RegistrationPrice quotes the accepted prices of registering the name now, expiry premium included,
+    // or of renewing it when it is taken. `duration` in seconds is rounded up to whole registration periods,
+    // the periods after the first are paid at the renewal price
+    #[returns(RegistrationPriceResponse)]
+    RegistrationPrice {
+        name: String,
+        #[serde(default)]
+        duration: Option<u64>,
+    },
+    // Tombstone tells who held a name removed from the registry, and why it was removed
+    #[returns(TombstoneResponse)]
+    Tombstone { name: String },
+    // Payout is what `address` can claim with ClaimPayout
+    #[returns(PayoutResponse)]
+    Payout { address: String },
+    // Treasury is the fee share credited to `address` by the revenue split
+    #[returns(TreasuryResponse)]
+    Treasury { address: String },
+    // Balances splits the contract balance into funds held for users and protocol revenue
+    #[returns(BalancesResponse)]
+    Balances {},
+    // PausedActions lists the executes governance paused, and whether the admin paused all of them
+    #[returns(PausedActionsResponse)]
+    PausedActions {},
+    // ResolveAddress resolves a name to its address on `chain_id`, the owner on this chain by default
+    #[returns(ResolveAddressResponse)]
+    ResolveAddress { name: String, chain_id: String },
+    // TextRecords lists the text records of a name, ordered by key
+    #[returns(TextRecordsResponse)]
+    TextRecords { name: String, start_after: Option<String>, limit: Option<u32> },
+    // PrimaryName resolves an address back to the name it chose, if it still holds it
+    #[returns(PrimaryNameResponse)]
+    PrimaryName { address: String },
+    // Watchers lists the addresses waiting for the name to become available
+    #[returns(WatchersResponse)]
+    Watchers { name: String, start_after: Option<String>, limit: Option<u32> },
+    // NamesOfOwner lists the unexpired names held by `owner`, in alphabetical order
+    #[returns(NamesResponse)]
+    NamesOfOwner { owner: String, start_after: Option<String>, limit: Option<u32> },
+    // ReservedNames lists the names held back by the admin, in alphabetical order
+    #[returns(NamesResponse)]
+    ReservedNames { start_after: Option<String>, limit: Option<u32> },
+    // NameScore rates a valid name from 0 to 100, the same way for every caller
+    #[returns(NameScoreResponse)]
+    NameScore { name: String },
+    // OwnersSnapshot lists names and their owners as they were at the start of block `at_height`,
+    // in alphabetical order. Expired names count until they are registered again
+    #[returns(OwnersSnapshotResponse)]
+    OwnersSnapshot { at_height: u64, start_after: Option<String>, limit: Option<u32> },
+    // ResolveRecordAt returns the owner of a name at the start of block `height`, None if nobody held it then
+    #[returns(ResolveRecordAtResponse)]
+    ResolveRecordAt { name: String, height: u64 },
+    // AllNames crawls the whole registry in alphabetical order, expired names left out
+    #[returns(AllNamesResponse)]
+    AllNames { start_after: Option<String>, limit: Option<u32> },
+    // NamesWithPrefix lists the registered names starting with `prefix` in alphabetical order, expired names left out
+    #[returns(AllNamesResponse)]
+    NamesWithPrefix { prefix: String, start_after: Option<String>, limit: Option<u32> },
+    // TransferMemos lists the memos of names transferred to `address` its executes didn't clear yet
+    #[returns(TransferMemosResponse)]
+    TransferMemos { address: String, start_after: Option<String>, limit: Option<u32> },
+    // CheckAvailability tells for up to 50 names whether they can be registered right now
+    #[returns(AvailabilityResponse)]
+    CheckAvailability { names: Vec<String> },
+    #[returns(GrantsResponse)]
+    Grants {
+        owner: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    // OwnerStats summarizes the names held by an address and what it paid so far
+    #[returns(OwnerStatsResponse)]
+    OwnerStats { address: String },
+    // RegistrationQuota tells how many more names an address may register and when it may register again
+    #[returns(RegistrationQuotaResponse)]
+    RegistrationQuota { address: String },
+    #[returns(CategoriesResponse)]
+    Categories {},
+    // CategoryMembers lists approved names of a category, or pending ones if `pending` is set
+    #[returns(CategoryMembersResponse)]
+    CategoryMembers {
+        category: String,
+        pending: Option<bool>,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    #[returns(VerifiersResponse)]
+    Verifiers {},
+    #[returns(RegistrarsResponse)]
+    Registrars {},
+    #[returns(AttestationIssuersResponse)]
+    AttestationIssuers {},
+    // ResolvePayload is the record of the name as gateways sign it, see GatewayPayload
+    #[returns(ResolvePayloadResponse)]
+    ResolvePayload { name: String },
+    #[returns(GatewayKeysResponse)]
+    GatewayKeys {},
+    // NameHistory lists the changes of a name oldest first, also of names removed since
+    #[returns(NameHistoryResponse)]
+    NameHistory { name: String, start_after: Option<u64>, limit: Option<u32> },
+    // VerifyGatewaySignature checks a gateway's signature of the sha256 digest of `payload`,
+    // signatures of revoked keys are invalid
+    #[returns(VerifyGatewaySignatureResponse)]
+    VerifyGatewaySignature { id: String, payload: Binary, signature: Binary },
+    // Attestations lists the records written to a name, ordered by (issuer, key)
+    #[returns(AttestationsResponse)]
+    Attestations {
+        name: String,
+        start_after: Option<(String, String)>,
+        limit: Option<u32>,
+    },
+    // DnsZone renders a name's records as zone-file entries under the given tld
+    #[returns(DnsZoneResponse)]
+    DnsZone { name: String, tld: String },
 }
 
 // We define a custom struct for each query response
 #[cw_serde]
 pub struct ResolveRecordResponse {
+    // the name is registered, also when it expired and no longer resolves
+    pub exists: bool,
     pub address: Option<String>,
+    // holder of the name, kept while it is expired until someone registers it again
+    pub owner: Option<Addr>,
     pub bio: Option<String>,
-    pub website: Option<String>
+    pub website: Option<String>,
+    pub verified: Option<bool>,
+    pub cache_control: Option<CacheControl>,
+    pub expires_at: Option<Timestamp>,
+    pub socials: Option<Socials>,
+    pub content_hash: Option<String>,
+    // number of text records, list them with the TextRecords query
+    pub text_records: u64,
+    // the owner locked the name, it can't be transferred or edited
+    pub locked: bool,
+    // the admin or governance froze the name
+    pub frozen: bool,
+    // deprecation notice, set when the contract has been sunset
+    pub successor: Option<Addr>,
+}
+
+#[cw_serde]
+pub struct ResolveRecordsResponse {
+    pub records: Vec<Option<ResolveRecordResponse>>,
+}
+
+#[cw_serde]
+pub struct RecordStatsResponse {
+    pub record: ResolveRecordResponse,
+    pub stats: NameStats,
 }
 
 #[cw_serde]
 pub struct ConfigResponse {
     pub owner: Addr,
-    pub purchase_price: Option<Coin>,
-    pub transfer_price: Option<Coin>,
-    pub edit_price: Option<Coin>,
+    pub purchase_price: Vec<Coin>,
+    pub transfer_price: Vec<Coin>,
+    pub edit_price: Vec<Coin>,
+    pub max_supply: Option<u64>,
+    pub frozen: bool,
+    pub community_fund: Option<Addr>,
+    pub min_delegation: Option<Coin>,
+    pub registration_duration: Option<u64>,
+    pub grace_period: u64,
+    pub renewal_price: Option<Coin>,
+    pub edit_byte_price: Option<Coin>,
+    pub length_prices: Vec<PriceTier>,
+    pub watch_deposit: Option<Coin>,
+    pub release_cooldown: u64,
+    pub fee_collector: Option<Addr>,
+    pub revenue_split: Vec<Beneficiary>,
+    pub fee_calculator: Option<Addr>,
+    pub ibc_channels: Vec<String>,
+    pub hold_deposit: Option<Coin>,
+    pub hold_duration: u64,
+    pub name_policy: NamePolicy,
+    pub burn_fees: bool,
+    pub referral_percent: u64,
+    pub premium_multiplier: u64,
+    pub premium_window: u64,
+    pub launch_phase: Option<LaunchPhase>,
+    pub allowlist_gate: Option<TokenGate>,
+    pub marketplace_fee_percent: u64,
+    pub royalty_percent: u64,
+    pub max_names_per_address: Option<u64>,
+    pub registration_cooldown_seconds: Option<u64>,
+    pub max_renewal_horizon: Option<u64>,
+    pub successor: Option<Addr>,
+}
+
+#[cw_serde]
+pub struct SunsetResponse {
+    pub sunset: bool,
+    pub successor: Option<Addr>,
+}
+
+#[cw_serde]
+pub struct PhaseResponse {
+    pub phase: LaunchPhase,
+    pub next: Option<PhaseStart>,
+}
+
+#[cw_serde]
+pub struct InvariantsResponse {
+    pub holds: bool,
+    pub violations: Vec<String>,
+}
+
+#[cw_serde]
+pub struct PendingOwnerResponse {
+    pub pending_owner: Option<Addr>,
+}
+
+#[cw_serde]
+pub struct MigrationStatusResponse {
+    pub in_progress: bool,
+    pub migrated: u64,
+    pub last_name: Option<String>,
+}
+
+#[cw_serde]
+pub struct MigrationPlanResponse {
+    // a pending migration is resumed where it stopped
+    pub resumes_pending: bool,
+    pub records_to_rewrite: u64,
+    // records written before the current layout, missing fields get their defaults
+    pub records_missing_fields: u64,
+    pub backfill_counters: bool,
+    // migrate plus the ContinueMigration calls needed to finish
+    pub passes: u64,
+}
+
+#[cw_serde]
+pub struct SponsorshipResponse {
+    pub balance: Vec<Coin>,
+    pub open: bool,
+    pub eligible: Option<bool>,
+}
+
+#[cw_serde]
+pub struct WebhookResponse {
+    pub contract: Option<Addr>,
+    pub payload: Option<Binary>,
+}
+
+#[cw_serde]
+pub struct NftApproval {
+    pub spender: String,
+    pub expires: Expiration,
+}
+
+#[cw_serde]
+pub struct OwnerOfResponse {
+    pub owner: String,
+    pub approvals: Vec<NftApproval>,
+}
+
+#[cw_serde]
+pub struct OperatorsResponse {
+    pub operators: Vec<NftApproval>,
+}
+
+#[cw_serde]
+pub struct NftExtension {
+    pub bio: String,
+    pub website: String,
+    pub expires_at: Option<Timestamp>,
+}
+
+#[cw_serde]
+pub struct NftInfoResponse {
+    pub token_uri: Option<String>,
+    pub extension: NftExtension,
+}
+
+#[cw_serde]
+pub struct TokensResponse {
+    pub tokens: Vec<String>,
+}
+
+#[cw_serde]
+pub struct TombstoneResponse {
+    pub tombstone: Option<Tombstone>,
+}
+
+#[cw_serde]
+pub struct NameHistoryEntry {
+    // position of the entry in the history of the name, pass it as start_after for the next page
+    pub seq: u64,
+    pub event: HistoryEvent,
+    pub actor: Addr,
+    pub height: u64,
+    pub time: Timestamp,
+}
+
+#[cw_serde]
+pub struct NameHistoryResponse {
+    pub entries: Vec<NameHistoryEntry>,
+}
+
+#[cw_serde]
+pub struct RegistrationPriceResponse {
+    // the quote is for registering the name unless it is taken, it can be bought only when available
+    pub status: AvailabilityStatus,
+    pub renew: bool,
+    pub periods: u64,
+    // paying any one of them is enough
+    pub prices: Vec<Coin>,
+    // when the premium on the expired name stops, if one is charged
+    pub premium_ends_at: Option<Timestamp>,
+}
+
+#[cw_serde]
+pub struct HoldResponse {
+    pub hold: Option<Hold>,
+}
+
+#[cw_serde]
+pub struct AuctionResponse {
+    pub auction: Option<Auction>,
+    pub highest_bid: Option<Bid>,
+}
+
+#[cw_serde]
+pub struct TreasuryResponse {
+    pub balance: Vec<Coin>,
+}
+
+#[cw_serde]
+pub struct PayoutResponse {
+    pub claimable: Vec<Coin>,
+}
+
+#[cw_serde]
+pub struct TransferOfferResponse {
+    pub offer: Option<TransferOffer>,
+}
+
+#[cw_serde]
+pub struct ListingInfo {
+    pub name: String,
+    pub seller: Addr,
+    pub price: Coin,
+    pub listed_at: Timestamp,
+}
+
+#[cw_serde]
+pub struct ListingsResponse {
+    pub listings: Vec<ListingInfo>,
+}
+
+#[cw_serde]
+pub struct BalancesResponse {
+    pub escrowed: Vec<Coin>,
+    pub revenue: Vec<Coin>,
+}
+
+#[cw_serde]
+pub struct PausedActionsResponse {
+    pub actions: Vec<String>,
+    pub paused: bool,
+}
+
+#[cw_serde]
+pub struct ResolveAddressResponse {
+    pub address: Option<String>,
+}
+
+#[cw_serde]
+pub struct TextRecord {
+    pub key: String,
+    pub value: String,
+}
+
+#[cw_serde]
+pub struct TextRecordsResponse {
+    pub records: Vec<TextRecord>,
+}
+
+#[cw_serde]
+pub struct PrimaryNameResponse {
+    pub name: Option<String>,
+}
+
+#[cw_serde]
+pub struct WatchersResponse {
+    pub watchers: Vec<Addr>,
+}
+
+#[cw_serde]
+pub struct DonationsResponse {
+    pub community_fund: Option<Addr>,
+    pub total: Vec<Coin>,
+    pub donated: Option<Vec<Coin>>,
+}
+
+#[cw_serde]
+pub struct GrantInfo {
+    pub grantee: Addr,
+    pub actions: Vec<GrantAction>,
+    pub expires: Option<Timestamp>,
+}
+
+#[cw_serde]
+pub struct GrantsResponse {
+    pub grants: Vec<GrantInfo>,
+}
+
+#[cw_serde]
+pub struct SupplyResponse {
+    pub total: u64,
+    pub max_supply: Option<u64>,
+    // None when the supply is unlimited
+    pub remaining: Option<u64>,
+}
+
+#[cw_serde]
+pub struct StatsResponse {
+    // names registered since instantiation, re-registrations included
+    pub total_registered: u64,
+    // names currently held, expired names count until they are registered again
+    pub total_active: u64,
+    // fees collected by denom. Referral rewards are excluded, refunds of released names are not deducted
+    pub fees_collected: Vec<Coin>,
+}
+
+#[cw_serde]
+pub struct OwnerStatsResponse {
+    pub name_count: u64,
+    pub first_registration: Option<Timestamp>,
+    // earliest expiry among the names the address holds that didn't expire yet, None when none of them expires
+    pub nearest_expiry: Option<Timestamp>,
+    pub total_spent: Vec<Coin>,
+}
+
+#[cw_serde]
+pub struct RegistrationQuotaResponse {
+    pub name_count: u64,
+    pub max_names_per_address: Option<u64>,
+    // None when the address may register any number of names
+    pub remaining: Option<u64>,
+    // None when the address may register right away
+    pub next_registration_at: Option<Timestamp>,
+}
+
+#[cw_serde]
+pub struct CategoryInfo {
+    pub category: String,
+    pub requires_approval: bool,
+}
+
+#[cw_serde]
+pub struct CategoriesResponse {
+    pub categories: Vec<CategoryInfo>,
+}
+
+#[cw_serde]
+pub struct NameInfo {
+    pub name: String,
+    pub owner: Addr,
+    pub expires_at: Option<Timestamp>,
+}
+
+#[cw_serde]
+pub struct NameOwner {
+    pub name: String,
+    pub owner: Addr,
+}
+
+#[cw_serde]
+pub struct OwnersSnapshotResponse {
+    pub owners: Vec<NameOwner>,
+}
+
+#[cw_serde]
+pub struct ResolveRecordAtResponse {
+    pub owner: Option<Addr>,
+}
+
+#[cw_serde]
+pub struct AllNamesResponse {
+    pub names: Vec<NameInfo>,
+}
+
+#[cw_serde]
+pub struct ReceivedMemo {
+    pub name: String,
+    pub from: Addr,
+    pub memo: String,
+}
+
+#[cw_serde]
+pub struct TransferMemosResponse {
+    pub memos: Vec<ReceivedMemo>,
+}
+
+#[cw_serde]
+pub enum AvailabilityStatus {
+    Available,
+    Taken,
+    // expired, but still reserved for its owner to renew
+    InGracePeriod,
+    // premium name, only sold by auction
+    InAuction,
+    // held back by the admin
+    Reserved,
+    // released moments ago, only its previous owner can register it yet
+    InCooldown,
+    // kept for the address holding it
+    Held,
+    // the name breaks the naming rules and can never be registered
+    Invalid,
+}
+
+#[cw_serde]
+pub struct Availability {
+    pub name: String,
+    pub status: AvailabilityStatus,
+}
+
+#[cw_serde]
+pub struct AvailabilityResponse {
+    pub names: Vec<Availability>,
+}
+
+#[cw_serde]
+pub struct NamesResponse {
+    pub names: Vec<String>,
+}
+
+#[cw_serde]
+pub struct NameScoreResponse {
+    pub score: u64,
+    // in characters, combining marks not counted
+    pub length: u64,
+    pub digits: u64,
+    pub hyphens: u64,
+    // longest dictionary word the name starts with
+    pub dictionary_word: Option<String>,
+}
+
+#[cw_serde]
+pub struct CategoryMembersResponse {
+    pub names: Vec<String>,
+}
+
+#[cw_serde]
+pub struct VerifiersResponse {
+    pub verifiers: Vec<Addr>,
+}
+
+#[cw_serde]
+pub struct RegistrarsResponse {
+    pub registrars: Vec<Addr>,
+}
+
+/// GatewayPayload is what a gateway signs to vouch for a record, `height` tells how fresh it is
+#[cw_serde]
+pub struct GatewayPayload {
+    pub name: String,
+    pub record: ResolveRecordResponse,
+    pub height: u64,
+}
+
+#[cw_serde]
+pub struct ResolvePayloadResponse {
+    // GatewayPayload serialized to JSON
+    pub payload: Binary,
+    // sha256 of the payload, the message gateways sign
+    pub digest: HexBinary,
+}
+
+#[cw_serde]
+pub struct GatewayKey {
+    pub id: String,
+    pub pubkey: Binary,
+}
+
+#[cw_serde]
+pub struct GatewayKeysResponse {
+    pub keys: Vec<GatewayKey>,
+}
+
+#[cw_serde]
+pub struct VerifyGatewaySignatureResponse {
+    pub valid: bool,
+}
+
+#[cw_serde]
+pub struct AttestationIssuersResponse {
+    pub issuers: Vec<Addr>,
+}
+
+#[cw_serde]
+pub struct Attestation {
+    pub issuer: Addr,
+    pub key: String,
+    pub value: String,
+}
+
+#[cw_serde]
+pub struct AttestationsResponse {
+    pub attestations: Vec<Attestation>,
+}
+
+#[cw_serde]
+pub struct DnsRecord {
+    pub host: String,
+    pub record_type: String,
+    pub value: String,
+}
+
+#[cw_serde]
+pub struct DnsZoneResponse {
+    pub origin: String,
+    pub records: Vec<DnsRecord>,
+    // records rendered as zone-file lines, in the same order
+    pub lines: Vec<String>,
 }
 
 impl From<Config> for ConfigResponse {
@@ -56,6 +1062,36 @@ impl From<Config> for ConfigResponse {
             purchase_price: config.purchase_price,
             transfer_price: config.transfer_price,
             edit_price: config.edit_price,
+            max_supply: config.max_supply,
+            frozen: config.frozen,
+            community_fund: config.community_fund,
+            min_delegation: config.min_delegation,
+            registration_duration: config.registration_duration,
+            grace_period: config.grace_period,
+            renewal_price: config.renewal_price,
+            edit_byte_price: config.edit_byte_price,
+            length_prices: config.length_prices,
+            watch_deposit: config.watch_deposit,
+            release_cooldown: config.release_cooldown,
+            fee_collector: config.fee_collector,
+            revenue_split: config.revenue_split,
+            fee_calculator: config.fee_calculator,
+            ibc_channels: config.ibc_channels,
+            hold_deposit: config.hold_deposit,
+            hold_duration: config.hold_duration,
+            name_policy: config.name_policy,
+            burn_fees: config.burn_fees,
+            referral_percent: config.referral_percent,
+            premium_multiplier: config.premium_multiplier,
+            premium_window: config.premium_window,
+            launch_phase: config.launch_phase,
+            allowlist_gate: config.allowlist_gate,
+            marketplace_fee_percent: config.marketplace_fee_percent,
+            royalty_percent: config.royalty_percent,
+            max_names_per_address: config.max_names_per_address,
+            registration_cooldown_seconds: config.registration_cooldown_seconds,
+            max_renewal_horizon: config.max_renewal_horizon,
+            successor: None,
         }
     }
 }
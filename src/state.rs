@@ -1,13 +1,166 @@
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Addr, Coin};
-use cw_storage_plus::{Item, Map};
+use cosmwasm_schema::serde::{Deserialize, Deserializer};
+use cosmwasm_std::{Addr, Binary, BlockInfo, Coin, Empty, Timestamp, Uint128};
+use cw_storage_plus::{Index, IndexList, IndexedMap, Item, Map, MultiIndex, SnapshotMap, Strategy};
+
+use crate::validation::{MAX_BIO_LENGTH, MAX_NAME_LENGTH, MAX_WEBSITE_LENGTH, MIN_NAME_LENGTH};
 
 #[cw_serde]
 pub struct Config {
     pub owner: Addr,
-    pub purchase_price: Option<Coin>,
-    pub transfer_price: Option<Coin>,
-    pub edit_price: Option<Coin>,
+    // accepted prices, paying any one of them is enough
+    #[serde(default, deserialize_with = "one_or_many")]
+    pub purchase_price: Vec<Coin>,
+    #[serde(default, deserialize_with = "one_or_many")]
+    pub transfer_price: Vec<Coin>,
+    #[serde(default, deserialize_with = "one_or_many")]
+    pub edit_price: Vec<Coin>,
+    #[serde(default)]
+    pub max_supply: Option<u64>,
+    // once set, prices and supply policy can never change again
+    #[serde(default)]
+    pub frozen: bool,
+    // receives the overpayment of payments made with `round_up`
+    #[serde(default)]
+    pub community_fund: Option<Addr>,
+    // staked amount required to claim sponsored and promo names for free
+    #[serde(default)]
+    pub min_delegation: Option<Coin>,
+    // seconds a registration or renewal lasts, names never expire when unset
+    #[serde(default)]
+    pub registration_duration: Option<u64>,
+    // seconds an expired name stays reserved for its owner to renew
+    #[serde(default)]
+    pub grace_period: u64,
+    #[serde(default)]
+    pub renewal_price: Option<Coin>,
+    // charged per byte of bio and website on top of edit_price, in the same denom
+    #[serde(default)]
+    pub edit_byte_price: Option<Coin>,
+    // registration prices of short names, sorted by max_length. Longer names pay purchase_price
+    #[serde(default)]
+    pub length_prices: Vec<PriceTier>,
+    // deposit held from every address watching a name, refunded when the name becomes available
+    #[serde(default)]
+    pub watch_deposit: Option<Coin>,
+    // seconds a released or revoked name can only be registered again by its previous owner
+    #[serde(default)]
+    pub release_cooldown: u64,
+    // receives the fees left after the revenue split, the admin when unset
+    #[serde(default)]
+    pub fee_collector: Option<Addr>,
+    // shares of every fee credited to other beneficiaries when it is paid
+    #[serde(default)]
+    pub revenue_split: Vec<Beneficiary>,
+    // contract answering FeeQueryMsg, its prices replace the built-in ones when set
+    #[serde(default)]
+    pub fee_calculator: Option<Addr>,
+    // channels other chains may resolve names over, see ibc
+    #[serde(default)]
+    pub ibc_channels: Vec<String>,
+    // deposit locked by Reserve, holds are disabled when unset
+    #[serde(default)]
+    pub hold_deposit: Option<Coin>,
+    // seconds a hold keeps a name for its holder
+    #[serde(default)]
+    pub hold_duration: u64,
+    // naming rules, configs stored before they were configurable get the rules that applied then
+    #[serde(default)]
+    pub name_policy: NamePolicy,
+    // fees are burned instead of collected, there is no revenue to split, withdraw or refund
+    #[serde(default)]
+    pub burn_fees: bool,
+    // percent of the price of a registration sent to the referrer named by the registrant
+    #[serde(default)]
+    pub referral_percent: u64,
+    // names that ran out of grace period cost premium_multiplier times their price at first,
+    // decaying to the price over premium_window seconds
+    #[serde(default)]
+    pub premium_multiplier: u64,
+    #[serde(default)]
+    pub premium_window: u64,
+    // phase set by the admin, it overrides the launch schedule until cleared
+    #[serde(default)]
+    pub launch_phase: Option<LaunchPhase>,
+    // holders of this token may register during the allowlist phase without being allowlisted
+    #[serde(default)]
+    pub allowlist_gate: Option<TokenGate>,
+    // percent of marketplace sales collected as a fee, and paid as royalty to the registrant of the name
+    #[serde(default)]
+    pub marketplace_fee_percent: u64,
+    #[serde(default)]
+    pub royalty_percent: u64,
+    // how many names an address may hold, and how long it waits between registrations
+    #[serde(default)]
+    pub max_names_per_address: Option<u64>,
+    #[serde(default)]
+    pub registration_cooldown_seconds: Option<u64>,
+    // seconds from now a name may be renewed up to, renewals are unlimited when unset
+    #[serde(default)]
+    pub max_renewal_horizon: Option<u64>,
+}
+
+/// one_or_many reads a list of accepted prices, also from prices stored and sent as a single coin or null
+pub fn one_or_many<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<Coin>, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(crate = "cosmwasm_schema::serde", untagged)]
+    enum OneOrMany {
+        One(Coin),
+        Many(Vec<Coin>),
+    }
+
+    Ok(match Option::<OneOrMany>::deserialize(deserializer)? {
+        None => vec![],
+        Some(OneOrMany::One(coin)) => vec![coin],
+        Some(OneOrMany::Many(coins)) => coins,
+    })
+}
+
+/// Beneficiary is credited `percent` of every fee paid to the contract
+#[cw_serde]
+pub struct Beneficiary {
+    pub address: Addr,
+    pub percent: u64,
+}
+
+/// NamePolicy is what names and profiles must look like to be registered, lengths are in bytes
+#[cw_serde]
+pub struct NamePolicy {
+    pub min_name_length: u64,
+    pub max_name_length: u64,
+    pub max_bio_length: u64,
+    pub max_website_length: u64,
+    // characters accepted on top of lowercase ascii letters, digits and hyphens
+    pub charset: Charset,
+}
+
+impl Default for NamePolicy {
+    fn default() -> Self {
+        NamePolicy {
+            min_name_length: MIN_NAME_LENGTH,
+            max_name_length: MAX_NAME_LENGTH,
+            max_bio_length: MAX_BIO_LENGTH,
+            max_website_length: MAX_WEBSITE_LENGTH,
+            charset: Charset::default(),
+        }
+    }
+}
+
+#[cw_serde]
+#[derive(Default)]
+pub struct Charset {
+    pub allow_underscore: bool,
+    // dotted names take precedence over subnames of the same spelling
+    pub allow_dot: bool,
+    // lowercase letters and digits of any script, names must already be lowercase
+    pub allow_unicode: bool,
+}
+
+/// PriceTier is the registration price of names up to max_length characters
+#[cw_serde]
+pub struct PriceTier {
+    pub max_length: u64,
+    pub price: Coin,
 }
 
 #[cw_serde]
@@ -15,7 +168,438 @@ pub struct NameRecord {
     pub owner: Addr,
     pub bio: String,
     pub website: String,
+    #[serde(default)]
+    pub verified: bool,
+    #[serde(default)]
+    pub accept_attestations: bool,
+    #[serde(default, skip_serializing_if = "CacheControl::is_standard")]
+    pub cache_control: CacheControl,
+    // names registered before expiry was configured never expire
+    #[serde(default)]
+    pub expires_at: Option<Timestamp>,
+    #[serde(default)]
+    pub socials: Socials,
+    // ipfs:// or ar:// CID of the decentralized website of the name
+    #[serde(default)]
+    pub content_hash: Option<String>,
+}
+
+/// Socials are the avatar and social accounts of a name, empty when not set
+#[cw_serde]
+#[derive(Default)]
+pub struct Socials {
+    // https:// or ipfs:// URI of the avatar image
+    pub avatar_url: String,
+    // handles without the leading @
+    pub twitter: String,
+    pub github: String,
+    pub discord: String,
+}
+
+/// NameRecordV1 is the layout records were stored in by releases before 0.2.0
+#[cw_serde]
+pub struct NameRecordV1 {
+    pub owner: Addr,
+    pub bio: String,
+    pub website: String,
+}
+
+impl From<NameRecordV1> for NameRecord {
+    fn from(record: NameRecordV1) -> Self {
+        NameRecord {
+            owner: record.owner,
+            bio: record.bio,
+            website: record.website,
+            verified: false,
+            accept_attestations: false,
+            cache_control: CacheControl::Standard,
+            expires_at: None,
+            socials: Socials::default(),
+            content_hash: None,
+        }
+    }
+}
+
+/// CacheControl is a hint for resolvers and gateways caching a record
+#[cw_serde]
+#[derive(Default)]
+pub enum CacheControl {
+    #[default]
+    Standard,
+    // the record changes often, always resolve it again
+    NoCache,
+    // the record is static and may be cached indefinitely
+    Immutable,
+}
+
+impl CacheControl {
+    pub fn is_standard(&self) -> bool {
+        *self == CacheControl::Standard
+    }
+}
+
+/// Webhook is a contract called back whenever something happens to a name
+#[cw_serde]
+pub struct Webhook {
+    pub contract: Addr,
+    // passed back untouched in every callback
+    pub payload: Binary,
+    // expiry the contract was last warned about, so it is warned once per period
+    pub notified_expiry: Option<Timestamp>,
+}
+
+#[cw_serde]
+pub struct TransferMemo {
+    pub from: Addr,
+    pub memo: String,
+}
+
+/// TransferOffer is a transfer waiting for the recipient to accept it
+#[cw_serde]
+pub struct TransferOffer {
+    pub from: Addr,
+    pub to: Addr,
+    // paid by the recipient to `from` on acceptance
+    pub price: Option<Coin>,
+}
+
+/// PaidTerm is what the owner of a name paid for the `seconds` of registration it has left to use
+#[cw_serde]
+pub struct PaidTerm {
+    pub paid: Vec<Coin>,
+    pub seconds: u64,
+}
+
+/// Listing is a name put up for sale to anyone at `price`
+#[cw_serde]
+pub struct Listing {
+    pub seller: Addr,
+    pub price: Coin,
+    pub listed_at: Timestamp,
+}
+
+/// LaunchPhase is who may register names at a point of the launch
+#[cw_serde]
+#[derive(Copy)]
+pub enum LaunchPhase {
+    // only the admin hands out names, through reserved names and promo commitments
+    ReservedOnly,
+    // allowlisted addresses may register
+    Allowlist,
+    // names are sold through auctions only
+    Auction,
+    // anyone may register
+    Public,
+}
+
+/// TokenGate lets the holders of a token in, checked by querying the token contract
+#[cw_serde]
+pub enum TokenGate {
+    // holders of a token of the cw721 collection
+    Cw721 { contract: Addr },
+    // holders of at least `min_balance` of the cw20 token
+    Cw20 { contract: Addr, min_balance: Uint128 },
+}
+
+#[cw_serde]
+pub struct PhaseStart {
+    pub phase: LaunchPhase,
+    pub starts_at: Timestamp,
+}
+
+/// SweepCursor is the last name and auction the end-block sweep looked at, none once it went through all of them
+#[cw_serde]
+#[derive(Default)]
+pub struct SweepCursor {
+    pub name: Option<String>,
+    pub auction: Option<String>,
+}
+
+/// Tombstone is what is left of a name removed from the registry
+#[cw_serde]
+pub struct Tombstone {
+    pub previous_owner: Addr,
+    // block height and time of the removal
+    pub height: u64,
+    pub removed_at: Timestamp,
+    pub reason: RemovalReason,
+}
+
+#[cw_serde]
+pub enum RemovalReason {
+    // given up by its owner
+    Released,
+    // taken down by the admin
+    Revoked,
+    // replaced by a new registration after its grace period ran out
+    Expired,
+}
+
+/// HistoryEntry is one change in the life of a name
+#[cw_serde]
+pub struct HistoryEntry {
+    pub event: HistoryEvent,
+    // sender of the change, the owner for registrations
+    pub actor: Addr,
+    pub height: u64,
+    pub time: Timestamp,
+}
+
+#[cw_serde]
+pub enum HistoryEvent {
+    Registered { owner: Addr },
+    Transferred { from: Addr, to: Addr },
+    Edited {},
+    Renewed { expires_at: Timestamp },
+    Removed { reason: RemovalReason },
+}
+
+/// Auction sells a premium name to the highest bidder once it ends
+#[cw_serde]
+pub struct Auction {
+    pub min_bid: Coin,
+    pub ends_at: Timestamp,
+}
+
+#[cw_serde]
+pub struct Bid {
+    pub bidder: Addr,
+    pub amount: Coin,
+}
+
+#[cw_serde]
+pub struct Category {
+    pub requires_approval: bool,
+}
+
+#[cw_serde]
+#[derive(Default)]
+pub struct OwnerStats {
+    pub name_count: u64,
+    pub first_registration: Option<Timestamp>,
+    pub total_spent: Vec<Coin>,
+    #[serde(default)]
+    pub last_registration: Option<Timestamp>,
+}
+
+/// NameStats counts what happened to a name since it was registered
+#[cw_serde]
+#[derive(Default)]
+pub struct NameStats {
+    pub edits: u64,
+    pub transfers: u64,
+    pub renewals: u64,
+    // transfers paid for through AcceptTransfer, and the auction that sold the name
+    pub sales: u64,
+    pub sale_volume: Vec<Coin>,
+}
+
+#[cw_serde]
+pub struct Migration {
+    // last name rewritten, the next batch starts after it
+    pub last_name: Option<String>,
+    pub migrated: u64,
+    // rebuild the name counters while rewriting, for contracts that predate them
+    pub backfill_counters: bool,
+    // records are still in the NameRecordV1 layout
+    #[serde(default)]
+    pub legacy_layout: bool,
+}
+
+#[cw_serde]
+pub struct Sponsorship {
+    pub balance: Vec<Coin>,
+    // open sponsorships pay for anyone, otherwise only listed addresses
+    pub open: bool,
+}
+
+/// GrantAction is a group of executes an owner can let another address perform
+#[cw_serde]
+pub enum GrantAction {
+    // Edit, SetCacheControl and the text records
+    Edit,
+    Transfer,
+    // JoinCategory and LeaveCategory
+    Categories,
+    // SetAttestationOptIn and RemoveAttestation
+    Attestations,
+}
+
+/// Expiration is when a cw721 approval runs out, serialized like cw-utils' Expiration
+#[cw_serde]
+pub enum Expiration {
+    AtHeight(u64),
+    AtTime(Timestamp),
+    Never {},
+}
+
+impl Default for Expiration {
+    fn default() -> Self {
+        Expiration::Never {}
+    }
+}
+
+impl Expiration {
+    pub fn is_expired(&self, block: &BlockInfo) -> bool {
+        match self {
+            Expiration::AtHeight(height) => block.height >= *height,
+            Expiration::AtTime(time) => block.time >= *time,
+            Expiration::Never {} => false,
+        }
+    }
+}
+
+#[cw_serde]
+pub struct Grant {
+    pub actions: Vec<GrantAction>,
+    pub expires: Option<Timestamp>,
 }
 
 pub const CONFIG: Item<Config> = Item::new("config");
-pub const NAME_RESOLVER: Map<&[u8], NameRecord> = Map::new("name_resolver");
+pub struct NameIndexes<'a> {
+    // reverse resolution, the names held by an address
+    pub owner: MultiIndex<'a, Addr, NameRecord, &'a [u8]>,
+}
+
+impl<'a> IndexList<NameRecord> for NameIndexes<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<NameRecord>> + '_> {
+        let v: Vec<&dyn Index<NameRecord>> = vec![&self.owner];
+        Box::new(v.into_iter())
+    }
+}
+
+const OWNER_INDEX_NAMESPACE: &str = "name_resolver__owner";
+// the entries of the owner index of name_resolver as stored, (owner, name) -> length of the name. Stale entries
+// are removed through it, the index itself needs the record they were written for
+pub const OWNER_INDEX: Map<(&Addr, &[u8]), u32> = Map::new(OWNER_INDEX_NAMESPACE);
+
+// records saved before the owner index existed are indexed when a migration rewrites them
+pub fn name_resolver<'a>() -> IndexedMap<'a, &'a [u8], NameRecord, NameIndexes<'a>> {
+    let indexes = NameIndexes {
+        owner: MultiIndex::new(|record: &NameRecord| record.owner.clone(), "name_resolver", OWNER_INDEX_NAMESPACE),
+    };
+    IndexedMap::new("name_resolver", indexes)
+}
+// set once the contract is sunset, pointing at the contract that replaces it
+pub const SUCCESSOR: Item<Addr> = Item::new("successor");
+pub const TOTAL_NAMES: Item<u64> = Item::new("total_names");
+// the records of name_resolver read in the layout of releases before 0.2.0, only migrations use it
+pub const NAME_RECORDS_V1: Map<&[u8], NameRecordV1> = Map::new("name_resolver");
+// registrations since instantiation, unlike TOTAL_NAMES it never goes down
+pub const TOTAL_REGISTRATIONS: Item<u64> = Item::new("total_registrations");
+// every fee collected per denom, burned fees included
+pub const FEES_COLLECTED: Item<Vec<Coin>> = Item::new("fees_collected");
+// phases of the launch in the order they start, registration is public without any
+pub const LAUNCH_SCHEDULE: Item<Vec<PhaseStart>> = Item::new("launch_schedule");
+pub const ALLOWLIST: Map<&Addr, Empty> = Map::new("allowlist");
+// admin proposed by the current one, it takes over once it accepts
+pub const PENDING_OWNER: Item<Addr> = Item::new("pending_owner");
+// present while a migration is still rewriting records
+pub const MIGRATION: Item<Migration> = Item::new("migration");
+pub const OWNER_STATS: Map<&Addr, OwnerStats> = Map::new("owner_stats");
+pub const NAME_STATS: Map<&[u8], NameStats> = Map::new("name_stats");
+pub const VERIFIERS: Map<&Addr, Empty> = Map::new("verifiers");
+// contracts allowed to register names on behalf of users, e.g. auctions, bridges or promo campaigns
+pub const REGISTRARS: Map<&Addr, Empty> = Map::new("registrars");
+pub const ATTESTATION_ISSUERS: Map<&Addr, Empty> = Map::new("attestation_issuers");
+// key id -> secp256k1 public key of an off-chain gateway signing resolution payloads
+pub const GATEWAY_KEYS: Map<&str, Binary> = Map::new("gateway_keys");
+
+// names governance froze, see SudoMsg::FreezeName
+pub const FROZEN_NAMES: Map<&[u8], Empty> = Map::new("frozen_names");
+// names locked by their owner
+pub const LOCKED_NAMES: Map<&[u8], Empty> = Map::new("locked_names");
+// names frozen by the admin
+pub const ADMIN_FROZEN_NAMES: Map<&[u8], Empty> = Map::new("admin_frozen_names");
+
+// every change of a name numbered from 0 in order, the history is never cleared, removals included
+pub const NAME_HISTORY: Map<(&[u8], u64), HistoryEntry> = Map::new("name_history");
+pub const HISTORY_LENGTHS: Map<&[u8], u64> = Map::new("history_lengths");
+// (name, issuer, key) -> value, so every issuer writes into its own namespace
+pub const ATTESTATIONS: Map<(&[u8], &Addr, &str), String> = Map::new("attestations");
+pub const SPONSORSHIPS: Map<&Addr, Sponsorship> = Map::new("sponsorships");
+// (sponsor, beneficiary) addresses a closed sponsorship pays for
+pub const SPONSORED_ADDRESSES: Map<(&Addr, &Addr), Empty> = Map::new("sponsored_addresses");
+// (sponsor, beneficiary) that already used a sponsorship, each address is gifted one name
+pub const SPONSORED_CLAIMS: Map<(&Addr, &Addr), Empty> = Map::new("sponsored_claims");
+// (owner, grantee) -> what the grantee may do on the owner's names
+pub const GRANTS: Map<(&Addr, &Addr), Grant> = Map::new("grants");
+// sha256("<name>:<secret>") of promo names the admin committed to
+pub const PROMO_COMMITMENTS: Map<&[u8], Empty> = Map::new("promo_commitments");
+pub const WEBHOOKS: Map<&[u8], Webhook> = Map::new("webhooks");
+// brand or blocked names only the admin can assign
+pub const RESERVED: Map<&[u8], Empty> = Map::new("reserved");
+// words the name score looks for at the start of names
+pub const DICTIONARY: Map<&str, Empty> = Map::new("dictionary");
+// owner of every name ever registered at each height, none once the name is removed
+pub const OWNERS: SnapshotMap<&[u8], Option<Addr>> = SnapshotMap::new(
+    "owners",
+    "owners__checkpoints",
+    "owners__changelog",
+    Strategy::EveryBlock,
+);
+// last removal of a name, kept after the name is registered again
+pub const TOMBSTONES: Map<&[u8], Tombstone> = Map::new("tombstones");
+// (name, spender) -> approval to edit and transfer the name, cleared on transfer
+pub const NFT_APPROVALS: Map<(&[u8], &Addr), Expiration> = Map::new("nft_approvals");
+// off-chain metadata of a name, served by NftInfo instead of the on-chain profile
+pub const TOKEN_URIS: Map<&[u8], String> = Map::new("token_uris");
+pub const TRANSFER_OFFERS: Map<&[u8], TransferOffer> = Map::new("transfer_offers");
+pub const LISTINGS: Map<&[u8], Listing> = Map::new("listings");
+// who registered each name, paid the royalty when it is sold on the marketplace
+pub const REGISTRANTS: Map<&[u8], Addr> = Map::new("registrants");
+// what was paid for the registration time of each name, releases refund at most its unused part
+pub const PAID_TERMS: Map<&[u8], PaidTerm> = Map::new("paid_terms");
+// premium names only sold through an auction, and the highest bid held in escrow for each
+pub const AUCTIONS: Map<&[u8], Auction> = Map::new("auctions");
+pub const BIDS: Map<&[u8], Bid> = Map::new("bids");
+// where the end-block sweep goes on in the next block
+pub const SWEEP_CURSOR: Item<SweepCursor> = Item::new("sweep_cursor");
+// (owner, operator) addresses allowed to manage every name of the owner
+pub const OPERATORS: Map<(&Addr, &Addr), Empty> = Map::new("operators");
+// (recipient, name) -> memo of a transfer, kept until the recipient's next execute
+pub const TRANSFER_MEMOS: Map<(&Addr, &[u8]), TransferMemo> = Map::new("transfer_memos");
+// (name, watcher) -> deposit of an address waiting for the name to become available
+pub const NAME_WATCHERS: Map<(&[u8], &Addr), Vec<Coin>> = Map::new("name_watchers");
+/// Hold keeps an available name for the holder until it expires, the deposit is applied to the
+/// holder's registration or forfeited
+#[cw_serde]
+pub struct Hold {
+    pub holder: Addr,
+    pub deposit: Coin,
+    pub expires_at: Timestamp,
+}
+
+pub const HOLDS: Map<&[u8], Hold> = Map::new("holds");
+/// Subname is a `label.parent` name managed by the owner of the parent
+#[cw_serde]
+pub struct Subname {
+    pub owner: Addr,
+    pub bio: String,
+    pub website: String,
+}
+
+// (parent, label) -> subname, removed with the parent
+pub const SUBNAMES: Map<(&[u8], &str), Subname> = Map::new("subnames");
+// (name, key) -> value of the free-form text records set by the owner
+pub const TEXT_RECORDS: Map<(&[u8], &str), String> = Map::new("text_records");
+// (name, chain_id) -> address the name resolves to on another chain
+pub const CHAIN_ADDRESSES: Map<(&[u8], &str), String> = Map::new("chain_addresses");
+// set while the admin has paused the whole contract
+pub const PAUSED: Item<bool> = Item::new("paused");
+// snake_case names of the executes governance paused
+pub const PAUSED_ACTIONS: Map<&str, Empty> = Map::new("paused_actions");
+// name an address displays as its handle, the reverse record
+pub const PRIMARY_NAME: Map<&Addr, String> = Map::new("primary_name");
+// fee shares credited to the beneficiaries of the revenue split, held until they withdraw them
+pub const TREASURY: Map<&Addr, Vec<Coin>> = Map::new("treasury");
+// proceeds credited to sellers, held until they claim them
+pub const PAYOUTS: Map<&Addr, Vec<Coin>> = Map::new("payouts");
+// funds held for users (sponsorship balances, watch deposits, bids, payouts, fee shares), never swept as revenue
+pub const ESCROWED: Item<Vec<Coin>> = Item::new("escrowed");
+// everything donated to the community fund, in total and per donor
+pub const DONATIONS: Item<Vec<Coin>> = Item::new("donations");
+pub const DONOR_TOTALS: Map<&Addr, Vec<Coin>> = Map::new("donor_totals");
+pub const CATEGORIES: Map<&str, Category> = Map::new("categories");
+// (category, name) -> approved, pending members are stored as false
+pub const CATEGORY_MEMBERS: Map<(&str, &[u8]), bool> = Map::new("category_members");
@@ -1,12 +1,28 @@
 #[cfg(test)]
 mod test_module {
-    use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
-    use cosmwasm_std::{coin, coins, from_binary, Coin, Deps, DepsMut};
+    use cosmwasm_schema::cw_serde;
+    use cosmwasm_schema::schemars::schema::Schema;
+    use cosmwasm_std::testing::{
+        mock_dependencies, mock_env, mock_ibc_channel_connect_ack, mock_ibc_channel_open_init, mock_ibc_packet_recv,
+        mock_info, MOCK_CONTRACT_ADDR,
+    };
+    use cosmwasm_std::{
+        attr, coin, coins, from_binary, from_slice, to_binary, Addr, BankMsg, Binary, Coin, ContractResult, CosmosMsg,
+        Deps, DepsMut, Env, FullDelegation, HexBinary, IbcOrder, Reply, ReplyOn, StdError, SubMsgResponse, SubMsgResult,
+        SystemResult, Uint128, WasmMsg, WasmQuery,
+    };
+    use cw_storage_plus::{Index, Map};
+    use sha2::{Digest, Sha256};
 
-    use crate::contract::{execute, instantiate, query};
+    use crate::contract::{execute, instantiate, migrate, query, reply, sudo};
     use crate::error::ContractError;
-    use crate::msg::{ExecuteMsg, InstantiateMsg, QueryMsg, ResolveRecordResponse};
-    use crate::state::Config;
+    use crate::harness::{App, ADMIN, CONTRACT};
+    use crate::ibc::{ibc_channel_connect, ibc_channel_open, ibc_packet_receive, IBC_VERSION};
+    use crate::msg::{AllNamesResponse, AvailabilityResponse, AvailabilityStatus, AttestationsResponse, BalancesResponse, CategoryMembersResponse, AuctionResponse, ConfigResponse, DonationsResponse, MigrateMsg, NameScoreResponse, PausedActionsResponse, PayoutResponse, PendingOwnerResponse, PhaseResponse, Preset, PrimaryNameResponse, RecordStatsResponse, ResolveAddressResponse, TextRecordsResponse, TombstoneResponse, TreasuryResponse, WatchersResponse, MigrationPlanResponse, NameEvent, NameHookMsg, NamesResponse, NftInfoResponse, NftReceiverMsg, NameOwner, OperatorsResponse, OwnersSnapshotResponse, OwnerOfResponse, TokensResponse, WebhookResponse, MigrationStatusResponse, SponsorshipResponse, DnsZoneResponse, OwnerStatsResponse, SupplyResponse, StatsResponse, TransferMemosResponse, TransferOfferResponse, ExecuteMsg, RegistrationPriceResponse, RegistrarsResponse, RegistrationQuotaResponse, FeeQueryMsg, FeeResponse, GatewayKeysResponse, GatewayPayload, NameHistoryResponse, HoldResponse, InstantiateMsg, InvariantsResponse, ListingsResponse, QueryMsg, RegistrationData, ResolveAck, ResolvePacket, ResolvePayloadResponse, ResolveRecordResponse, ResolveRecordAtResponse, ResolveRecordsResponse, RevenueShare, ConfigUpdate, SudoMsg, VerifyGatewaySignatureResponse};
+    use crate::state::{
+        CacheControl, Charset, Expiration, GrantAction, HistoryEvent, LaunchPhase, NamePolicy, PhaseStart, PriceTier,
+        RemovalReason, Socials, Tombstone, TokenGate, OWNER_STATS, TOTAL_NAMES, name_resolver,
+    };
 
     fn assert_name_owner(deps: Deps, name: &str, owner: &str) {
         let res = query(
@@ -22,16 +38,25 @@ mod test_module {
         assert_eq!(Some(owner.to_string()), value.address);
     }
 
-    fn assert_config_state(deps: Deps, expected: Config) {
+    fn assert_config_state(deps: Deps, expected: ConfigResponse) {
         let res = query(deps, mock_env(), QueryMsg::Config {}).unwrap();
-        let value: Config = from_binary(&res).unwrap();
+        let value: ConfigResponse = from_binary(&res).unwrap();
         assert_eq!(value, expected);
     }
 
     fn mock_init_with_price(deps: DepsMut, purchase_price: Coin, transfer_price: Coin) {
         let msg = InstantiateMsg {
-            purchase_price: Some(purchase_price),
-            transfer_price: Some(transfer_price),
+            admin: None,
+            purchase_price: vec![purchase_price],
+            transfer_price: vec![transfer_price],
+            edit_price: vec![],
+            max_supply: None,
+            registration_duration: None,
+            grace_period: None,
+            renewal_price: None,
+            preset: None,
+            launch_schedule: vec![],
+            burn_fees: false,
         };
 
         let info = mock_info("creator", &coins(2, "token"));
@@ -41,8 +66,17 @@ mod test_module {
 
     fn mock_init_no_price(deps: DepsMut) {
         let msg = InstantiateMsg {
-            purchase_price: None,
-            transfer_price: None,
+            admin: None,
+            purchase_price: vec![],
+            transfer_price: vec![],
+            edit_price: vec![],
+            max_supply: None,
+            registration_duration: None,
+            grace_period: None,
+            renewal_price: None,
+            preset: None,
+            launch_schedule: vec![],
+            burn_fees: false,
         };
 
         let info = mock_info("creator", &coins(2, "token"));
@@ -55,6 +89,10 @@ mod test_module {
         let info = mock_info("alice_key", sent);
         let msg = ExecuteMsg::Register {
             name: "alice".to_string(),
+            bio: "".to_string(),
+            website: "".to_string(),
+            round_up: false,
+            referrer: None,
         };
         let _res = execute(deps, mock_env(), info, msg)
             .expect("contract successfully handles Register message");
@@ -68,9 +106,41 @@ mod test_module {
 
         assert_config_state(
             deps.as_ref(),
-            Config {
-                purchase_price: None,
-                transfer_price: None,
+            ConfigResponse {
+                owner: Addr::unchecked("creator"),
+                purchase_price: vec![],
+                transfer_price: vec![],
+                edit_price: vec![],
+                max_supply: None,
+                frozen: false,
+                community_fund: None,
+                min_delegation: None,
+                registration_duration: None,
+                grace_period: 0,
+                renewal_price: None,
+                edit_byte_price: None,
+                length_prices: vec![],
+                watch_deposit: None,
+                release_cooldown: 0,
+                fee_collector: None,
+                revenue_split: vec![],
+                fee_calculator: None,
+                ibc_channels: vec![],
+                hold_deposit: None,
+                hold_duration: 0,
+                name_policy: NamePolicy::default(),
+                burn_fees: false,
+                referral_percent: 0,
+                premium_multiplier: 0,
+                premium_window: 0,
+                launch_phase: None,
+                allowlist_gate: None,
+                marketplace_fee_percent: 0,
+                royalty_percent: 0,
+                max_names_per_address: None,
+                registration_cooldown_seconds: None,
+                max_renewal_horizon: None,
+                successor: None,
             },
         );
     }
@@ -83,9 +153,41 @@ mod test_module {
 
         assert_config_state(
             deps.as_ref(),
-            Config {
-                purchase_price: Some(coin(3, "token")),
-                transfer_price: Some(coin(4, "token")),
+            ConfigResponse {
+                owner: Addr::unchecked("creator"),
+                purchase_price: vec![coin(3, "token")],
+                transfer_price: vec![coin(4, "token")],
+                edit_price: vec![],
+                max_supply: None,
+                frozen: false,
+                community_fund: None,
+                min_delegation: None,
+                registration_duration: None,
+                grace_period: 0,
+                renewal_price: None,
+                edit_byte_price: None,
+                length_prices: vec![],
+                watch_deposit: None,
+                release_cooldown: 0,
+                fee_collector: None,
+                revenue_split: vec![],
+                fee_calculator: None,
+                ibc_channels: vec![],
+                hold_deposit: None,
+                hold_duration: 0,
+                name_policy: NamePolicy::default(),
+                burn_fees: false,
+                referral_percent: 0,
+                premium_multiplier: 0,
+                premium_window: 0,
+                launch_phase: None,
+                allowlist_gate: None,
+                marketplace_fee_percent: 0,
+                royalty_percent: 0,
+                max_names_per_address: None,
+                registration_cooldown_seconds: None,
+                max_renewal_horizon: None,
+                successor: None,
             },
         );
     }
@@ -110,6 +212,10 @@ mod test_module {
         let info = mock_info("bob_key", &coins(5, "token"));
         let msg = ExecuteMsg::Register {
             name: "bob".to_string(),
+            bio: "".to_string(),
+            website: "".to_string(),
+            round_up: false,
+            referrer: None,
         };
 
         let _res = execute(deps.as_mut(), mock_env(), info, msg)
@@ -130,6 +236,10 @@ mod test_module {
         let info = mock_info("bob_key", &coins(2, "token"));
         let msg = ExecuteMsg::Register {
             name: "alice".to_string(),
+            bio: "".to_string(),
+            website: "".to_string(),
+            round_up: false,
+            referrer: None,
         };
         let res = execute(deps.as_mut(), mock_env(), info, msg);
 
@@ -142,6 +252,10 @@ mod test_module {
         let info = mock_info("alice_key", &coins(2, "token"));
         let msg = ExecuteMsg::Register {
             name: "alice".to_string(),
+            bio: "".to_string(),
+            website: "".to_string(),
+            round_up: false,
+            referrer: None,
         };
         let res = execute(deps.as_mut(), mock_env(), info, msg);
 
@@ -161,6 +275,10 @@ mod test_module {
         // hi is too short
         let msg = ExecuteMsg::Register {
             name: "hi".to_string(),
+            bio: "".to_string(),
+            website: "".to_string(),
+            round_up: false,
+            referrer: None,
         };
         match execute(deps.as_mut(), mock_env(), info.clone(), msg) {
             Ok(_) => panic!("Must return error"),
@@ -171,6 +289,10 @@ mod test_module {
         // 65 chars is too long
         let msg = ExecuteMsg::Register {
             name: "01234567890123456789012345678901234567890123456789012345678901234".to_string(),
+            bio: "".to_string(),
+            website: "".to_string(),
+            round_up: false,
+            referrer: None,
         };
         match execute(deps.as_mut(), mock_env(), info.clone(), msg) {
             Ok(_) => panic!("Must return error"),
@@ -181,6 +303,10 @@ mod test_module {
         // no upper case...
         let msg = ExecuteMsg::Register {
             name: "LOUD".to_string(),
+            bio: "".to_string(),
+            website: "".to_string(),
+            round_up: false,
+            referrer: None,
         };
         match execute(deps.as_mut(), mock_env(), info.clone(), msg) {
             Ok(_) => panic!("Must return error"),
@@ -190,6 +316,10 @@ mod test_module {
         // ... or spaces
         let msg = ExecuteMsg::Register {
             name: "two words".to_string(),
+            bio: "".to_string(),
+            website: "".to_string(),
+            round_up: false,
+            referrer: None,
         };
         match execute(deps.as_mut(), mock_env(), info, msg) {
             Ok(_) => panic!("Must return error"),
@@ -207,6 +337,10 @@ mod test_module {
         let info = mock_info("alice_key", &[]);
         let msg = ExecuteMsg::Register {
             name: "alice".to_string(),
+            bio: "".to_string(),
+            website: "".to_string(),
+            round_up: false,
+            referrer: None,
         };
 
         let res = execute(deps.as_mut(), mock_env(), info, msg);
@@ -227,13 +361,17 @@ mod test_module {
         let info = mock_info("alice_key", &coins(2, "earth"));
         let msg = ExecuteMsg::Register {
             name: "alice".to_string(),
+            bio: "".to_string(),
+            website: "".to_string(),
+            round_up: false,
+            referrer: None,
         };
 
         let res = execute(deps.as_mut(), mock_env(), info, msg);
 
         match res {
-            Ok(_) => panic!("register call should fail with insufficient fees"),
-            Err(ContractError::InsufficientFundsSend {}) => {}
+            Ok(_) => panic!("register call should fail with a foreign fee denom"),
+            Err(ContractError::UnacceptedDenom { denom }) => assert_eq!(denom, "earth"),
             Err(e) => panic!("Unexpected error: {:?}", e),
         }
     }
@@ -249,6 +387,8 @@ mod test_module {
         let msg = ExecuteMsg::Transfer {
             name: "alice".to_string(),
             to: "bob_key".to_string(),
+            round_up: false,
+            memo: None,
         };
 
         let _res = execute(deps.as_mut(), mock_env(), info, msg)
@@ -264,10 +404,12 @@ mod test_module {
         mock_alice_registers_name(deps.as_mut(), &coins(2, "token"));
 
         // alice can transfer her name successfully to bob
-        let info = mock_info("alice_key", &[coin(1, "earth"), coin(2, "token")]);
+        let info = mock_info("alice_key", &coins(2, "token"));
         let msg = ExecuteMsg::Transfer {
             name: "alice".to_string(),
             to: "bob_key".to_string(),
+            round_up: false,
+            memo: None,
         };
 
         let _res = execute(deps.as_mut(), mock_env(), info, msg)
@@ -287,6 +429,8 @@ mod test_module {
         let msg = ExecuteMsg::Transfer {
             name: "alice42".to_string(),
             to: "bob_key".to_string(),
+            round_up: false,
+            memo: None,
         };
 
         let res = execute(deps.as_mut(), mock_env(), info, msg);
@@ -312,6 +456,8 @@ mod test_module {
         let msg = ExecuteMsg::Transfer {
             name: "alice".to_string(),
             to: "bob_key".to_string(),
+            round_up: false,
+            memo: None,
         };
 
         let res = execute(deps.as_mut(), mock_env(), info, msg);
@@ -333,10 +479,12 @@ mod test_module {
         mock_alice_registers_name(deps.as_mut(), &coins(2, "token"));
 
         // alice can transfer her name successfully to bob
-        let info = mock_info("alice_key", &[coin(1, "earth"), coin(2, "token")]);
+        let info = mock_info("alice_key", &coins(2, "token"));
         let msg = ExecuteMsg::Transfer {
             name: "alice".to_string(),
             to: "bob_key".to_string(),
+            round_up: false,
+            memo: None,
         };
 
         let res = execute(deps.as_mut(), mock_env(), info, msg);
@@ -369,4 +517,4964 @@ mod test_module {
         let value: ResolveRecordResponse = from_binary(&res).unwrap();
         assert_eq!(None, value.address);
     }
+
+    fn mock_add_category(deps: DepsMut, category: &str, requires_approval: bool) {
+        let info = mock_info("creator", &[]);
+        let msg = ExecuteMsg::AddCategory {
+            category: category.to_string(),
+            requires_approval,
+        };
+        let _res = execute(deps, mock_env(), info, msg)
+            .expect("contract successfully handles AddCategory message");
+    }
+
+    fn query_category_members(deps: Deps, category: &str, pending: bool) -> Vec<String> {
+        let res = query(
+            deps,
+            mock_env(),
+            QueryMsg::CategoryMembers {
+                category: category.to_string(),
+                pending: Some(pending),
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+        let value: CategoryMembersResponse = from_binary(&res).unwrap();
+        value.names
+    }
+
+    #[test]
+    fn join_open_category_works() {
+        let mut deps = mock_dependencies();
+        mock_init_no_price(deps.as_mut());
+        mock_alice_registers_name(deps.as_mut(), &[]);
+        mock_add_category(deps.as_mut(), "artist", false);
+
+        let info = mock_info("alice_key", &[]);
+        let msg = ExecuteMsg::JoinCategory {
+            name: "alice".to_string(),
+            category: "artist".to_string(),
+        };
+        let _res = execute(deps.as_mut(), mock_env(), info.clone(), msg.clone())
+            .expect("contract successfully handles JoinCategory message");
+
+        assert_eq!(query_category_members(deps.as_ref(), "artist", false), vec!["alice"]);
+        assert!(query_category_members(deps.as_ref(), "artist", true).is_empty());
+
+        // joining twice is rejected
+        match execute(deps.as_mut(), mock_env(), info.clone(), msg) {
+            Ok(_) => panic!("Must return error"),
+            Err(ContractError::AlreadyInCategory { .. }) => {}
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        }
+
+        let msg = ExecuteMsg::LeaveCategory {
+            name: "alice".to_string(),
+            category: "artist".to_string(),
+        };
+        let _res = execute(deps.as_mut(), mock_env(), info, msg)
+            .expect("contract successfully handles LeaveCategory message");
+        assert!(query_category_members(deps.as_ref(), "artist", false).is_empty());
+    }
+
+    #[test]
+    fn join_curated_category_requires_approval() {
+        let mut deps = mock_dependencies();
+        mock_init_no_price(deps.as_mut());
+        mock_alice_registers_name(deps.as_mut(), &[]);
+        mock_add_category(deps.as_mut(), "validator", true);
+
+        let info = mock_info("alice_key", &[]);
+        let msg = ExecuteMsg::JoinCategory {
+            name: "alice".to_string(),
+            category: "validator".to_string(),
+        };
+        let _res = execute(deps.as_mut(), mock_env(), info.clone(), msg)
+            .expect("contract successfully handles JoinCategory message");
+
+        assert!(query_category_members(deps.as_ref(), "validator", false).is_empty());
+        assert_eq!(query_category_members(deps.as_ref(), "validator", true), vec!["alice"]);
+
+        // only the admin can approve
+        let msg = ExecuteMsg::ApproveCategoryMember {
+            name: "alice".to_string(),
+            category: "validator".to_string(),
+        };
+        match execute(deps.as_mut(), mock_env(), info, msg.clone()) {
+            Ok(_) => panic!("Must return error"),
+            Err(ContractError::Unauthorized {}) => {}
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        }
+
+        let info = mock_info("creator", &[]);
+        let _res = execute(deps.as_mut(), mock_env(), info, msg)
+            .expect("contract successfully handles ApproveCategoryMember message");
+        assert_eq!(query_category_members(deps.as_ref(), "validator", false), vec!["alice"]);
+    }
+
+    #[test]
+    fn fails_on_category_management_from_nonowner() {
+        let mut deps = mock_dependencies();
+        mock_init_no_price(deps.as_mut());
+        mock_alice_registers_name(deps.as_mut(), &[]);
+
+        // only the admin can curate categories
+        let info = mock_info("alice_key", &[]);
+        let msg = ExecuteMsg::AddCategory {
+            category: "meme".to_string(),
+            requires_approval: false,
+        };
+        match execute(deps.as_mut(), mock_env(), info, msg) {
+            Ok(_) => panic!("Must return error"),
+            Err(ContractError::Unauthorized {}) => {}
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        }
+
+        // joining an unknown category fails
+        let info = mock_info("alice_key", &[]);
+        let msg = ExecuteMsg::JoinCategory {
+            name: "alice".to_string(),
+            category: "meme".to_string(),
+        };
+        match execute(deps.as_mut(), mock_env(), info, msg) {
+            Ok(_) => panic!("Must return error"),
+            Err(ContractError::CategoryNotExists { category }) => assert_eq!(category, "meme"),
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        }
+
+        // only the name owner can opt in
+        mock_add_category(deps.as_mut(), "meme", false);
+        let info = mock_info("bob_key", &[]);
+        let msg = ExecuteMsg::JoinCategory {
+            name: "alice".to_string(),
+            category: "meme".to_string(),
+        };
+        match execute(deps.as_mut(), mock_env(), info, msg) {
+            Ok(_) => panic!("Must return error"),
+            Err(ContractError::Unauthorized {}) => {}
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        }
+    }
+
+    fn query_verified(deps: Deps, name: &str) -> Option<bool> {
+        let res = query(
+            deps,
+            mock_env(),
+            QueryMsg::ResolveRecord {
+                name: name.to_string(),
+            },
+        )
+        .unwrap();
+        let value: ResolveRecordResponse = from_binary(&res).unwrap();
+        value.verified
+    }
+
+    #[test]
+    fn verifier_can_set_verified_flag() {
+        let mut deps = mock_dependencies();
+        mock_init_no_price(deps.as_mut());
+        mock_alice_registers_name(deps.as_mut(), &[]);
+        assert_eq!(query_verified(deps.as_ref(), "alice"), Some(false));
+
+        let info = mock_info("creator", &[]);
+        let msg = ExecuteMsg::AddVerifier {
+            address: "oracle_key".to_string(),
+        };
+        let _res = execute(deps.as_mut(), mock_env(), info, msg)
+            .expect("contract successfully handles AddVerifier message");
+
+        let info = mock_info("oracle_key", &[]);
+        let msg = ExecuteMsg::SetVerified {
+            name: "alice".to_string(),
+            verified: true,
+        };
+        let res = execute(deps.as_mut(), mock_env(), info, msg)
+            .expect("contract successfully handles SetVerified message");
+        assert!(res.attributes.iter().any(|attr| attr.key == "verified" && attr.value == "true"));
+        assert_eq!(query_verified(deps.as_ref(), "alice"), Some(true));
+
+        // the badge does not follow the name to a new owner
+        let info = mock_info("alice_key", &[]);
+        let msg = ExecuteMsg::Transfer {
+            name: "alice".to_string(),
+            to: "bob_key".to_string(),
+            round_up: false,
+            memo: None,
+        };
+        let _res = execute(deps.as_mut(), mock_env(), info, msg)
+            .expect("contract successfully handles Transfer message");
+        assert_eq!(query_verified(deps.as_ref(), "alice"), Some(false));
+    }
+
+    #[test]
+    fn fails_on_set_verified_from_non_verifier() {
+        let mut deps = mock_dependencies();
+        mock_init_no_price(deps.as_mut());
+        mock_alice_registers_name(deps.as_mut(), &[]);
+
+        // the owner cannot verify their own name
+        let info = mock_info("alice_key", &[]);
+        let msg = ExecuteMsg::SetVerified {
+            name: "alice".to_string(),
+            verified: true,
+        };
+        match execute(deps.as_mut(), mock_env(), info, msg) {
+            Ok(_) => panic!("Must return error"),
+            Err(ContractError::Unauthorized {}) => {}
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        }
+        assert_eq!(query_verified(deps.as_ref(), "alice"), Some(false));
+        assert_eq!(query_verified(deps.as_ref(), "bob"), None);
+    }
+
+    fn mock_add_attestation_issuer(deps: DepsMut, issuer: &str) {
+        let info = mock_info("creator", &[]);
+        let msg = ExecuteMsg::AddAttestationIssuer {
+            address: issuer.to_string(),
+        };
+        let _res = execute(deps, mock_env(), info, msg)
+            .expect("contract successfully handles AddAttestationIssuer message");
+    }
+
+    fn query_attestations(deps: Deps, name: &str) -> AttestationsResponse {
+        let res = query(
+            deps,
+            mock_env(),
+            QueryMsg::Attestations {
+                name: name.to_string(),
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+        from_binary(&res).unwrap()
+    }
+
+    #[test]
+    fn issuer_attests_opted_in_name() {
+        let mut deps = mock_dependencies();
+        mock_init_no_price(deps.as_mut());
+        mock_alice_registers_name(deps.as_mut(), &[]);
+        mock_add_attestation_issuer(deps.as_mut(), "kyc_key");
+
+        let attest = ExecuteMsg::Attest {
+            name: "alice".to_string(),
+            key: "kyc-level".to_string(),
+            value: "2".to_string(),
+        };
+
+        // alice has not opted in yet
+        let info = mock_info("kyc_key", &[]);
+        match execute(deps.as_mut(), mock_env(), info.clone(), attest.clone()) {
+            Ok(_) => panic!("Must return error"),
+            Err(ContractError::AttestationsNotAccepted { name }) => assert_eq!(name, "alice"),
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        }
+
+        let msg = ExecuteMsg::SetAttestationOptIn {
+            name: "alice".to_string(),
+            accept: true,
+        };
+        let _res = execute(deps.as_mut(), mock_env(), mock_info("alice_key", &[]), msg)
+            .expect("contract successfully handles SetAttestationOptIn message");
+
+        let _res = execute(deps.as_mut(), mock_env(), info, attest)
+            .expect("contract successfully handles Attest message");
+
+        let value = query_attestations(deps.as_ref(), "alice");
+        assert_eq!(value.attestations.len(), 1);
+        assert_eq!(value.attestations[0].issuer, "kyc_key");
+        assert_eq!(value.attestations[0].key, "kyc-level");
+        assert_eq!(value.attestations[0].value, "2");
+
+        // the owner can drop an attestation they disagree with
+        let msg = ExecuteMsg::RemoveAttestation {
+            name: "alice".to_string(),
+            issuer: "kyc_key".to_string(),
+            key: "kyc-level".to_string(),
+        };
+        let _res = execute(deps.as_mut(), mock_env(), mock_info("alice_key", &[]), msg)
+            .expect("contract successfully handles RemoveAttestation message");
+        assert!(query_attestations(deps.as_ref(), "alice").attestations.is_empty());
+    }
+
+    #[test]
+    fn fails_on_attest_from_unregistered_issuer() {
+        let mut deps = mock_dependencies();
+        mock_init_no_price(deps.as_mut());
+        mock_alice_registers_name(deps.as_mut(), &[]);
+
+        let msg = ExecuteMsg::SetAttestationOptIn {
+            name: "alice".to_string(),
+            accept: true,
+        };
+        let _res = execute(deps.as_mut(), mock_env(), mock_info("alice_key", &[]), msg)
+            .expect("contract successfully handles SetAttestationOptIn message");
+
+        let info = mock_info("random_key", &[]);
+        let msg = ExecuteMsg::Attest {
+            name: "alice".to_string(),
+            key: "kyc-level".to_string(),
+            value: "2".to_string(),
+        };
+        match execute(deps.as_mut(), mock_env(), info, msg) {
+            Ok(_) => panic!("Must return error"),
+            Err(ContractError::Unauthorized {}) => {}
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        }
+        assert!(query_attestations(deps.as_ref(), "alice").attestations.is_empty());
+    }
+
+    #[test]
+    fn dns_zone_renders_txt_records() {
+        let mut deps = mock_dependencies();
+        mock_init_no_price(deps.as_mut());
+
+        let info = mock_info("alice_key", &[]);
+        let msg = ExecuteMsg::Register {
+            name: "alice".to_string(),
+            bio: "say \"hi\"".to_string(),
+            website: "".to_string(),
+            round_up: false,
+            referrer: None,
+        };
+        let _res = execute(deps.as_mut(), mock_env(), info, msg)
+            .expect("contract successfully handles Register message");
+
+        let res = query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::DnsZone {
+                name: "alice".to_string(),
+                tld: "huahua".to_string(),
+            },
+        )
+        .unwrap();
+        let value: DnsZoneResponse = from_binary(&res).unwrap();
+        assert_eq!(value.origin, "alice.huahua.");
+        // empty website is skipped
+        assert_eq!(
+            value.lines,
+            vec![
+                "alice.huahua. IN TXT \"owner=alice_key\"",
+                "alice.huahua. IN TXT \"bio=say \\\"hi\\\"\"",
+            ]
+        );
+
+        // line breaks can't start records of their own, long values are split into 255 byte strings
+        let msg = ExecuteMsg::SetTextRecord {
+            name: "alice".to_string(),
+            key: "note".to_string(),
+            value: "hé\nalice.huahua. IN A 1.2.3.4".to_string(),
+        };
+        execute(deps.as_mut(), mock_env(), mock_info("alice_key", &[]), msg)
+            .expect("contract successfully handles SetTextRecord message");
+        let policy = NamePolicy { max_bio_length: 300, ..NamePolicy::default() };
+        let msg = ExecuteMsg::Editconf {
+            purchase_price: vec![],
+            transfer_price: vec![],
+            edit_price: vec![],
+            length_prices: None,
+            name_policy: Some(policy),
+        };
+        execute(deps.as_mut(), mock_env(), mock_info("creator", &[]), msg)
+            .expect("contract successfully handles Editconf message");
+        let msg = ExecuteMsg::Edit {
+            name: "alice".to_string(),
+            bio: "b".repeat(300),
+            website: "".to_string(),
+            round_up: false,
+        };
+        execute(deps.as_mut(), mock_env(), mock_info("alice_key", &[]), msg)
+            .expect("contract successfully handles Edit message");
+        let msg = QueryMsg::DnsZone { name: "alice".to_string(), tld: "huahua".to_string() };
+        let value: DnsZoneResponse = from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+        assert_eq!(value.records[1].value, format!("\"bio={}\" \"{}\"", "b".repeat(251), "b".repeat(49)));
+        assert_eq!(value.records[2].value, "\"note=h\\195\\169\\010alice.huahua. IN A 1.2.3.4\"");
+        assert!(value.lines.iter().all(|line| !line.contains('\n')));
+
+        // unregistered names render an empty zone
+        let res = query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::DnsZone {
+                name: "bob".to_string(),
+                tld: "huahua".to_string(),
+            },
+        )
+        .unwrap();
+        let value: DnsZoneResponse = from_binary(&res).unwrap();
+        assert!(value.records.is_empty());
+    }
+
+    #[test]
+    fn owner_sets_cache_control() {
+        let mut deps = mock_dependencies();
+        mock_init_no_price(deps.as_mut());
+        mock_alice_registers_name(deps.as_mut(), &[]);
+
+        let resolve = QueryMsg::ResolveRecord {
+            name: "alice".to_string(),
+        };
+        let value: ResolveRecordResponse =
+            from_binary(&query(deps.as_ref(), mock_env(), resolve.clone()).unwrap()).unwrap();
+        assert_eq!(value.cache_control, Some(CacheControl::Standard));
+
+        let msg = ExecuteMsg::SetCacheControl {
+            name: "alice".to_string(),
+            cache_control: CacheControl::Immutable,
+        };
+
+        // only the owner sets cache directives
+        match execute(deps.as_mut(), mock_env(), mock_info("bob_key", &[]), msg.clone()) {
+            Ok(_) => panic!("Must return error"),
+            Err(ContractError::Unauthorized {}) => {}
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        }
+
+        let _res = execute(deps.as_mut(), mock_env(), mock_info("alice_key", &[]), msg)
+            .expect("contract successfully handles SetCacheControl message");
+        let value: ResolveRecordResponse =
+            from_binary(&query(deps.as_ref(), mock_env(), resolve).unwrap()).unwrap();
+        assert_eq!(value.cache_control, Some(CacheControl::Immutable));
+    }
+
+    fn query_supply(deps: Deps) -> SupplyResponse {
+        let res = query(deps, mock_env(), QueryMsg::Supply {}).unwrap();
+        from_binary(&res).unwrap()
+    }
+
+    #[test]
+    fn register_fails_once_max_supply_reached() {
+        let mut deps = mock_dependencies();
+        let msg = InstantiateMsg {
+            admin: None,
+            purchase_price: vec![],
+            transfer_price: vec![],
+            edit_price: vec![],
+            max_supply: Some(1),
+            registration_duration: None,
+            grace_period: None,
+            renewal_price: None,
+            preset: None,
+            launch_schedule: vec![],
+            burn_fees: false,
+        };
+        let _res = instantiate(deps.as_mut(), mock_env(), mock_info("creator", &[]), msg)
+            .expect("contract successfully handles InstantiateMsg");
+        assert_eq!(
+            query_supply(deps.as_ref()),
+            SupplyResponse {
+                total: 0,
+                max_supply: Some(1),
+                remaining: Some(1),
+            }
+        );
+
+        mock_alice_registers_name(deps.as_mut(), &[]);
+        assert_eq!(query_supply(deps.as_ref()).remaining, Some(0));
+
+        let msg = ExecuteMsg::Register {
+            name: "bob".to_string(),
+            bio: "".to_string(),
+            website: "".to_string(),
+            round_up: false,
+            referrer: None,
+        };
+        match execute(deps.as_mut(), mock_env(), mock_info("bob_key", &[]), msg) {
+            Ok(_) => panic!("Must return error"),
+            Err(ContractError::MaxSupplyReached { max_supply }) => assert_eq!(max_supply, 1),
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        }
+    }
+
+    #[test]
+    fn max_supply_can_only_be_lowered() {
+        let mut deps = mock_dependencies();
+        mock_init_no_price(deps.as_mut());
+        mock_alice_registers_name(deps.as_mut(), &[]);
+        assert_eq!(query_supply(deps.as_ref()).remaining, None);
+
+        let info = mock_info("creator", &[]);
+        let _res = execute(deps.as_mut(), mock_env(), info.clone(), ExecuteMsg::SetMaxSupply { max_supply: 10 })
+            .expect("contract successfully handles SetMaxSupply message");
+
+        match execute(deps.as_mut(), mock_env(), info.clone(), ExecuteMsg::SetMaxSupply { max_supply: 11 }) {
+            Ok(_) => panic!("Must return error"),
+            Err(ContractError::MaxSupplyIncrease { max_supply, current }) => {
+                assert_eq!((max_supply, current), (11, 10))
+            }
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        }
+
+        match execute(deps.as_mut(), mock_env(), info, ExecuteMsg::SetMaxSupply { max_supply: 0 }) {
+            Ok(_) => panic!("Must return error"),
+            Err(ContractError::MaxSupplyBelowTotal { .. }) => {}
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        }
+        assert_eq!(query_supply(deps.as_ref()).remaining, Some(9));
+    }
+
+    fn query_owner_stats(deps: Deps, address: &str) -> OwnerStatsResponse {
+        let res = query(
+            deps,
+            mock_env(),
+            QueryMsg::OwnerStats {
+                address: address.to_string(),
+            },
+        )
+        .unwrap();
+        from_binary(&res).unwrap()
+    }
+
+    #[test]
+    fn owner_stats_follow_registrations_and_transfers() {
+        let mut deps = mock_dependencies();
+        mock_init_with_price(deps.as_mut(), coin(2, "token"), coin(3, "token"));
+        mock_alice_registers_name(deps.as_mut(), &coins(2, "token"));
+
+        let stats = query_owner_stats(deps.as_ref(), "alice_key");
+        assert_eq!(stats.name_count, 1);
+        assert_eq!(stats.first_registration, Some(mock_env().block.time));
+        assert_eq!(stats.total_spent, coins(2, "token"));
+
+        let info = mock_info("alice_key", &coins(3, "token"));
+        let msg = ExecuteMsg::Transfer {
+            name: "alice".to_string(),
+            to: "bob_key".to_string(),
+            round_up: false,
+            memo: None,
+        };
+        let _res = execute(deps.as_mut(), mock_env(), info, msg)
+            .expect("contract successfully handles Transfer message");
+
+        let stats = query_owner_stats(deps.as_ref(), "alice_key");
+        assert_eq!(stats.name_count, 0);
+        assert_eq!(stats.total_spent, coins(5, "token"));
+
+        // bob received the name without registering or paying anything
+        let stats = query_owner_stats(deps.as_ref(), "bob_key");
+        assert_eq!(stats.name_count, 1);
+        assert_eq!(stats.first_registration, None);
+        assert!(stats.total_spent.is_empty());
+    }
+
+    #[test]
+    fn frozen_config_rejects_price_changes() {
+        let mut deps = mock_dependencies();
+        mock_init_with_price(deps.as_mut(), coin(2, "token"), coin(2, "token"));
+
+        let info = mock_info("creator", &coins(2, "token"));
+        let _res = execute(deps.as_mut(), mock_env(), info.clone(), ExecuteMsg::FreezeConfig {})
+            .expect("contract successfully handles FreezeConfig message");
+
+        let msg = ExecuteMsg::Editconf {
+            purchase_price: vec![],
+            transfer_price: vec![],
+            edit_price: vec![],
+            length_prices: None,
+            name_policy: None,
+        };
+        match execute(deps.as_mut(), mock_env(), info.clone(), msg) {
+            Ok(_) => panic!("Must return error"),
+            Err(ContractError::ConfigFrozen {}) => {}
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        }
+
+        let community_fund = ExecuteMsg::SetCommunityFund { address: Some("fund_key".to_string()) };
+        let min_delegation = ExecuteMsg::SetMinDelegation { min_delegation: Some(coin(100, "token")) };
+        for msg in [ExecuteMsg::SetMaxSupply { max_supply: 5 }, community_fund, min_delegation] {
+            match execute(deps.as_mut(), mock_env(), info.clone(), msg) {
+                Ok(_) => panic!("Must return error"),
+                Err(ContractError::ConfigFrozen {}) => {}
+                Err(e) => panic!("Unexpected error: {:?}", e),
+            }
+        }
+
+        // operational actions keep working
+        mock_alice_registers_name(deps.as_mut(), &coins(2, "token"));
+        assert_name_owner(deps.as_ref(), "alice", "alice_key");
+    }
+
+    #[test]
+    fn sunset_stops_registrations_and_flags_queries() {
+        let mut deps = mock_dependencies();
+        mock_init_no_price(deps.as_mut());
+        mock_alice_registers_name(deps.as_mut(), &[]);
+
+        let msg = ExecuteMsg::Sunset {
+            successor: "successor_contract".to_string(),
+        };
+        match execute(deps.as_mut(), mock_env(), mock_info("alice_key", &[]), msg.clone()) {
+            Ok(_) => panic!("Must return error"),
+            Err(ContractError::Unauthorized {}) => {}
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        }
+        let _res = execute(deps.as_mut(), mock_env(), mock_info("creator", &[]), msg)
+            .expect("contract successfully handles Sunset message");
+
+        let msg = ExecuteMsg::Register {
+            name: "bob".to_string(),
+            bio: "".to_string(),
+            website: "".to_string(),
+            round_up: false,
+            referrer: None,
+        };
+        match execute(deps.as_mut(), mock_env(), mock_info("bob_key", &[]), msg) {
+            Ok(_) => panic!("Must return error"),
+            Err(ContractError::Sunset { successor }) => assert_eq!(successor, "successor_contract"),
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        }
+
+        // existing names still resolve, with the deprecation notice attached
+        let res = query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::ResolveRecord {
+                name: "alice".to_string(),
+            },
+        )
+        .unwrap();
+        let value: ResolveRecordResponse = from_binary(&res).unwrap();
+        assert_eq!(value.address, Some("alice_key".to_string()));
+        assert_eq!(value.successor, Some(Addr::unchecked("successor_contract")));
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap();
+        let value: ConfigResponse = from_binary(&res).unwrap();
+        assert_eq!(value.successor, Some(Addr::unchecked("successor_contract")));
+    }
+
+    #[cw_serde]
+    struct NewerResolveRecordResponse {
+        address: Option<String>,
+        bio: Option<String>,
+        website: Option<String>,
+        verified: Option<bool>,
+        expires_at: Option<u64>,
+    }
+
+    #[test]
+    fn sunset_resolution_falls_back_to_successor() {
+        let mut deps = mock_dependencies();
+        mock_init_no_price(deps.as_mut());
+        mock_alice_registers_name(deps.as_mut(), &[]);
+
+        let msg = ExecuteMsg::Sunset {
+            successor: "successor_contract".to_string(),
+        };
+        let _res = execute(deps.as_mut(), mock_env(), mock_info("creator", &[]), msg)
+            .expect("contract successfully handles Sunset message");
+
+        // the successor knows bob and answers with an extra field we don't know about
+        deps.querier.update_wasm(|query| match query {
+            WasmQuery::Smart { contract_addr, .. } if contract_addr == "successor_contract" => {
+                let resp = to_binary(&NewerResolveRecordResponse {
+                    address: Some("bob_key".to_string()),
+                    bio: Some("moved".to_string()),
+                    website: Some("".to_string()),
+                    verified: Some(false),
+                    expires_at: Some(0),
+                });
+                SystemResult::Ok(ContractResult::Ok(resp.unwrap()))
+            }
+            _ => panic!("Unexpected query"),
+        });
+
+        // local names are still answered locally
+        assert_name_owner(deps.as_ref(), "alice", "alice_key");
+
+        let res = query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::ResolveRecord {
+                name: "bob".to_string(),
+            },
+        )
+        .unwrap();
+        let value: ResolveRecordResponse = from_binary(&res).unwrap();
+        assert_eq!(value.address, Some("bob_key".to_string()));
+        assert_eq!(value.bio, Some("moved".to_string()));
+        assert_eq!(value.successor, Some(Addr::unchecked("successor_contract")));
+    }
+
+    #[test]
+    fn batched_migration_backfills_counters() {
+        let mut deps = mock_dependencies();
+        mock_init_no_price(deps.as_mut());
+        for name in ["alice", "carol", "dave"] {
+            let msg = ExecuteMsg::Register {
+                name: name.to_string(),
+                bio: "".to_string(),
+                website: "".to_string(),
+                round_up: false,
+                referrer: None,
+            };
+            let _res = execute(deps.as_mut(), mock_env(), mock_info("alice_key", &[]), msg)
+                .expect("contract successfully handles Register message");
+        }
+
+        // pretend the contract predates the counters
+        TOTAL_NAMES.remove(deps.as_mut().storage);
+        OWNER_STATS.remove(deps.as_mut().storage, &Addr::unchecked("alice_key"));
+
+        let _res = migrate(deps.as_mut(), mock_env(), MigrateMsg { batch_size: Some(2) })
+            .expect("contract successfully handles MigrateMsg");
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::MigrationStatus {}).unwrap();
+        let value: MigrationStatusResponse = from_binary(&res).unwrap();
+        assert!(value.in_progress);
+        assert_eq!(value.migrated, 2);
+        assert_eq!(value.last_name, Some("carol".to_string()));
+
+        // everything else waits for the migration
+        let msg = ExecuteMsg::Transfer {
+            name: "alice".to_string(),
+            to: "bob_key".to_string(),
+            round_up: false,
+            memo: None,
+        };
+        match execute(deps.as_mut(), mock_env(), mock_info("alice_key", &[]), msg) {
+            Ok(_) => panic!("Must return error"),
+            Err(ContractError::MigrationInProgress {}) => {}
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        }
+
+        let msg = ExecuteMsg::ContinueMigration { limit: None };
+        let res = execute(deps.as_mut(), mock_env(), mock_info("creator", &[]), msg)
+            .expect("contract successfully handles ContinueMigration message");
+        assert!(res.attributes.iter().any(|attr| attr.key == "done" && attr.value == "true"));
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::MigrationStatus {}).unwrap();
+        let value: MigrationStatusResponse = from_binary(&res).unwrap();
+        assert!(!value.in_progress);
+        assert_eq!(query_supply(deps.as_ref()).total, 3);
+        assert_eq!(query_owner_stats(deps.as_ref(), "alice_key").name_count, 3);
+    }
+
+    fn query_sponsorship(deps: Deps, sponsor: &str, address: &str) -> SponsorshipResponse {
+        let res = query(
+            deps,
+            mock_env(),
+            QueryMsg::Sponsorship {
+                sponsor: sponsor.to_string(),
+                address: Some(address.to_string()),
+            },
+        )
+        .unwrap();
+        from_binary(&res).unwrap()
+    }
+
+    #[test]
+    fn sponsor_pays_for_listed_addresses() {
+        let mut deps = mock_dependencies();
+        mock_init_with_price(deps.as_mut(), coin(2, "token"), coin(2, "token"));
+
+        let info = mock_info("project_key", &coins(3, "token"));
+        let _res = execute(deps.as_mut(), mock_env(), info, ExecuteMsg::FundSponsorship { open: false })
+            .expect("contract successfully handles FundSponsorship message");
+        let msg = ExecuteMsg::SetSponsoredAddresses {
+            add: vec!["alice_key".to_string()],
+            remove: vec![],
+        };
+        let _res = execute(deps.as_mut(), mock_env(), mock_info("project_key", &[]), msg)
+            .expect("contract successfully handles SetSponsoredAddresses message");
+        assert_eq!(query_sponsorship(deps.as_ref(), "project_key", "alice_key").eligible, Some(true));
+        assert_eq!(query_sponsorship(deps.as_ref(), "project_key", "bob_key").eligible, Some(false));
+
+        let register = |name: &str| ExecuteMsg::RegisterSponsored {
+            name: name.to_string(),
+            bio: "".to_string(),
+            website: "".to_string(),
+            sponsor: "project_key".to_string(),
+        };
+
+        // bob is not part of the cohort
+        match execute(deps.as_mut(), mock_env(), mock_info("bob_key", &[]), register("bob")) {
+            Ok(_) => panic!("Must return error"),
+            Err(ContractError::NotSponsored { .. }) => {}
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        }
+
+        let _res = execute(deps.as_mut(), mock_env(), mock_info("alice_key", &[]), register("alice"))
+            .expect("contract successfully handles RegisterSponsored message");
+        assert_name_owner(deps.as_ref(), "alice", "alice_key");
+
+        let value = query_sponsorship(deps.as_ref(), "project_key", "alice_key");
+        assert_eq!(value.balance, coins(1, "token"));
+        // each address is gifted a single name
+        assert_eq!(value.eligible, Some(false));
+        match execute(deps.as_mut(), mock_env(), mock_info("alice_key", &[]), register("alice2")) {
+            Ok(_) => panic!("Must return error"),
+            Err(ContractError::NotSponsored { .. }) => {}
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        }
+    }
+
+    #[test]
+    fn open_sponsorship_runs_dry_and_refunds_rest() {
+        let mut deps = mock_dependencies();
+        mock_init_with_price(deps.as_mut(), coin(2, "token"), coin(2, "token"));
+
+        let info = mock_info("project_key", &coins(3, "token"));
+        let _res = execute(deps.as_mut(), mock_env(), info, ExecuteMsg::FundSponsorship { open: true })
+            .expect("contract successfully handles FundSponsorship message");
+
+        let register = |name: &str| ExecuteMsg::RegisterSponsored {
+            name: name.to_string(),
+            bio: "".to_string(),
+            website: "".to_string(),
+            sponsor: "project_key".to_string(),
+        };
+        let _res = execute(deps.as_mut(), mock_env(), mock_info("alice_key", &[]), register("alice"))
+            .expect("contract successfully handles RegisterSponsored message");
+        match execute(deps.as_mut(), mock_env(), mock_info("bob_key", &[]), register("bob")) {
+            Ok(_) => panic!("Must return error"),
+            Err(ContractError::InsufficientSponsorFunds { .. }) => {}
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        }
+
+        let res = execute(deps.as_mut(), mock_env(), mock_info("project_key", &[]), ExecuteMsg::WithdrawSponsorship {})
+            .expect("contract successfully handles WithdrawSponsorship message");
+        assert_eq!(
+            res.messages[0].msg,
+            BankMsg::Send {
+                to_address: "project_key".to_string(),
+                amount: coins(1, "token"),
+            }
+            .into()
+        );
+    }
+
+    #[test]
+    fn grantee_acts_only_within_grant() {
+        let mut deps = mock_dependencies();
+        mock_init_no_price(deps.as_mut());
+        mock_alice_registers_name(deps.as_mut(), &[]);
+
+        let env = mock_env();
+        let msg = ExecuteMsg::Grant {
+            grantee: "bot_key".to_string(),
+            actions: vec![GrantAction::Edit],
+            expires: Some(env.block.time.plus_seconds(100)),
+        };
+        let _res = execute(deps.as_mut(), env.clone(), mock_info("alice_key", &[]), msg)
+            .expect("contract successfully handles Grant message");
+
+        let edit = ExecuteMsg::Edit {
+            name: "alice".to_string(),
+            bio: "edited by bot".to_string(),
+            website: "".to_string(),
+            round_up: false,
+        };
+        let _res = execute(deps.as_mut(), env.clone(), mock_info("bot_key", &[]), edit.clone())
+            .expect("contract successfully handles Edit message");
+
+        // editing does not allow transferring
+        let transfer = ExecuteMsg::Transfer {
+            name: "alice".to_string(),
+            to: "bot_key".to_string(),
+            round_up: false,
+            memo: None,
+        };
+        match execute(deps.as_mut(), env.clone(), mock_info("bot_key", &[]), transfer) {
+            Ok(_) => panic!("Must return error"),
+            Err(ContractError::Unauthorized {}) => {}
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        }
+        assert_name_owner(deps.as_ref(), "alice", "alice_key");
+
+        // the grant lapses on expiry
+        let mut later = env.clone();
+        later.block.time = env.block.time.plus_seconds(100);
+        match execute(deps.as_mut(), later, mock_info("bot_key", &[]), edit.clone()) {
+            Ok(_) => panic!("Must return error"),
+            Err(ContractError::Unauthorized {}) => {}
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        }
+
+        let msg = ExecuteMsg::RevokeGrant {
+            grantee: "bot_key".to_string(),
+        };
+        let _res = execute(deps.as_mut(), env.clone(), mock_info("alice_key", &[]), msg)
+            .expect("contract successfully handles RevokeGrant message");
+        match execute(deps.as_mut(), env, mock_info("bot_key", &[]), edit) {
+            Ok(_) => panic!("Must return error"),
+            Err(ContractError::Unauthorized {}) => {}
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        }
+    }
+
+    #[test]
+    fn grantee_transfer_moves_owner_stats() {
+        let mut deps = mock_dependencies();
+        mock_init_no_price(deps.as_mut());
+        mock_alice_registers_name(deps.as_mut(), &[]);
+
+        let msg = ExecuteMsg::Grant {
+            grantee: "custodian_key".to_string(),
+            actions: vec![GrantAction::Transfer],
+            expires: None,
+        };
+        let _res = execute(deps.as_mut(), mock_env(), mock_info("alice_key", &[]), msg)
+            .expect("contract successfully handles Grant message");
+
+        let msg = ExecuteMsg::Transfer {
+            name: "alice".to_string(),
+            to: "bob_key".to_string(),
+            round_up: false,
+            memo: None,
+        };
+        let _res = execute(deps.as_mut(), mock_env(), mock_info("custodian_key", &[]), msg)
+            .expect("contract successfully handles Transfer message");
+
+        assert_name_owner(deps.as_ref(), "alice", "bob_key");
+        assert_eq!(query_owner_stats(deps.as_ref(), "alice_key").name_count, 0);
+        assert_eq!(query_owner_stats(deps.as_ref(), "custodian_key").name_count, 0);
+        assert_eq!(query_owner_stats(deps.as_ref(), "bob_key").name_count, 1);
+    }
+
+    #[test]
+    fn promo_name_claimed_with_secret() {
+        let mut deps = mock_dependencies();
+        mock_init_with_price(deps.as_mut(), coin(2, "token"), coin(2, "token"));
+
+        let hash = HexBinary::from(Sha256::digest(b"woof:bark-at-the-moon").to_vec());
+        let msg = ExecuteMsg::CommitPromoNames { hashes: vec![hash] };
+        let _res = execute(deps.as_mut(), mock_env(), mock_info("creator", &[]), msg)
+            .expect("contract successfully handles CommitPromoNames message");
+
+        let claim = |secret: &str| ExecuteMsg::ClaimPromoName {
+            name: "woof".to_string(),
+            secret: secret.to_string(),
+            bio: "".to_string(),
+            website: "".to_string(),
+        };
+        match execute(deps.as_mut(), mock_env(), mock_info("alice_key", &[]), claim("wrong")) {
+            Ok(_) => panic!("Must return error"),
+            Err(ContractError::InvalidPromoSecret {}) => {}
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        }
+
+        // no fee is due for a promo claim
+        let _res = execute(deps.as_mut(), mock_env(), mock_info("alice_key", &[]), claim("bark-at-the-moon"))
+            .expect("contract successfully handles ClaimPromoName message");
+        assert_name_owner(deps.as_ref(), "woof", "alice_key");
+
+        // a commitment can be used once
+        match execute(deps.as_mut(), mock_env(), mock_info("bob_key", &[]), claim("bark-at-the-moon")) {
+            Ok(_) => panic!("Must return error"),
+            Err(ContractError::InvalidPromoSecret {}) => {}
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        }
+    }
+
+    fn query_donations(deps: Deps, address: &str) -> DonationsResponse {
+        let res = query(
+            deps,
+            mock_env(),
+            QueryMsg::Donations {
+                address: Some(address.to_string()),
+            },
+        )
+        .unwrap();
+        from_binary(&res).unwrap()
+    }
+
+    #[test]
+    fn round_up_donates_overpayment() {
+        let mut deps = mock_dependencies();
+        mock_init_with_price(deps.as_mut(), coin(2, "token"), coin(2, "token"));
+
+        let msg = ExecuteMsg::Register {
+            name: "alice".to_string(),
+            bio: "".to_string(),
+            website: "".to_string(),
+            round_up: true,
+            referrer: None,
+        };
+        match execute(deps.as_mut(), mock_env(), mock_info("alice_key", &coins(5, "token")), msg.clone()) {
+            Ok(_) => panic!("Must return error"),
+            Err(ContractError::NoCommunityFund {}) => {}
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        }
+
+        let set_fund = ExecuteMsg::SetCommunityFund {
+            address: Some("fund_key".to_string()),
+        };
+        match execute(deps.as_mut(), mock_env(), mock_info("alice_key", &[]), set_fund.clone()) {
+            Ok(_) => panic!("Must return error"),
+            Err(ContractError::Unauthorized {}) => {}
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        }
+        let _res = execute(deps.as_mut(), mock_env(), mock_info("creator", &[]), set_fund)
+            .expect("contract successfully handles SetCommunityFund message");
+
+        let res = execute(deps.as_mut(), mock_env(), mock_info("alice_key", &coins(5, "token")), msg)
+            .expect("contract successfully handles Register message");
+        assert_eq!(
+            res.messages[0].msg,
+            BankMsg::Send {
+                to_address: "fund_key".to_string(),
+                amount: coins(3, "token"),
+            }
+            .into()
+        );
+        assert_eq!(query_owner_stats(deps.as_ref(), "alice_key").total_spent, coins(2, "token"));
+
+        // an exact payment leaves nothing to donate
+        let msg = ExecuteMsg::Transfer {
+            name: "alice".to_string(),
+            to: "bob_key".to_string(),
+            round_up: true,
+            memo: None,
+        };
+        let res = execute(deps.as_mut(), mock_env(), mock_info("alice_key", &coins(2, "token")), msg)
+            .expect("contract successfully handles Transfer message");
+        assert!(res.messages.is_empty());
+
+        let donations = query_donations(deps.as_ref(), "alice_key");
+        assert_eq!(donations.community_fund, Some(Addr::unchecked("fund_key")));
+        assert_eq!(donations.total, coins(3, "token"));
+        assert_eq!(donations.donated, Some(coins(3, "token")));
+        assert_eq!(query_donations(deps.as_ref(), "bob_key").donated, Some(vec![]));
+    }
+
+    fn mock_delegation(delegator: &str, validator: &str, amount: u128) -> FullDelegation {
+        FullDelegation {
+            delegator: Addr::unchecked(delegator),
+            validator: validator.to_string(),
+            amount: coin(amount, "uhuahua"),
+            can_redelegate: coin(amount, "uhuahua"),
+            accumulated_rewards: vec![],
+        }
+    }
+
+    #[test]
+    fn free_claims_require_min_delegation() {
+        let mut deps = mock_dependencies();
+        mock_init_no_price(deps.as_mut());
+        deps.querier.update_staking(
+            "uhuahua",
+            &[],
+            &[
+                mock_delegation("alice_key", "validator_one", 60),
+                mock_delegation("alice_key", "validator_two", 40),
+                mock_delegation("bob_key", "validator_one", 10),
+            ],
+        );
+
+        let msg = ExecuteMsg::SetMinDelegation {
+            min_delegation: Some(coin(100, "uhuahua")),
+        };
+        let _res = execute(deps.as_mut(), mock_env(), mock_info("creator", &[]), msg)
+            .expect("contract successfully handles SetMinDelegation message");
+
+        let hashes = vec![
+            HexBinary::from(Sha256::digest(b"woof:one").to_vec()),
+            HexBinary::from(Sha256::digest(b"bark:two").to_vec()),
+        ];
+        let _res = execute(deps.as_mut(), mock_env(), mock_info("creator", &[]), ExecuteMsg::CommitPromoNames { hashes })
+            .expect("contract successfully handles CommitPromoNames message");
+
+        let msg = ExecuteMsg::ClaimPromoName {
+            name: "bark".to_string(),
+            secret: "two".to_string(),
+            bio: "".to_string(),
+            website: "".to_string(),
+        };
+        match execute(deps.as_mut(), mock_env(), mock_info("bob_key", &[]), msg) {
+            Ok(_) => panic!("Must return error"),
+            Err(ContractError::InsufficientDelegation { min_delegation }) => {
+                assert_eq!(min_delegation, coin(100, "uhuahua"))
+            }
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        }
+
+        // delegations to every validator count towards the minimum
+        let msg = ExecuteMsg::ClaimPromoName {
+            name: "woof".to_string(),
+            secret: "one".to_string(),
+            bio: "".to_string(),
+            website: "".to_string(),
+        };
+        let _res = execute(deps.as_mut(), mock_env(), mock_info("alice_key", &[]), msg)
+            .expect("contract successfully handles ClaimPromoName message");
+        assert_name_owner(deps.as_ref(), "woof", "alice_key");
+    }
+
+    fn mock_env_at(seconds: u64) -> Env {
+        let mut env = mock_env();
+        env.block.time = env.block.time.plus_seconds(seconds);
+        env
+    }
+
+    fn query_record_at(deps: Deps, name: &str, seconds: u64) -> ResolveRecordResponse {
+        let res = query(deps, mock_env_at(seconds), QueryMsg::ResolveRecord { name: name.to_string() }).unwrap();
+        from_binary(&res).unwrap()
+    }
+
+    #[test]
+    fn names_expire_and_renew() {
+        let mut deps = mock_dependencies();
+        let msg = InstantiateMsg {
+            admin: None,
+            purchase_price: vec![],
+            transfer_price: vec![],
+            edit_price: vec![],
+            max_supply: None,
+            registration_duration: Some(100),
+            grace_period: Some(50),
+            renewal_price: Some(coin(2, "token")),
+            preset: None,
+            launch_schedule: vec![],
+            burn_fees: false,
+        };
+        let _res = instantiate(deps.as_mut(), mock_env(), mock_info("creator", &[]), msg)
+            .expect("contract successfully handles InstantiateMsg");
+        mock_alice_registers_name(deps.as_mut(), &[]);
+
+        let expires_at = mock_env_at(100).block.time;
+        assert_eq!(query_record_at(deps.as_ref(), "alice", 99).expires_at, Some(expires_at));
+        assert_eq!(query_record_at(deps.as_ref(), "alice", 99).address, Some("alice_key".to_string()));
+
+        // expired names stop resolving and can only be renewed
+        let resp = query_record_at(deps.as_ref(), "alice", 100);
+        assert_eq!(resp.address, None);
+        assert_eq!(resp.expires_at, Some(expires_at));
+        assert_eq!(query_owner_stats(deps.as_ref(), "alice_key").nearest_expiry, Some(expires_at));
+        let msg = QueryMsg::OwnerStats { address: "alice_key".to_string() };
+        let stats: OwnerStatsResponse = from_binary(&query(deps.as_ref(), mock_env_at(100), msg).unwrap()).unwrap();
+        assert_eq!((stats.name_count, stats.nearest_expiry), (1, None));
+        for msg in [
+            QueryMsg::AllNames { start_after: None, limit: None },
+            QueryMsg::NamesWithPrefix { prefix: "ali".to_string(), start_after: None, limit: None },
+        ] {
+            let names = |seconds| {
+                let res = query(deps.as_ref(), mock_env_at(seconds), msg.clone()).unwrap();
+                from_binary::<AllNamesResponse>(&res).unwrap().names
+            };
+            assert_eq!(names(99).len(), 1);
+            assert!(names(100).is_empty());
+        }
+        let msg = QueryMsg::DnsZone { name: "alice".to_string(), tld: "huahua".to_string() };
+        let zone: DnsZoneResponse = from_binary(&query(deps.as_ref(), mock_env_at(100), msg).unwrap()).unwrap();
+        assert!(zone.records.is_empty());
+        let msg = ExecuteMsg::Transfer {
+            name: "alice".to_string(),
+            to: "bob_key".to_string(),
+            round_up: false,
+            memo: None,
+        };
+        match execute(deps.as_mut(), mock_env_at(100), mock_info("alice_key", &[]), msg) {
+            Ok(_) => panic!("Must return error"),
+            Err(ContractError::NameExpired { .. }) => {}
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        }
+
+        // the grace period keeps the name reserved for its owner
+        let register = ExecuteMsg::Register {
+            name: "alice".to_string(),
+            bio: "".to_string(),
+            website: "".to_string(),
+            round_up: false,
+            referrer: None,
+        };
+        match execute(deps.as_mut(), mock_env_at(120), mock_info("bob_key", &[]), register.clone()) {
+            Ok(_) => panic!("Must return error"),
+            Err(ContractError::NameTaken { .. }) => {}
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        }
+
+        let renew = ExecuteMsg::Renew { name: "alice".to_string() };
+        match execute(deps.as_mut(), mock_env_at(120), mock_info("alice_key", &[]), renew.clone()) {
+            Ok(_) => panic!("Must return error"),
+            Err(ContractError::InsufficientFundsSend {}) => {}
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        }
+        let _res = execute(deps.as_mut(), mock_env_at(120), mock_info("alice_key", &coins(2, "token")), renew.clone())
+            .expect("contract successfully handles Renew message");
+        assert_eq!(query_record_at(deps.as_ref(), "alice", 120).expires_at, Some(mock_env_at(200).block.time));
+
+        // once the grace period is over anyone can take the name
+        match execute(deps.as_mut(), mock_env_at(250), mock_info("alice_key", &coins(2, "token")), renew) {
+            Ok(_) => panic!("Must return error"),
+            Err(ContractError::NameExpired { .. }) => {}
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        }
+        let _res = execute(deps.as_mut(), mock_env_at(250), mock_info("bob_key", &[]), register)
+            .expect("contract successfully handles Register message");
+        assert_eq!(query_record_at(deps.as_ref(), "alice", 250).address, Some("bob_key".to_string()));
+        assert_eq!(query_owner_stats(deps.as_ref(), "alice_key").name_count, 0);
+        assert_eq!(query_supply(deps.as_ref()).total, 1);
+    }
+
+    #[test]
+    fn webhook_hears_about_its_name() {
+        let mut deps = mock_dependencies();
+        mock_init_no_price(deps.as_mut());
+        mock_alice_registers_name(deps.as_mut(), &[]);
+
+        let msg = ExecuteMsg::SetWebhook {
+            name: "alice".to_string(),
+            contract: Some("custodian_contract".to_string()),
+            payload: Some(Binary::from(b"account-7".to_vec())),
+        };
+        match execute(deps.as_mut(), mock_env(), mock_info("bob_key", &[]), msg.clone()) {
+            Ok(_) => panic!("Must return error"),
+            Err(ContractError::Unauthorized {}) => {}
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        }
+        let _res = execute(deps.as_mut(), mock_env(), mock_info("alice_key", &[]), msg)
+            .expect("contract successfully handles SetWebhook message");
+
+        let msg = ExecuteMsg::Edit {
+            name: "alice".to_string(),
+            bio: "custodied".to_string(),
+            website: "".to_string(),
+            round_up: false,
+        };
+        let res = execute(deps.as_mut(), mock_env(), mock_info("alice_key", &[]), msg)
+            .expect("contract successfully handles Edit message");
+        let hook = NameHookMsg::NameHook {
+            name: "alice".to_string(),
+            event: NameEvent::Edited {},
+            payload: Binary::from(b"account-7".to_vec()),
+        };
+        assert_eq!(res.messages.len(), 1);
+        assert_eq!(res.messages[0].reply_on, ReplyOn::Error);
+        assert_eq!(
+            res.messages[0].msg,
+            WasmMsg::Execute {
+                contract_addr: "custodian_contract".to_string(),
+                msg: to_binary(&hook).unwrap(),
+                funds: vec![],
+            }
+            .into()
+        );
+
+        // a failing callback is swallowed
+        let failure = Reply {
+            id: res.messages[0].id,
+            result: SubMsgResult::Err("out of gas".to_string()),
+        };
+        let _res = reply(deps.as_mut(), mock_env(), failure).expect("contract ignores failing webhooks");
+        let success = Reply {
+            id: res.messages[0].id,
+            result: SubMsgResult::Ok(SubMsgResponse { events: vec![], data: None }),
+        };
+        let ignored = reply(deps.as_mut(), mock_env(), success).expect("contract ignores successful webhooks");
+        assert!(ignored.attributes.is_empty());
+
+        // the transfer is the last event the webhook hears about
+        let msg = ExecuteMsg::Transfer {
+            name: "alice".to_string(),
+            to: "bob_key".to_string(),
+            round_up: false,
+            memo: None,
+        };
+        let res = execute(deps.as_mut(), mock_env(), mock_info("alice_key", &[]), msg)
+            .expect("contract successfully handles Transfer message");
+        assert_eq!(res.messages.len(), 1);
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::Webhook { name: "alice".to_string() }).unwrap();
+        let webhook: WebhookResponse = from_binary(&res).unwrap();
+        assert_eq!(webhook.contract, None);
+    }
+
+    #[test]
+    fn webhook_warned_once_before_expiry() {
+        let mut deps = mock_dependencies();
+        let msg = InstantiateMsg {
+            admin: None,
+            purchase_price: vec![],
+            transfer_price: vec![],
+            edit_price: vec![],
+            max_supply: None,
+            registration_duration: Some(365 * 24 * 3600),
+            grace_period: None,
+            renewal_price: None,
+            preset: None,
+            launch_schedule: vec![],
+            burn_fees: false,
+        };
+        let _res = instantiate(deps.as_mut(), mock_env(), mock_info("creator", &[]), msg)
+            .expect("contract successfully handles InstantiateMsg");
+        mock_alice_registers_name(deps.as_mut(), &[]);
+        let msg = ExecuteMsg::SetWebhook {
+            name: "alice".to_string(),
+            contract: Some("custodian_contract".to_string()),
+            payload: None,
+        };
+        let _res = execute(deps.as_mut(), mock_env(), mock_info("alice_key", &[]), msg)
+            .expect("contract successfully handles SetWebhook message");
+
+        let notify = ExecuteMsg::NotifyExpiring { name: "alice".to_string() };
+        match execute(deps.as_mut(), mock_env(), mock_info("keeper_key", &[]), notify.clone()) {
+            Ok(_) => panic!("Must return error"),
+            Err(ContractError::NotExpiringSoon { .. }) => {}
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        }
+
+        let soon = mock_env_at(340 * 24 * 3600);
+        let res = execute(deps.as_mut(), soon.clone(), mock_info("keeper_key", &[]), notify.clone())
+            .expect("contract successfully handles NotifyExpiring message");
+        assert_eq!(res.messages.len(), 1);
+        match execute(deps.as_mut(), soon, mock_info("keeper_key", &[]), notify) {
+            Ok(_) => panic!("Must return error"),
+            Err(ContractError::NotExpiringSoon { .. }) => {}
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        }
+    }
+
+    #[cw_serde]
+    struct LegacyNameRecord {
+        owner: Addr,
+        bio: String,
+        website: String,
+    }
+
+    #[test]
+    fn migration_plan_reports_without_migrating() {
+        let mut deps = mock_dependencies();
+        mock_init_no_price(deps.as_mut());
+        for name in ["alice", "carol", "dave"] {
+            let msg = ExecuteMsg::Register {
+                name: name.to_string(),
+                bio: "".to_string(),
+                website: "".to_string(),
+                round_up: false,
+                referrer: None,
+            };
+            let _res = execute(deps.as_mut(), mock_env(), mock_info("alice_key", &[]), msg)
+                .expect("contract successfully handles Register message");
+        }
+        // a record written by the very first release
+        let legacy: Map<&[u8], LegacyNameRecord> = Map::new("name_resolver");
+        let record = LegacyNameRecord {
+            owner: Addr::unchecked("bob_key"),
+            bio: "".to_string(),
+            website: "".to_string(),
+        };
+        legacy.save(deps.as_mut().storage, b"bob", &record).unwrap();
+        TOTAL_NAMES.remove(deps.as_mut().storage);
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::MigrationPlan { batch_size: Some(2) }).unwrap();
+        let plan: MigrationPlanResponse = from_binary(&res).unwrap();
+        assert_eq!(
+            plan,
+            MigrationPlanResponse {
+                resumes_pending: false,
+                records_to_rewrite: 4,
+                records_missing_fields: 1,
+                backfill_counters: true,
+                passes: 3,
+            }
+        );
+        assert!(TOTAL_NAMES.may_load(deps.as_ref().storage).unwrap().is_none());
+
+        // a pending migration is planned from where it stopped
+        let _res = migrate(deps.as_mut(), mock_env(), MigrateMsg { batch_size: Some(2) })
+            .expect("contract successfully handles MigrateMsg");
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::MigrationPlan { batch_size: Some(2) }).unwrap();
+        let plan: MigrationPlanResponse = from_binary(&res).unwrap();
+        assert!(plan.resumes_pending);
+        assert_eq!(plan.records_to_rewrite, 2);
+        assert_eq!(plan.records_missing_fields, 0);
+        assert_eq!(plan.passes, 2);
+    }
+
+    fn query_names_of_owner(deps: Deps, owner: &str, start_after: Option<&str>) -> Vec<String> {
+        let msg = QueryMsg::NamesOfOwner {
+            owner: owner.to_string(),
+            start_after: start_after.map(|name| name.to_string()),
+            limit: Some(2),
+        };
+        let res: NamesResponse = from_binary(&query(deps, mock_env(), msg).unwrap()).unwrap();
+        res.names
+    }
+
+    #[test]
+    fn names_of_owner_follow_transfers() {
+        let mut deps = mock_dependencies();
+        mock_init_no_price(deps.as_mut());
+        for name in ["dave", "alice", "carol"] {
+            let msg = ExecuteMsg::Register {
+                name: name.to_string(),
+                bio: "".to_string(),
+                website: "".to_string(),
+                round_up: false,
+                referrer: None,
+            };
+            let _res = execute(deps.as_mut(), mock_env(), mock_info("alice_key", &[]), msg)
+                .expect("contract successfully handles Register message");
+        }
+
+        assert_eq!(query_names_of_owner(deps.as_ref(), "alice_key", None), vec!["alice", "carol"]);
+        assert_eq!(query_names_of_owner(deps.as_ref(), "alice_key", Some("carol")), vec!["dave"]);
+
+        let msg = ExecuteMsg::Transfer {
+            name: "carol".to_string(),
+            to: "bob_key".to_string(),
+            round_up: false,
+            memo: None,
+        };
+        let _res = execute(deps.as_mut(), mock_env(), mock_info("alice_key", &[]), msg)
+            .expect("contract successfully handles Transfer message");
+
+        assert_eq!(query_names_of_owner(deps.as_ref(), "alice_key", None), vec!["alice", "dave"]);
+        assert_eq!(query_names_of_owner(deps.as_ref(), "bob_key", None), vec!["carol"]);
+    }
+
+    #[test]
+    fn all_names_paginates_the_registry() {
+        let mut deps = mock_dependencies();
+        mock_init_no_price(deps.as_mut());
+        for (name, owner) in [("dave", "alice_key"), ("alice", "alice_key"), ("carol", "bob_key")] {
+            let msg = ExecuteMsg::Register {
+                name: name.to_string(),
+                bio: "".to_string(),
+                website: "".to_string(),
+                round_up: false,
+                referrer: None,
+            };
+            let _res = execute(deps.as_mut(), mock_env(), mock_info(owner, &[]), msg)
+                .expect("contract successfully handles Register message");
+        }
+
+        let msg = QueryMsg::AllNames { start_after: None, limit: Some(2) };
+        let res: AllNamesResponse = from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+        let names: Vec<_> = res.names.iter().map(|info| (info.name.as_str(), info.owner.as_str())).collect();
+        assert_eq!(names, vec![("alice", "alice_key"), ("carol", "bob_key")]);
+
+        let msg = QueryMsg::AllNames {
+            start_after: Some("carol".to_string()),
+            limit: Some(2),
+        };
+        let res: AllNamesResponse = from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+        assert_eq!(res.names.len(), 1);
+        assert_eq!(res.names[0].name, "dave");
+    }
+
+    #[test]
+    fn edit_price_follows_bytes_written() {
+        let mut deps = mock_dependencies();
+        mock_init_with_price(deps.as_mut(), coin(2, "token"), coin(2, "token"));
+        mock_alice_registers_name(deps.as_mut(), &coins(2, "token"));
+
+        let msg = ExecuteMsg::SetEditBytePrice {
+            edit_byte_price: Some(coin(2, "token")),
+        };
+        let _res = execute(deps.as_mut(), mock_env(), mock_info("creator", &[]), msg)
+            .expect("contract successfully handles SetEditBytePrice message");
+
+        let msg = ExecuteMsg::Edit {
+            name: "alice".to_string(),
+            bio: "hello".to_string(),
+            website: "".to_string(),
+            round_up: false,
+        };
+        match execute(deps.as_mut(), mock_env(), mock_info("alice_key", &coins(9, "token")), msg.clone()) {
+            Ok(_) => panic!("Must return error"),
+            Err(ContractError::InsufficientFundsSend {}) => {}
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        }
+        let _res = execute(deps.as_mut(), mock_env(), mock_info("alice_key", &coins(10, "token")), msg)
+            .expect("contract successfully handles Edit message");
+
+        // the flat part has to be paid in the same denom
+        let msg = ExecuteMsg::Editconf {
+            purchase_price: vec![],
+            transfer_price: vec![],
+            edit_price: vec![coin(1, "earth")],
+            length_prices: None,
+            name_policy: None,
+        };
+        match execute(deps.as_mut(), mock_env(), mock_info("creator", &coins(2, "token")), msg) {
+            Ok(_) => panic!("Must return error"),
+            Err(ContractError::EditPriceDenomMismatch {}) => {}
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        }
+    }
+
+    #[test]
+    fn short_names_cost_more() {
+        let mut deps = mock_dependencies();
+        mock_init_with_price(deps.as_mut(), coin(2, "token"), coin(2, "token"));
+
+        let tiers = vec![
+            PriceTier { max_length: 3, price: coin(20, "token") },
+            PriceTier { max_length: 4, price: coin(10, "token") },
+        ];
+        let msg = ExecuteMsg::Editconf {
+            purchase_price: vec![coin(2, "token")],
+            transfer_price: vec![coin(2, "token")],
+            edit_price: vec![],
+            length_prices: Some(tiers.clone()),
+            name_policy: None,
+        };
+        let _res = execute(deps.as_mut(), mock_env(), mock_info("creator", &coins(2, "token")), msg)
+            .expect("contract successfully handles Editconf message");
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap();
+        let config: ConfigResponse = from_binary(&res).unwrap();
+        assert_eq!(config.length_prices, tiers);
+
+        let register = |name: &str| ExecuteMsg::Register {
+            name: name.to_string(),
+            bio: "".to_string(),
+            website: "".to_string(),
+            round_up: false,
+            referrer: None,
+        };
+        match execute(deps.as_mut(), mock_env(), mock_info("alice_key", &coins(10, "token")), register("abc")) {
+            Ok(_) => panic!("Must return error"),
+            Err(ContractError::InsufficientFundsSend {}) => {}
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        }
+        let _res = execute(deps.as_mut(), mock_env(), mock_info("alice_key", &coins(20, "token")), register("abc"))
+            .expect("contract successfully handles Register message");
+        let _res = execute(deps.as_mut(), mock_env(), mock_info("alice_key", &coins(10, "token")), register("abcd"))
+            .expect("contract successfully handles Register message");
+        let _res = execute(deps.as_mut(), mock_env(), mock_info("alice_key", &coins(2, "token")), register("abcde"))
+            .expect("contract successfully handles Register message");
+    }
+
+    fn query_transfer_memos(deps: Deps, address: &str) -> TransferMemosResponse {
+        let msg = QueryMsg::TransferMemos {
+            address: address.to_string(),
+            start_after: None,
+            limit: None,
+        };
+        from_binary(&query(deps, mock_env(), msg).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn transfer_memo_shown_until_recipient_acts() {
+        let mut deps = mock_dependencies();
+        mock_init_no_price(deps.as_mut());
+        mock_alice_registers_name(deps.as_mut(), &[]);
+
+        let msg = ExecuteMsg::Transfer {
+            name: "alice".to_string(),
+            to: "bob_key".to_string(),
+            round_up: false,
+            memo: Some("happy birthday bob".to_string()),
+        };
+        let res = execute(deps.as_mut(), mock_env(), mock_info("alice_key", &[]), msg)
+            .expect("contract successfully handles Transfer message");
+        assert!(res.attributes.iter().any(|attr| attr.key == "memo" && attr.value == "happy birthday bob"));
+
+        let memos = query_transfer_memos(deps.as_ref(), "bob_key").memos;
+        assert_eq!(memos.len(), 1);
+        assert_eq!(memos[0].name, "alice");
+        assert_eq!(memos[0].from, Addr::unchecked("alice_key"));
+
+        // any execute of the recipient clears its memos
+        let msg = ExecuteMsg::Edit {
+            name: "alice".to_string(),
+            bio: "thanks".to_string(),
+            website: "".to_string(),
+            round_up: false,
+        };
+        let _res = execute(deps.as_mut(), mock_env(), mock_info("bob_key", &[]), msg)
+            .expect("contract successfully handles Edit message");
+        assert!(query_transfer_memos(deps.as_ref(), "bob_key").memos.is_empty());
+    }
+
+    #[test]
+    fn executes_clear_a_bounded_number_of_memos() {
+        let mut deps = mock_dependencies();
+        mock_init_no_price(deps.as_mut());
+        for letter in 'a'..='l' {
+            let name = format!("{}name", letter);
+            let msg = ExecuteMsg::Register {
+                name: name.clone(),
+                bio: "".to_string(),
+                website: "".to_string(),
+                round_up: false,
+                referrer: None,
+            };
+            execute(deps.as_mut(), mock_env(), mock_info("alice_key", &[]), msg)
+                .expect("contract successfully handles Register message");
+            let memo = Some("hi".to_string());
+            let msg = ExecuteMsg::Transfer { name, to: "bob_key".to_string(), round_up: false, memo };
+            execute(deps.as_mut(), mock_env(), mock_info("alice_key", &[]), msg)
+                .expect("contract successfully handles Transfer message");
+        }
+
+        // an execute clears ten memos, the next one the rest
+        let edit = ExecuteMsg::Edit {
+            name: "aname".to_string(),
+            bio: "thanks".to_string(),
+            website: "".to_string(),
+            round_up: false,
+        };
+        execute(deps.as_mut(), mock_env(), mock_info("bob_key", &[]), edit.clone())
+            .expect("contract successfully handles Edit message");
+        let memos = query_transfer_memos(deps.as_ref(), "bob_key").memos;
+        let names: Vec<_> = memos.into_iter().map(|memo| memo.name).collect();
+        assert_eq!(names, vec!["kname".to_string(), "lname".to_string()]);
+        execute(deps.as_mut(), mock_env(), mock_info("bob_key", &[]), edit)
+            .expect("contract successfully handles Edit message");
+        assert!(query_transfer_memos(deps.as_ref(), "bob_key").memos.is_empty());
+    }
+
+    #[test]
+    fn check_availability_in_bulk() {
+        let mut deps = mock_dependencies();
+        mock_init_no_price(deps.as_mut());
+        mock_alice_registers_name(deps.as_mut(), &[]);
+
+        let names = vec!["alice".to_string(), "bob".to_string(), "Bob".to_string()];
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::CheckAvailability { names }).unwrap();
+        let res: AvailabilityResponse = from_binary(&res).unwrap();
+        let statuses: Vec<_> = res.names.into_iter().map(|availability| availability.status).collect();
+        assert_eq!(
+            statuses,
+            vec![AvailabilityStatus::Taken, AvailabilityStatus::Available, AvailabilityStatus::Invalid]
+        );
+
+        let names = (0..51).map(|i| format!("name{}", i)).collect();
+        assert!(query(deps.as_ref(), mock_env(), QueryMsg::CheckAvailability { names }).is_err());
+    }
+
+    #[test]
+    fn released_names_can_be_registered_again() {
+        let mut deps = mock_dependencies();
+        mock_init_no_price(deps.as_mut());
+        mock_alice_registers_name(deps.as_mut(), &[]);
+
+        let release = ExecuteMsg::Release { name: "alice".to_string() };
+        match execute(deps.as_mut(), mock_env(), mock_info("bob_key", &[]), release.clone()) {
+            Ok(_) => panic!("Must return error"),
+            Err(ContractError::Unauthorized {}) => {}
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        }
+        let res = execute(deps.as_mut(), mock_env(), mock_info("alice_key", &[]), release)
+            .expect("contract successfully handles Release message");
+        assert!(res.attributes.iter().any(|attr| attr.key == "action" && attr.value == "release"));
+
+        assert_eq!(query_supply(deps.as_ref()).total, 0);
+        assert_eq!(query_owner_stats(deps.as_ref(), "alice_key").name_count, 0);
+        assert!(query_names_of_owner(deps.as_ref(), "alice_key", None).is_empty());
+
+        let msg = ExecuteMsg::Register {
+            name: "alice".to_string(),
+            bio: "".to_string(),
+            website: "".to_string(),
+            round_up: false,
+            referrer: None,
+        };
+        let _res = execute(deps.as_mut(), mock_env(), mock_info("bob_key", &[]), msg)
+            .expect("contract successfully handles Register message");
+        assert_name_owner(deps.as_ref(), "alice", "bob_key");
+
+        // the admin can take any name down
+        let force = ExecuteMsg::ForceRelease { name: "alice".to_string() };
+        match execute(deps.as_mut(), mock_env(), mock_info("alice_key", &[]), force.clone()) {
+            Ok(_) => panic!("Must return error"),
+            Err(ContractError::Unauthorized {}) => {}
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        }
+        let _res = execute(deps.as_mut(), mock_env(), mock_info("creator", &[]), force)
+            .expect("contract successfully handles ForceRelease message");
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::ResolveRecord { name: "alice".to_string() }).unwrap();
+        let value: ResolveRecordResponse = from_binary(&res).unwrap();
+        assert_eq!(value.address, None);
+    }
+
+    #[test]
+    fn watchers_are_refunded_when_name_is_released() {
+        let mut deps = mock_dependencies();
+        mock_init_no_price(deps.as_mut());
+        mock_alice_registers_name(deps.as_mut(), &[]);
+
+        let msg = ExecuteMsg::SetWatchDeposit { watch_deposit: Some(coin(5, "token")) };
+        let _res = execute(deps.as_mut(), mock_env(), mock_info("creator", &[]), msg)
+            .expect("contract successfully handles SetWatchDeposit message");
+
+        let watch = ExecuteMsg::WatchName { name: "alice".to_string() };
+        match execute(deps.as_mut(), mock_env(), mock_info("bob_key", &coins(2, "token")), watch.clone()) {
+            Ok(_) => panic!("Must return error"),
+            Err(ContractError::InsufficientFundsSend {}) => {}
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        }
+        let _res = execute(deps.as_mut(), mock_env(), mock_info("bob_key", &coins(5, "token")), watch)
+            .expect("contract successfully handles WatchName message");
+
+        let res = query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Watchers { name: "alice".to_string(), start_after: None, limit: None },
+        )
+        .unwrap();
+        let value: WatchersResponse = from_binary(&res).unwrap();
+        assert_eq!(value.watchers, vec![Addr::unchecked("bob_key")]);
+
+        // the name is still held
+        let notify = ExecuteMsg::NotifyWatchers { name: "alice".to_string() };
+        match execute(deps.as_mut(), mock_env(), mock_info("anyone", &[]), notify) {
+            Ok(_) => panic!("Must return error"),
+            Err(ContractError::NameTaken { .. }) => {}
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        }
+
+        let msg = ExecuteMsg::Release { name: "alice".to_string() };
+        let res = execute(deps.as_mut(), mock_env(), mock_info("alice_key", &[]), msg)
+            .expect("contract successfully handles Release message");
+        assert_eq!(res.events.len(), 1);
+        assert_eq!(res.events[0].ty, "name_available");
+        assert!(res.events[0].attributes.iter().any(|attr| attr.key == "watcher" && attr.value == "bob_key"));
+        assert_eq!(res.messages.len(), 1);
+        assert_eq!(
+            res.messages[0].msg,
+            BankMsg::Send { to_address: "bob_key".to_string(), amount: coins(5, "token") }.into(),
+        );
+
+        let msg = ExecuteMsg::UnwatchName { name: "alice".to_string() };
+        match execute(deps.as_mut(), mock_env(), mock_info("bob_key", &[]), msg) {
+            Ok(_) => panic!("Must return error"),
+            Err(ContractError::NotWatching { .. }) => {}
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        }
+    }
+
+    fn query_primary_name(deps: Deps, address: &str) -> Option<String> {
+        let res = query(deps, mock_env(), QueryMsg::PrimaryName { address: address.to_string() }).unwrap();
+        let value: PrimaryNameResponse = from_binary(&res).unwrap();
+        value.name
+    }
+
+    #[test]
+    fn primary_name_follows_ownership() {
+        let mut deps = mock_dependencies();
+        mock_init_no_price(deps.as_mut());
+        mock_alice_registers_name(deps.as_mut(), &[]);
+        assert_eq!(query_primary_name(deps.as_ref(), "alice_key"), None);
+
+        let msg = ExecuteMsg::SetPrimaryName { name: "alice".to_string() };
+        match execute(deps.as_mut(), mock_env(), mock_info("bob_key", &[]), msg.clone()) {
+            Ok(_) => panic!("Must return error"),
+            Err(ContractError::Unauthorized {}) => {}
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        }
+        let _res = execute(deps.as_mut(), mock_env(), mock_info("alice_key", &[]), msg)
+            .expect("contract successfully handles SetPrimaryName message");
+        assert_eq!(query_primary_name(deps.as_ref(), "alice_key"), Some("alice".to_string()));
+
+        // giving the name away also gives away the reverse record
+        let msg = ExecuteMsg::Transfer {
+            name: "alice".to_string(),
+            to: "bob_key".to_string(),
+            round_up: false,
+            memo: None,
+        };
+        let _res = execute(deps.as_mut(), mock_env(), mock_info("alice_key", &[]), msg)
+            .expect("contract successfully handles Transfer message");
+        assert_eq!(query_primary_name(deps.as_ref(), "alice_key"), None);
+        assert_eq!(query_primary_name(deps.as_ref(), "bob_key"), None);
+    }
+
+    #[test]
+    fn text_records_are_listed_by_key() {
+        let mut deps = mock_dependencies();
+        mock_init_no_price(deps.as_mut());
+        mock_alice_registers_name(deps.as_mut(), &[]);
+
+        for (key, value) in [("twitter", "@alice"), ("email", "alice@example.com")] {
+            let msg = ExecuteMsg::SetTextRecord {
+                name: "alice".to_string(),
+                key: key.to_string(),
+                value: value.to_string(),
+            };
+            let _res = execute(deps.as_mut(), mock_env(), mock_info("alice_key", &[]), msg)
+                .expect("contract successfully handles SetTextRecord message");
+        }
+
+        let msg = ExecuteMsg::SetTextRecord {
+            name: "alice".to_string(),
+            key: "github".to_string(),
+            value: "alice".to_string(),
+        };
+        match execute(deps.as_mut(), mock_env(), mock_info("bob_key", &[]), msg) {
+            Ok(_) => panic!("Must return error"),
+            Err(ContractError::Unauthorized {}) => {}
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        }
+
+        let msg = ExecuteMsg::SetTextRecord {
+            name: "alice".to_string(),
+            key: "bio".to_string(),
+            value: "a".repeat(201),
+        };
+        match execute(deps.as_mut(), mock_env(), mock_info("alice_key", &[]), msg) {
+            Ok(_) => panic!("Must return error"),
+            Err(ContractError::TextValueTooLong { .. }) => {}
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        }
+
+        let msg = QueryMsg::TextRecords { name: "alice".to_string(), start_after: None, limit: None };
+        let value: TextRecordsResponse = from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+        let keys: Vec<_> = value.records.iter().map(|record| record.key.as_str()).collect();
+        assert_eq!(keys, vec!["email", "twitter"]);
+
+        let msg = ExecuteMsg::RemoveTextRecord { name: "alice".to_string(), key: "email".to_string() };
+        let _res = execute(deps.as_mut(), mock_env(), mock_info("alice_key", &[]), msg.clone())
+            .expect("contract successfully handles RemoveTextRecord message");
+        match execute(deps.as_mut(), mock_env(), mock_info("alice_key", &[]), msg) {
+            Ok(_) => panic!("Must return error"),
+            Err(ContractError::TextRecordNotExists { .. }) => {}
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        }
+
+        let msg = QueryMsg::TextRecords {
+            name: "alice".to_string(),
+            start_after: Some("email".to_string()),
+            limit: None,
+        };
+        let value: TextRecordsResponse = from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+        assert_eq!(value.records.len(), 1);
+        assert_eq!(value.records[0].value, "@alice");
+    }
+
+    #[test]
+    fn range_queries_fail_instead_of_truncating() {
+        let mut deps = mock_dependencies();
+        let msg = InstantiateMsg {
+            admin: None,
+            purchase_price: vec![],
+            transfer_price: vec![],
+            edit_price: vec![],
+            max_supply: None,
+            registration_duration: Some(100),
+            grace_period: None,
+            renewal_price: None,
+            preset: None,
+            launch_schedule: vec![],
+            burn_fees: false,
+        };
+        let _res = instantiate(deps.as_mut(), mock_env(), mock_info("creator", &[]), msg)
+            .expect("contract successfully handles InstantiateMsg");
+
+        let msg = QueryMsg::AllNames { start_after: None, limit: Some(31) };
+        match query(deps.as_ref(), mock_env(), msg) {
+            Ok(_) => panic!("Must return error"),
+            Err(e) => assert!(e.to_string().contains("Limit too large")),
+        }
+
+        let register = |deps: DepsMut, name: String, env: Env| {
+            let msg = ExecuteMsg::Register {
+                name,
+                bio: "".to_string(),
+                website: "".to_string(),
+                round_up: false,
+                referrer: None,
+            };
+            let _res = execute(deps, env, mock_info("alice_key", &[]), msg)
+                .expect("contract successfully handles Register message");
+        };
+        for i in 0..300 {
+            register(deps.as_mut(), format!("exp{:03}", i), mock_env());
+        }
+        register(deps.as_mut(), "zzz".to_string(), mock_env_at(200));
+
+        // every name before zzz has expired, the page cannot be filled within the scan budget
+        let names_of_owner = |start_after: Option<&str>| QueryMsg::NamesOfOwner {
+            owner: "alice_key".to_string(),
+            start_after: start_after.map(|name| name.to_string()),
+            limit: None,
+        };
+        match query(deps.as_ref(), mock_env_at(200), names_of_owner(None)) {
+            Ok(_) => panic!("Must return error"),
+            Err(e) => assert!(e.to_string().contains("continue with start_after exp299")),
+        }
+        let res = query(deps.as_ref(), mock_env_at(200), names_of_owner(Some("exp299"))).unwrap();
+        let value: NamesResponse = from_binary(&res).unwrap();
+        assert_eq!(value.names, vec!["zzz".to_string()]);
+    }
+
+    fn query_address(deps: Deps, name: &str, chain_id: &str) -> Option<String> {
+        let msg = QueryMsg::ResolveAddress { name: name.to_string(), chain_id: chain_id.to_string() };
+        let value: ResolveAddressResponse = from_binary(&query(deps, mock_env(), msg).unwrap()).unwrap();
+        value.address
+    }
+
+    #[test]
+    fn names_resolve_to_addresses_on_other_chains() {
+        let mut deps = mock_dependencies();
+        mock_init_no_price(deps.as_mut());
+        mock_alice_registers_name(deps.as_mut(), &[]);
+
+        // the owner is the address on this chain until another one is set
+        assert_eq!(query_address(deps.as_ref(), "alice", &mock_env().block.chain_id), Some("alice_key".to_string()));
+        assert_eq!(query_address(deps.as_ref(), "alice", "osmosis-1"), None);
+
+        let msg = ExecuteMsg::SetAddress {
+            name: "alice".to_string(),
+            chain_id: "osmosis-1".to_string(),
+            address: Some("osmo1alice".to_string()),
+        };
+        match execute(deps.as_mut(), mock_env(), mock_info("bob_key", &[]), msg.clone()) {
+            Ok(_) => panic!("Must return error"),
+            Err(ContractError::Unauthorized {}) => {}
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        }
+        let _res = execute(deps.as_mut(), mock_env(), mock_info("alice_key", &[]), msg)
+            .expect("contract successfully handles SetAddress message");
+        assert_eq!(query_address(deps.as_ref(), "alice", "osmosis-1"), Some("osmo1alice".to_string()));
+
+        let msg = ExecuteMsg::SetAddress {
+            name: "alice".to_string(),
+            chain_id: "Osmosis 1".to_string(),
+            address: Some("osmo1alice".to_string()),
+        };
+        match execute(deps.as_mut(), mock_env(), mock_info("alice_key", &[]), msg) {
+            Ok(_) => panic!("Must return error"),
+            Err(ContractError::InvalidChainAddress { .. }) => {}
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        }
+
+        // the new owner starts over
+        let msg = ExecuteMsg::Transfer {
+            name: "alice".to_string(),
+            to: "bob_key".to_string(),
+            round_up: false,
+            memo: None,
+        };
+        let _res = execute(deps.as_mut(), mock_env(), mock_info("alice_key", &[]), msg)
+            .expect("contract successfully handles Transfer message");
+        assert_eq!(query_address(deps.as_ref(), "alice", "osmosis-1"), None);
+    }
+
+    #[test]
+    fn governance_pauses_single_actions() {
+        let mut deps = mock_dependencies();
+        mock_init_no_price(deps.as_mut());
+        mock_alice_registers_name(deps.as_mut(), &[]);
+
+        let msg = SudoMsg::PauseActions { actions: vec!["transfer".to_string()] };
+        let _res = sudo(deps.as_mut(), mock_env(), msg).expect("contract successfully handles PauseActions message");
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::PausedActions {}).unwrap();
+        let value: PausedActionsResponse = from_binary(&res).unwrap();
+        assert_eq!(value.actions, vec!["transfer".to_string()]);
+        // misspelled actions would pause nothing
+        for action in ["transfer_nft ", "Register"] {
+            match sudo(deps.as_mut(), mock_env(), SudoMsg::PauseActions { actions: vec![action.to_string()] }) {
+                Ok(_) => panic!("Must return error"),
+                Err(ContractError::UnknownAction { action: unknown }) => assert_eq!(unknown, action),
+                Err(e) => panic!("Unexpected error: {:?}", e),
+            }
+        }
+
+        let transfer = ExecuteMsg::Transfer {
+            name: "alice".to_string(),
+            to: "bob_key".to_string(),
+            round_up: false,
+            memo: None,
+        };
+        match execute(deps.as_mut(), mock_env(), mock_info("alice_key", &[]), transfer.clone()) {
+            Ok(_) => panic!("Must return error"),
+            Err(ContractError::ActionPaused { action }) => assert_eq!(action, "transfer"),
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        }
+        // other actions keep working
+        let msg = ExecuteMsg::Register {
+            name: "bob".to_string(),
+            bio: "".to_string(),
+            website: "".to_string(),
+            round_up: false,
+            referrer: None,
+        };
+        let _res = execute(deps.as_mut(), mock_env(), mock_info("bob_key", &[]), msg)
+            .expect("contract successfully handles Register message");
+
+        let msg = SudoMsg::UnpauseActions { actions: vec!["transfer".to_string()] };
+        let _res = sudo(deps.as_mut(), mock_env(), msg).expect("contract successfully handles UnpauseActions message");
+        let _res = execute(deps.as_mut(), mock_env(), mock_info("alice_key", &[]), transfer)
+            .expect("contract successfully handles Transfer message");
+    }
+
+    #[test]
+    fn refund_leaves_escrowed_funds() {
+        let mut deps = mock_dependencies();
+        mock_init_no_price(deps.as_mut());
+
+        let info = mock_info("sponsor_key", &coins(10, "token"));
+        let _res = execute(deps.as_mut(), mock_env(), info, ExecuteMsg::FundSponsorship { open: true })
+            .expect("contract successfully handles FundSponsorship message");
+        // 5 token of fees were collected next to the sponsorship
+        deps.querier.update_balance(MOCK_CONTRACT_ADDR, coins(15, "token"));
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::Balances {}).unwrap();
+        let value: BalancesResponse = from_binary(&res).unwrap();
+        assert_eq!(value.escrowed, coins(10, "token"));
+        assert_eq!(value.revenue, coins(5, "token"));
+
+        let res = execute(deps.as_mut(), mock_env(), mock_info("creator", &[]), ExecuteMsg::Refund {})
+            .expect("contract successfully handles Refund message");
+        assert_eq!(
+            res.messages[0].msg,
+            BankMsg::Send { to_address: "creator".to_string(), amount: coins(5, "token") }.into(),
+        );
+
+        // the sponsor gets everything back
+        deps.querier.update_balance(MOCK_CONTRACT_ADDR, coins(10, "token"));
+        let res = execute(deps.as_mut(), mock_env(), mock_info("sponsor_key", &[]), ExecuteMsg::WithdrawSponsorship {})
+            .expect("contract successfully handles WithdrawSponsorship message");
+        assert_eq!(
+            res.messages[0].msg,
+            BankMsg::Send { to_address: "sponsor_key".to_string(), amount: coins(10, "token") }.into(),
+        );
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::Balances {}).unwrap();
+        let value: BalancesResponse = from_binary(&res).unwrap();
+        assert!(value.escrowed.is_empty());
+    }
+
+    fn query_owner_of(deps: Deps, token_id: &str) -> OwnerOfResponse {
+        let msg = QueryMsg::OwnerOf { token_id: token_id.to_string(), include_expired: None };
+        from_binary(&query(deps, mock_env(), msg).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn names_move_as_cw721_tokens() {
+        let mut deps = mock_dependencies();
+        mock_init_no_price(deps.as_mut());
+        mock_alice_registers_name(deps.as_mut(), &[]);
+
+        let transfer = |recipient: &str| ExecuteMsg::TransferNft {
+            recipient: recipient.to_string(),
+            token_id: "alice".to_string(),
+        };
+        match execute(deps.as_mut(), mock_env(), mock_info("bob_key", &[]), transfer("bob_key")) {
+            Ok(_) => panic!("Must return error"),
+            Err(ContractError::Unauthorized {}) => {}
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        }
+
+        let msg = ExecuteMsg::Approve {
+            spender: "bob_key".to_string(),
+            token_id: "alice".to_string(),
+            expires: Some(Expiration::AtHeight(1)),
+        };
+        match execute(deps.as_mut(), mock_env(), mock_info("alice_key", &[]), msg) {
+            Ok(_) => panic!("Must return error"),
+            Err(ContractError::ApprovalExpired {}) => {}
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        }
+        let msg = ExecuteMsg::Approve {
+            spender: "bob_key".to_string(),
+            token_id: "alice".to_string(),
+            expires: None,
+        };
+        let _res = execute(deps.as_mut(), mock_env(), mock_info("alice_key", &[]), msg)
+            .expect("contract successfully handles Approve message");
+        let owner = query_owner_of(deps.as_ref(), "alice");
+        assert_eq!(owner.owner, "alice_key");
+        assert_eq!(owner.approvals[0].spender, "bob_key");
+        assert_eq!(owner.approvals[0].expires, Expiration::Never {});
+
+        // the approved spender moves the name, approvals do not survive the transfer
+        let res = execute(deps.as_mut(), mock_env(), mock_info("bob_key", &[]), transfer("carol_key"))
+            .expect("contract successfully handles TransferNft message");
+        let actions: Vec<_> = res.attributes.iter().filter(|attribute| attribute.key == "action").collect();
+        assert_eq!(actions, vec![&attr("action", "transfer_nft")]);
+        let owner = query_owner_of(deps.as_ref(), "alice");
+        assert_eq!(owner.owner, "carol_key");
+        assert!(owner.approvals.is_empty());
+
+        let msg = QueryMsg::Tokens { owner: "carol_key".to_string(), start_after: None, limit: None };
+        let tokens: TokensResponse = from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+        assert_eq!(tokens.tokens, vec!["alice".to_string()]);
+        let msg = QueryMsg::AllTokens { start_after: None, limit: None };
+        let tokens: TokensResponse = from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+        assert_eq!(tokens.tokens, vec!["alice".to_string()]);
+        let msg = QueryMsg::NftInfo { token_id: "alice".to_string() };
+        let info: NftInfoResponse = from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+        assert_eq!(info.extension.website, "");
+
+        let payload = to_binary("list").unwrap();
+        let msg = ExecuteMsg::SendNft {
+            contract: "market".to_string(),
+            token_id: "alice".to_string(),
+            msg: payload.clone(),
+        };
+        let res = execute(deps.as_mut(), mock_env(), mock_info("carol_key", &[]), msg)
+            .expect("contract successfully handles SendNft message");
+        let actions: Vec<_> = res.attributes.iter().filter(|attribute| attribute.key == "action").collect();
+        assert_eq!(actions, vec![&attr("action", "send_nft")]);
+        assert_eq!(query_owner_of(deps.as_ref(), "alice").owner, "market");
+        let receive: NftReceiverMsg = match &res.messages.last().unwrap().msg {
+            CosmosMsg::Wasm(WasmMsg::Execute { contract_addr, msg, .. }) => {
+                assert_eq!(contract_addr, "market");
+                from_binary(msg).unwrap()
+            }
+            msg => panic!("Unexpected message: {:?}", msg),
+        };
+        let NftReceiverMsg::ReceiveNft(receive) = receive;
+        assert_eq!(receive.sender, "carol_key");
+        assert_eq!(receive.token_id, "alice");
+        assert_eq!(receive.msg, payload);
+    }
+
+    #[test]
+    fn approved_managers_edit_and_operators_transfer() {
+        let mut deps = mock_dependencies();
+        mock_init_no_price(deps.as_mut());
+        mock_alice_registers_name(deps.as_mut(), &[]);
+
+        let edit = ExecuteMsg::Edit {
+            name: "alice".to_string(),
+            bio: "managed".to_string(),
+            website: "".to_string(),
+            round_up: false,
+        };
+        match execute(deps.as_mut(), mock_env(), mock_info("manager_key", &[]), edit.clone()) {
+            Ok(_) => panic!("Must return error"),
+            Err(ContractError::Unauthorized {}) => {}
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        }
+        let msg = ExecuteMsg::Approve {
+            spender: "manager_key".to_string(),
+            token_id: "alice".to_string(),
+            expires: None,
+        };
+        let _res = execute(deps.as_mut(), mock_env(), mock_info("alice_key", &[]), msg)
+            .expect("contract successfully handles Approve message");
+        let _res = execute(deps.as_mut(), mock_env(), mock_info("manager_key", &[]), edit)
+            .expect("contract successfully handles Edit message");
+
+        let msg = ExecuteMsg::SetOperator { operator: "operator_key".to_string(), approved: true };
+        let _res = execute(deps.as_mut(), mock_env(), mock_info("alice_key", &[]), msg)
+            .expect("contract successfully handles SetOperator message");
+        let msg = QueryMsg::AllOperators { owner: "alice_key".to_string(), start_after: None, limit: None };
+        let value: OperatorsResponse = from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+        assert_eq!(value.operators[0].spender, "operator_key");
+
+        let transfer = ExecuteMsg::Transfer {
+            name: "alice".to_string(),
+            to: "bob_key".to_string(),
+            round_up: false,
+            memo: None,
+        };
+        let _res = execute(deps.as_mut(), mock_env(), mock_info("operator_key", &[]), transfer)
+            .expect("contract successfully handles Transfer message");
+        assert_name_owner(deps.as_ref(), "alice", "bob_key");
+
+        // operators of the previous owner have no say over bob's names
+        let msg = ExecuteMsg::SetOperator { operator: "operator_key".to_string(), approved: false };
+        let _res = execute(deps.as_mut(), mock_env(), mock_info("alice_key", &[]), msg)
+            .expect("contract successfully handles SetOperator message");
+        let transfer = ExecuteMsg::TransferNft { recipient: "alice_key".to_string(), token_id: "alice".to_string() };
+        match execute(deps.as_mut(), mock_env(), mock_info("operator_key", &[]), transfer) {
+            Ok(_) => panic!("Must return error"),
+            Err(ContractError::Unauthorized {}) => {}
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        }
+    }
+
+    #[test]
+    fn token_uri_overrides_nft_metadata() {
+        let mut deps = mock_dependencies();
+        mock_init_no_price(deps.as_mut());
+        mock_alice_registers_name(deps.as_mut(), &[]);
+
+        let set_uri = |token_uri: &str| ExecuteMsg::SetTokenUri {
+            name: "alice".to_string(),
+            token_uri: Some(token_uri.to_string()),
+        };
+        for token_uri in ["http://alice.example/meta.json", "ipfs://", "javascript:alert(1)"] {
+            match execute(deps.as_mut(), mock_env(), mock_info("alice_key", &[]), set_uri(token_uri)) {
+                Ok(_) => panic!("Must return error"),
+                Err(ContractError::InvalidTokenUri { .. }) => {}
+                Err(e) => panic!("Unexpected error: {:?}", e),
+            }
+        }
+        match execute(deps.as_mut(), mock_env(), mock_info("bob_key", &[]), set_uri("ipfs://bafy")) {
+            Ok(_) => panic!("Must return error"),
+            Err(ContractError::Unauthorized {}) => {}
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        }
+        let _res = execute(deps.as_mut(), mock_env(), mock_info("alice_key", &[]), set_uri("ipfs://bafy"))
+            .expect("contract successfully handles SetTokenUri message");
+
+        let msg = QueryMsg::NftInfo { token_id: "alice".to_string() };
+        let info: NftInfoResponse = from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+        assert_eq!(info.token_uri, Some("ipfs://bafy".to_string()));
+    }
+
+    #[test]
+    fn release_batch_refunds_unused_time_once() {
+        let mut deps = mock_dependencies();
+        let msg = InstantiateMsg {
+            admin: None,
+            purchase_price: vec![coin(100, "token")],
+            transfer_price: vec![],
+            edit_price: vec![],
+            max_supply: None,
+            registration_duration: Some(1000),
+            grace_period: None,
+            renewal_price: None,
+            preset: None,
+            launch_schedule: vec![],
+            burn_fees: false,
+        };
+        let _res = instantiate(deps.as_mut(), mock_env(), mock_info("creator", &[]), msg)
+            .expect("contract successfully handles InstantiateMsg");
+        for name in ["alice", "alice-two", "alice-three"] {
+            let msg = ExecuteMsg::Register {
+                name: name.to_string(),
+                bio: "".to_string(),
+                website: "".to_string(),
+                round_up: false,
+                referrer: None,
+            };
+            let _res = execute(deps.as_mut(), mock_env(), mock_info("alice_key", &coins(100, "token")), msg)
+                .expect("contract successfully handles Register message");
+        }
+        deps.querier.update_balance(MOCK_CONTRACT_ADDR, coins(300, "token"));
+
+        let msg = ExecuteMsg::ReleaseBatch { names: vec!["alice".to_string(), "alice-two".to_string()] };
+        match execute(deps.as_mut(), mock_env(), mock_info("bob_key", &[]), msg.clone()) {
+            Ok(_) => panic!("Must return error"),
+            Err(ContractError::Unauthorized {}) => {}
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        }
+
+        // three quarters of the registration period are left on both names
+        let res = execute(deps.as_mut(), mock_env_at(250), mock_info("alice_key", &[]), msg)
+            .expect("contract successfully handles ReleaseBatch message");
+        assert_eq!(res.messages.len(), 1);
+        assert_eq!(
+            res.messages[0].msg,
+            BankMsg::Send { to_address: "alice_key".to_string(), amount: coins(150, "token") }.into(),
+        );
+        assert_eq!(query_supply(deps.as_ref()).total, 1);
+        assert_name_owner(deps.as_ref(), "alice-three", "alice_key");
+    }
+
+    #[test]
+    fn transfer_offer_waits_for_recipient() {
+        let mut deps = mock_dependencies();
+        mock_init_no_price(deps.as_mut());
+        mock_alice_registers_name(deps.as_mut(), &[]);
+
+        let msg = ExecuteMsg::OfferTransfer {
+            name: "alice".to_string(),
+            to: "bob_key".to_string(),
+            price: Some(coin(10, "token")),
+        };
+        let _res = execute(deps.as_mut(), mock_env(), mock_info("alice_key", &[]), msg)
+            .expect("contract successfully handles OfferTransfer message");
+        // alice keeps the name until bob accepts
+        assert_name_owner(deps.as_ref(), "alice", "alice_key");
+
+        let accept = ExecuteMsg::AcceptTransfer { name: "alice".to_string() };
+        let res = execute(deps.as_mut(), mock_env(), mock_info("carol_key", &coins(10, "token")), accept.clone());
+        match res {
+            Ok(_) => panic!("Must return error"),
+            Err(ContractError::OfferNotExists { .. }) => {}
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        }
+
+        let res = execute(deps.as_mut(), mock_env(), mock_info("bob_key", &coins(9, "token")), accept.clone());
+        match res {
+            Ok(_) => panic!("Must return error"),
+            Err(ContractError::InsufficientFundsSend {}) => {}
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        }
+
+        let _res = execute(deps.as_mut(), mock_env(), mock_info("bob_key", &coins(10, "token")), accept)
+            .expect("contract successfully handles AcceptTransfer message");
+        assert_name_owner(deps.as_ref(), "alice", "bob_key");
+
+        // the price waits in the payout ledger until alice claims it
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::Payout { address: "alice_key".to_string() }).unwrap();
+        let res: PayoutResponse = from_binary(&res).unwrap();
+        assert_eq!(res.claimable, coins(10, "token"));
+        let res = execute(deps.as_mut(), mock_env(), mock_info("alice_key", &[]), ExecuteMsg::ClaimPayout {})
+            .expect("contract successfully handles ClaimPayout message");
+        assert_eq!(
+            res.messages[0].msg,
+            CosmosMsg::Bank(BankMsg::Send { to_address: "alice_key".to_string(), amount: coins(10, "token") })
+        );
+        let res = execute(deps.as_mut(), mock_env(), mock_info("alice_key", &[]), ExecuteMsg::ClaimPayout {});
+        match res {
+            Ok(_) => panic!("Must return error"),
+            Err(ContractError::NothingToClaim {}) => {}
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        }
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::TransferOffer { name: "alice".to_string() }).unwrap();
+        let res: TransferOfferResponse = from_binary(&res).unwrap();
+        assert_eq!(res.offer, None);
+    }
+
+    #[test]
+    fn transfer_offer_can_be_cancelled() {
+        let mut deps = mock_dependencies();
+        mock_init_no_price(deps.as_mut());
+        mock_alice_registers_name(deps.as_mut(), &[]);
+
+        let msg = ExecuteMsg::OfferTransfer { name: "alice".to_string(), to: "bob_key".to_string(), price: None };
+        let _res = execute(deps.as_mut(), mock_env(), mock_info("alice_key", &[]), msg)
+            .expect("contract successfully handles OfferTransfer message");
+
+        let cancel = ExecuteMsg::CancelTransfer { name: "alice".to_string() };
+        let res = execute(deps.as_mut(), mock_env(), mock_info("carol_key", &[]), cancel.clone());
+        match res {
+            Ok(_) => panic!("Must return error"),
+            Err(ContractError::Unauthorized {}) => {}
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        }
+        let _res = execute(deps.as_mut(), mock_env(), mock_info("alice_key", &[]), cancel)
+            .expect("contract successfully handles CancelTransfer message");
+
+        let accept = ExecuteMsg::AcceptTransfer { name: "alice".to_string() };
+        let res = execute(deps.as_mut(), mock_env(), mock_info("bob_key", &[]), accept);
+        match res {
+            Ok(_) => panic!("Must return error"),
+            Err(ContractError::OfferNotExists { .. }) => {}
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        }
+        assert_name_owner(deps.as_ref(), "alice", "alice_key");
+    }
+
+    #[test]
+    fn premium_names_go_to_the_highest_bidder() {
+        let mut deps = mock_dependencies();
+        mock_init_no_price(deps.as_mut());
+
+        let msg = ExecuteMsg::StartAuction { name: "woof".to_string(), min_bid: coin(100, "token"), duration: 100 };
+        let res = execute(deps.as_mut(), mock_env(), mock_info("alice_key", &[]), msg.clone());
+        match res {
+            Ok(_) => panic!("Must return error"),
+            Err(ContractError::Unauthorized {}) => {}
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        }
+        let _res = execute(deps.as_mut(), mock_env(), mock_info("creator", &[]), msg)
+            .expect("contract successfully handles StartAuction message");
+
+        // auctioned names can't be bought directly
+        let msg = ExecuteMsg::Register {
+            name: "woof".to_string(),
+            bio: "".to_string(),
+            website: "".to_string(),
+            round_up: false,
+            referrer: None,
+        };
+        let res = execute(deps.as_mut(), mock_env(), mock_info("alice_key", &[]), msg);
+        match res {
+            Ok(_) => panic!("Must return error"),
+            Err(ContractError::NameInAuction { .. }) => {}
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        }
+
+        let bid = ExecuteMsg::PlaceBid { name: "woof".to_string() };
+        let res = execute(deps.as_mut(), mock_env(), mock_info("alice_key", &coins(99, "token")), bid.clone());
+        match res {
+            Ok(_) => panic!("Must return error"),
+            Err(ContractError::BidTooLow { .. }) => {}
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        }
+        let _res = execute(deps.as_mut(), mock_env(), mock_info("alice_key", &coins(100, "token")), bid.clone())
+            .expect("contract successfully handles PlaceBid message");
+
+        // bob outbids alice, who gets her bid back
+        let res = execute(deps.as_mut(), mock_env(), mock_info("bob_key", &coins(150, "token")), bid.clone())
+            .expect("contract successfully handles PlaceBid message");
+        assert_eq!(
+            res.messages[0].msg,
+            CosmosMsg::Bank(BankMsg::Send { to_address: "alice_key".to_string(), amount: coins(100, "token") })
+        );
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::Auction { name: "woof".to_string() }).unwrap();
+        let res: AuctionResponse = from_binary(&res).unwrap();
+        assert_eq!(res.highest_bid.map(|bid| bid.bidder), Some(Addr::unchecked("bob_key")));
+
+        let close = ExecuteMsg::CloseAuction { name: "woof".to_string() };
+        let res = execute(deps.as_mut(), mock_env_at(50), mock_info("carol_key", &[]), close.clone());
+        match res {
+            Ok(_) => panic!("Must return error"),
+            Err(ContractError::AuctionNotEnded { .. }) => {}
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        }
+        let res = execute(deps.as_mut(), mock_env_at(100), mock_info("alice_key", &coins(200, "token")), bid);
+        match res {
+            Ok(_) => panic!("Must return error"),
+            Err(ContractError::AuctionEnded { .. }) => {}
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        }
+
+        let _res = execute(deps.as_mut(), mock_env_at(100), mock_info("carol_key", &[]), close)
+            .expect("contract successfully handles CloseAuction message");
+        assert_name_owner(deps.as_ref(), "woof", "bob_key");
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::Balances {}).unwrap();
+        let res: BalancesResponse = from_binary(&res).unwrap();
+        assert!(res.escrowed.is_empty());
+    }
+
+    #[test]
+    fn presets_fill_unset_fields() {
+        let mut deps = mock_dependencies();
+        let msg = InstantiateMsg {
+            admin: None,
+            purchase_price: vec![coin(2, "token")],
+            transfer_price: vec![],
+            edit_price: vec![],
+            max_supply: None,
+            registration_duration: None,
+            grace_period: Some(10),
+            renewal_price: None,
+            preset: Some(Preset::Identity),
+            launch_schedule: vec![],
+            burn_fees: false,
+        };
+        let _res = instantiate(deps.as_mut(), mock_env(), mock_info("creator", &[]), msg)
+            .expect("contract successfully handles InstantiateMsg");
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap();
+        let config: ConfigResponse = from_binary(&res).unwrap();
+        assert_eq!(config.registration_duration, Some(365 * 24 * 3600));
+        assert_eq!(config.renewal_price, Some(coin(2, "token")));
+        // set fields override the preset
+        assert_eq!(config.grace_period, 10);
+
+        let mut deps = mock_dependencies();
+        let msg = InstantiateMsg {
+            admin: None,
+            purchase_price: vec![coin(2, "token")],
+            transfer_price: vec![],
+            edit_price: vec![],
+            max_supply: None,
+            registration_duration: None,
+            grace_period: None,
+            renewal_price: None,
+            preset: Some(Preset::Collectible),
+            launch_schedule: vec![],
+            burn_fees: false,
+        };
+        let _res = instantiate(deps.as_mut(), mock_env(), mock_info("creator", &[]), msg)
+            .expect("contract successfully handles InstantiateMsg");
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap();
+        let config: ConfigResponse = from_binary(&res).unwrap();
+        assert_eq!(config.registration_duration, None);
+        assert_eq!(
+            config.length_prices,
+            vec![
+                PriceTier { max_length: 3, price: coin(200, "token") },
+                PriceTier { max_length: 5, price: coin(20, "token") },
+            ]
+        );
+    }
+
+    fn query_tombstone(deps: Deps, name: &str) -> Option<Tombstone> {
+        let res = query(deps, mock_env(), QueryMsg::Tombstone { name: name.to_string() }).unwrap();
+        from_binary::<TombstoneResponse>(&res).unwrap().tombstone
+    }
+
+    #[test]
+    fn removed_names_leave_a_tombstone() {
+        let mut deps = mock_dependencies();
+        mock_init_no_price(deps.as_mut());
+        mock_alice_registers_name(deps.as_mut(), &[]);
+
+        assert_eq!(query_tombstone(deps.as_ref(), "alice"), None);
+
+        let msg = ExecuteMsg::ForceRelease { name: "alice".to_string() };
+        let _res = execute(deps.as_mut(), mock_env(), mock_info("creator", &[]), msg)
+            .expect("contract successfully handles ForceRelease message");
+
+        let tombstone = query_tombstone(deps.as_ref(), "alice").expect("tombstone written");
+        assert_eq!(tombstone.previous_owner, Addr::unchecked("alice_key"));
+        assert_eq!(tombstone.height, mock_env().block.height);
+        assert_eq!(tombstone.reason, RemovalReason::Revoked);
+        assert_eq!(query_tombstone(deps.as_ref(), "bob"), None);
+    }
+
+    #[test]
+    fn reserved_names_are_assigned_by_the_admin() {
+        let mut deps = mock_dependencies();
+        mock_init_no_price(deps.as_mut());
+
+        let msg = ExecuteMsg::ReserveNames { names: vec!["huahua".to_string(), "woof".to_string()] };
+        let _res = execute(deps.as_mut(), mock_env(), mock_info("creator", &[]), msg)
+            .expect("contract successfully handles ReserveNames message");
+
+        let msg = ExecuteMsg::Register {
+            name: "huahua".to_string(),
+            bio: "".to_string(),
+            website: "".to_string(),
+            round_up: false,
+            referrer: None,
+        };
+        let res = execute(deps.as_mut(), mock_env(), mock_info("alice_key", &[]), msg);
+        match res {
+            Ok(_) => panic!("Must return error"),
+            Err(ContractError::NameReserved { .. }) => {}
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        }
+
+        let msg = ExecuteMsg::AssignReservedName { name: "huahua".to_string(), owner: "alice_key".to_string() };
+        let res = execute(deps.as_mut(), mock_env(), mock_info("alice_key", &[]), msg.clone());
+        match res {
+            Ok(_) => panic!("Must return error"),
+            Err(ContractError::Unauthorized {}) => {}
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        }
+        let _res = execute(deps.as_mut(), mock_env(), mock_info("creator", &[]), msg)
+            .expect("contract successfully handles AssignReservedName message");
+        assert_name_owner(deps.as_ref(), "huahua", "alice_key");
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::ReservedNames { start_after: None, limit: None }).unwrap();
+        let res: NamesResponse = from_binary(&res).unwrap();
+        assert_eq!(res.names, vec!["woof".to_string()]);
+
+        let msg = ExecuteMsg::UnreserveNames { names: vec!["woof".to_string()] };
+        let _res = execute(deps.as_mut(), mock_env(), mock_info("creator", &[]), msg)
+            .expect("contract successfully handles UnreserveNames message");
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::ReservedNames { start_after: None, limit: None }).unwrap();
+        let res: NamesResponse = from_binary(&res).unwrap();
+        assert!(res.names.is_empty());
+    }
+
+    #[test]
+    fn released_names_cool_down_for_their_previous_owner() {
+        let mut deps = mock_dependencies();
+        mock_init_no_price(deps.as_mut());
+        let msg = ExecuteMsg::SetReleaseCooldown { release_cooldown: 100 };
+        let _res = execute(deps.as_mut(), mock_env(), mock_info("creator", &[]), msg)
+            .expect("contract successfully handles SetReleaseCooldown message");
+        mock_alice_registers_name(deps.as_mut(), &[]);
+
+        let msg = ExecuteMsg::Release { name: "alice".to_string() };
+        let _res = execute(deps.as_mut(), mock_env(), mock_info("alice_key", &[]), msg)
+            .expect("contract successfully handles Release message");
+
+        let register = ExecuteMsg::Register {
+            name: "alice".to_string(),
+            bio: "".to_string(),
+            website: "".to_string(),
+            round_up: false,
+            referrer: None,
+        };
+        let res = execute(deps.as_mut(), mock_env_at(99), mock_info("bob_key", &[]), register.clone());
+        match res {
+            Ok(_) => panic!("Must return error"),
+            Err(ContractError::NameInCooldown { .. }) => {}
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        }
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::CheckAvailability { names: vec!["alice".to_string()] });
+        let res: AvailabilityResponse = from_binary(&res.unwrap()).unwrap();
+        assert_eq!(res.names[0].status, AvailabilityStatus::InCooldown);
+
+        // others can register it once the cooldown is over
+        let _res = execute(deps.as_mut(), mock_env_at(100), mock_info("bob_key", &[]), register)
+            .expect("contract successfully handles Register message");
+        assert_name_owner(deps.as_ref(), "alice", "bob_key");
+    }
+
+    #[test]
+    fn previous_owner_registers_during_cooldown() {
+        let mut deps = mock_dependencies();
+        mock_init_no_price(deps.as_mut());
+        let msg = ExecuteMsg::SetReleaseCooldown { release_cooldown: 100 };
+        let _res = execute(deps.as_mut(), mock_env(), mock_info("creator", &[]), msg)
+            .expect("contract successfully handles SetReleaseCooldown message");
+        mock_alice_registers_name(deps.as_mut(), &[]);
+
+        let msg = ExecuteMsg::Release { name: "alice".to_string() };
+        let _res = execute(deps.as_mut(), mock_env(), mock_info("alice_key", &[]), msg)
+            .expect("contract successfully handles Release message");
+        mock_alice_registers_name(deps.as_mut(), &[]);
+        assert_name_owner(deps.as_ref(), "alice", "alice_key");
+    }
+
+    #[test]
+    fn prices_accept_a_single_coin_or_a_list() {
+        let msg: InstantiateMsg = from_slice(
+            br#"{
+                "admin": null,
+                "purchase_price": {"denom": "token", "amount": "5"},
+                "transfer_price": null,
+                "edit_price": [{"denom": "token", "amount": "1"}, {"denom": "earth", "amount": "2"}],
+                "max_supply": null,
+                "registration_duration": null,
+                "grace_period": null,
+                "renewal_price": null
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(msg.purchase_price, coins(5, "token"));
+        assert_eq!(msg.transfer_price, vec![]);
+        assert_eq!(msg.edit_price, vec![coin(1, "token"), coin(2, "earth")]);
+
+        let mut deps = mock_dependencies();
+        let _res = instantiate(deps.as_mut(), mock_env(), mock_info("creator", &[]), msg)
+            .expect("contract successfully handles InstantiateMsg");
+        mock_alice_registers_name(deps.as_mut(), &coins(5, "token"));
+
+        // the edit can be paid in either denom
+        let msg = ExecuteMsg::Edit {
+            name: "alice".to_string(),
+            bio: "woof".to_string(),
+            website: "".to_string(),
+            round_up: false,
+        };
+        let _res = execute(deps.as_mut(), mock_env(), mock_info("alice_key", &coins(2, "earth")), msg.clone())
+            .expect("contract successfully handles Edit message");
+        let res = execute(deps.as_mut(), mock_env(), mock_info("alice_key", &coins(1, "earth")), msg);
+        match res {
+            Ok(_) => panic!("Must return error"),
+            Err(ContractError::InsufficientFundsSend {}) => {}
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        }
+    }
+
+    #[test]
+    fn record_stats_count_activity() {
+        let mut deps = mock_dependencies();
+        mock_init_no_price(deps.as_mut());
+        mock_alice_registers_name(deps.as_mut(), &[]);
+
+        let msg = ExecuteMsg::Edit {
+            name: "alice".to_string(),
+            bio: "woof".to_string(),
+            website: "".to_string(),
+            round_up: false,
+        };
+        let _res = execute(deps.as_mut(), mock_env(), mock_info("alice_key", &[]), msg)
+            .expect("contract successfully handles Edit message");
+        let msg = ExecuteMsg::OfferTransfer {
+            name: "alice".to_string(),
+            to: "bob_key".to_string(),
+            price: Some(coin(10, "token")),
+        };
+        let _res = execute(deps.as_mut(), mock_env(), mock_info("alice_key", &[]), msg)
+            .expect("contract successfully handles OfferTransfer message");
+        let msg = ExecuteMsg::AcceptTransfer { name: "alice".to_string() };
+        let _res = execute(deps.as_mut(), mock_env(), mock_info("bob_key", &coins(10, "token")), msg)
+            .expect("contract successfully handles AcceptTransfer message");
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::RecordStats { name: "alice".to_string() }).unwrap();
+        let res: RecordStatsResponse = from_binary(&res).unwrap();
+        assert_eq!(res.record.address, Some("bob_key".to_string()));
+        assert_eq!(res.stats.edits, 1);
+        assert_eq!(res.stats.transfers, 1);
+        assert_eq!(res.stats.renewals, 0);
+        assert_eq!(res.stats.sales, 1);
+        assert_eq!(res.stats.sale_volume, coins(10, "token"));
+    }
+
+    #[test]
+    fn overpayment_is_refunded() {
+        let mut deps = mock_dependencies();
+        mock_init_with_price(deps.as_mut(), coin(2, "token"), coin(2, "token"));
+
+        // exact payments need no refund
+        let msg = ExecuteMsg::Register {
+            name: "alice".to_string(),
+            bio: "".to_string(),
+            website: "".to_string(),
+            round_up: false,
+            referrer: None,
+        };
+        let res = execute(deps.as_mut(), mock_env(), mock_info("alice_key", &coins(2, "token")), msg)
+            .expect("contract successfully handles Register message");
+        assert!(res.messages.is_empty());
+
+        let msg = ExecuteMsg::Register {
+            name: "alice2".to_string(),
+            bio: "".to_string(),
+            website: "".to_string(),
+            round_up: false,
+            referrer: None,
+        };
+        let res = execute(deps.as_mut(), mock_env(), mock_info("alice_key", &coins(5, "token")), msg)
+            .expect("contract successfully handles Register message");
+        assert_eq!(
+            res.messages[0].msg,
+            CosmosMsg::Bank(BankMsg::Send { to_address: "alice_key".to_string(), amount: coins(3, "token") })
+        );
+
+        // denoms the price is not asked in are refused outright
+        let msg = ExecuteMsg::Transfer {
+            name: "alice".to_string(),
+            to: "bob_key".to_string(),
+            round_up: false,
+            memo: None,
+        };
+        let info = mock_info("alice_key", &[coin(1, "earth"), coin(3, "token")]);
+        let res = execute(deps.as_mut(), mock_env(), info, msg);
+        match res {
+            Ok(_) => panic!("Must return error"),
+            Err(ContractError::UnacceptedDenom { denom }) => assert_eq!(denom, "earth"),
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        }
+
+        // coins of the price denom add up, what is left over comes back
+        let msg = ExecuteMsg::Transfer {
+            name: "alice".to_string(),
+            to: "bob_key".to_string(),
+            round_up: false,
+            memo: None,
+        };
+        let res = execute(deps.as_mut(), mock_env(), mock_info("alice_key", &[coin(1, "token"), coin(2, "token")]), msg)
+            .expect("contract successfully handles Transfer message");
+        assert_eq!(
+            res.messages[0].msg,
+            CosmosMsg::Bank(BankMsg::Send { to_address: "alice_key".to_string(), amount: coins(1, "token") })
+        );
+
+        // edits are free here, everything sent is returned
+        let msg = ExecuteMsg::Edit {
+            name: "alice".to_string(),
+            bio: "woof".to_string(),
+            website: "".to_string(),
+            round_up: false,
+        };
+        let res = execute(deps.as_mut(), mock_env(), mock_info("bob_key", &coins(1, "token")), msg)
+            .expect("contract successfully handles Edit message");
+        assert_eq!(
+            res.messages[0].msg,
+            CosmosMsg::Bank(BankMsg::Send { to_address: "bob_key".to_string(), amount: coins(1, "token") })
+        );
+    }
+
+    fn query_treasury(deps: Deps, address: &str) -> Vec<Coin> {
+        let res = query(deps, mock_env(), QueryMsg::Treasury { address: address.to_string() }).unwrap();
+        from_binary::<TreasuryResponse>(&res).unwrap().balance
+    }
+
+    #[test]
+    fn fees_are_split_with_beneficiaries() {
+        let mut deps = mock_dependencies();
+        mock_init_with_price(deps.as_mut(), coin(10, "token"), coin(10, "token"));
+
+        let split = |percent| ExecuteMsg::SetTreasury {
+            fee_collector: Some("collector_key".to_string()),
+            revenue_split: vec![RevenueShare { address: "dev_key".to_string(), percent }],
+        };
+        let res = execute(deps.as_mut(), mock_env(), mock_info("anyone", &[]), split(20));
+        match res {
+            Ok(_) => panic!("Must return error"),
+            Err(ContractError::Unauthorized {}) => {}
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        }
+        let res = execute(deps.as_mut(), mock_env(), mock_info("creator", &[]), split(101));
+        match res {
+            Ok(_) => panic!("Must return error"),
+            Err(ContractError::InvalidRevenueSplit {}) => {}
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        }
+        execute(deps.as_mut(), mock_env(), mock_info("creator", &[]), split(20))
+            .expect("contract successfully handles SetTreasury message");
+
+        mock_alice_registers_name(deps.as_mut(), &coins(10, "token"));
+        assert_eq!(query_treasury(deps.as_ref(), "dev_key"), coins(2, "token"));
+        deps.querier.update_balance(MOCK_CONTRACT_ADDR, coins(10, "token"));
+
+        // a beneficiary can only take its own share
+        let withdraw = |amount: u128| ExecuteMsg::Withdraw { amount: Uint128::new(amount), denom: "token".to_string() };
+        let res = execute(deps.as_mut(), mock_env(), mock_info("dev_key", &[]), withdraw(3));
+        match res {
+            Ok(_) => panic!("Must return error"),
+            Err(ContractError::WithdrawTooLarge { available }) => assert_eq!(available, coin(2, "token")),
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        }
+        let res = execute(deps.as_mut(), mock_env(), mock_info("dev_key", &[]), withdraw(2))
+            .expect("contract successfully handles Withdraw message");
+        assert_eq!(
+            res.messages[0].msg,
+            CosmosMsg::Bank(BankMsg::Send { to_address: "dev_key".to_string(), amount: coins(2, "token") })
+        );
+        assert!(query_treasury(deps.as_ref(), "dev_key").is_empty());
+        deps.querier.update_balance(MOCK_CONTRACT_ADDR, coins(8, "token"));
+
+        // the collector gets the rest of the revenue
+        let res = execute(deps.as_mut(), mock_env(), mock_info("collector_key", &[]), withdraw(9));
+        match res {
+            Ok(_) => panic!("Must return error"),
+            Err(ContractError::WithdrawTooLarge { available }) => assert_eq!(available, coin(8, "token")),
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        }
+        execute(deps.as_mut(), mock_env(), mock_info("collector_key", &[]), withdraw(8))
+            .expect("contract successfully handles Withdraw message");
+    }
+
+    #[test]
+    fn names_are_scored_against_the_dictionary() {
+        let mut deps = mock_dependencies();
+        mock_init_no_price(deps.as_mut());
+
+        let msg = ExecuteMsg::AddDictionaryWords { words: vec!["dog".to_string(), "doge".to_string()] };
+        let res = execute(deps.as_mut(), mock_env(), mock_info("anyone", &[]), msg.clone());
+        match res {
+            Ok(_) => panic!("Must return error"),
+            Err(ContractError::Unauthorized {}) => {}
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        }
+        execute(deps.as_mut(), mock_env(), mock_info("creator", &[]), msg)
+            .expect("contract successfully handles AddDictionaryWords message");
+
+        let msg = ExecuteMsg::AddDictionaryWords { words: vec!["dog-2".to_string()] };
+        let res = execute(deps.as_mut(), mock_env(), mock_info("creator", &[]), msg);
+        match res {
+            Ok(_) => panic!("Must return error"),
+            Err(ContractError::InvalidDictionaryWord { word }) => assert_eq!(word, "dog-2"),
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        }
+
+        // the longest word the name starts with is the one found
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::NameScore { name: "dogecoin".to_string() }).unwrap();
+        let value: NameScoreResponse = from_binary(&res).unwrap();
+        assert_eq!(
+            value,
+            NameScoreResponse { score: 85, length: 8, digits: 0, hyphens: 0, dictionary_word: Some("doge".to_string()) }
+        );
+
+        let msg = ExecuteMsg::RemoveDictionaryWords { words: vec!["doge".to_string()] };
+        execute(deps.as_mut(), mock_env(), mock_info("creator", &[]), msg)
+            .expect("contract successfully handles RemoveDictionaryWords message");
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::NameScore { name: "dogecoin".to_string() }).unwrap();
+        let value: NameScoreResponse = from_binary(&res).unwrap();
+        assert_eq!(value.dictionary_word, Some("dog".to_string()));
+
+        // only valid names are scored
+        query(deps.as_ref(), mock_env(), QueryMsg::NameScore { name: "Dogecoin".to_string() }).unwrap_err();
+
+        // unicode names are scored by characters, not bytes
+        let charset = Charset { allow_unicode: true, ..Charset::default() };
+        let policy = NamePolicy { charset, ..NamePolicy::default() };
+        let msg = ExecuteMsg::Editconf {
+            purchase_price: vec![],
+            transfer_price: vec![],
+            edit_price: vec![],
+            length_prices: None,
+            name_policy: Some(policy),
+        };
+        execute(deps.as_mut(), mock_env(), mock_info("creator", &[]), msg)
+            .expect("contract successfully handles Editconf message");
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::NameScore { name: "dogé".to_string() }).unwrap();
+        let value: NameScoreResponse = from_binary(&res).unwrap();
+        assert_eq!((value.score, value.length, value.dictionary_word.as_deref()), (100, 4, Some("dog")));
+    }
+
+    #[test]
+    fn ownership_moves_once_accepted() {
+        let mut deps = mock_dependencies();
+        mock_init_no_price(deps.as_mut());
+
+        let msg = ExecuteMsg::TransferOwnership { new_owner: "new_admin".to_string() };
+        let res = execute(deps.as_mut(), mock_env(), mock_info("anyone", &[]), msg.clone());
+        match res {
+            Ok(_) => panic!("Must return error"),
+            Err(ContractError::Unauthorized {}) => {}
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        }
+        execute(deps.as_mut(), mock_env(), mock_info("creator", &[]), msg)
+            .expect("contract successfully handles TransferOwnership message");
+
+        // the current admin keeps its rights until the new one accepts
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::PendingOwner {}).unwrap();
+        let value: PendingOwnerResponse = from_binary(&res).unwrap();
+        assert_eq!(value.pending_owner, Some(Addr::unchecked("new_admin")));
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap();
+        assert_eq!(from_binary::<ConfigResponse>(&res).unwrap().owner, Addr::unchecked("creator"));
+
+        let res = execute(deps.as_mut(), mock_env(), mock_info("anyone", &[]), ExecuteMsg::AcceptOwnership {});
+        match res {
+            Ok(_) => panic!("Must return error"),
+            Err(ContractError::Unauthorized {}) => {}
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        }
+        execute(deps.as_mut(), mock_env(), mock_info("new_admin", &[]), ExecuteMsg::AcceptOwnership {})
+            .expect("contract successfully handles AcceptOwnership message");
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap();
+        assert_eq!(from_binary::<ConfigResponse>(&res).unwrap().owner, Addr::unchecked("new_admin"));
+        let res = execute(deps.as_mut(), mock_env(), mock_info("new_admin", &[]), ExecuteMsg::AcceptOwnership {});
+        match res {
+            Ok(_) => panic!("Must return error"),
+            Err(ContractError::NoPendingOwner {}) => {}
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        }
+    }
+
+    #[test]
+    fn launch_schedule_gates_registration() {
+        let mut deps = mock_dependencies();
+        let start = |phase, seconds| PhaseStart { phase, starts_at: mock_env_at(seconds).block.time };
+        let msg = InstantiateMsg {
+            admin: None,
+            purchase_price: vec![],
+            transfer_price: vec![],
+            edit_price: vec![],
+            max_supply: None,
+            registration_duration: None,
+            grace_period: None,
+            renewal_price: None,
+            preset: None,
+            launch_schedule: vec![start(LaunchPhase::Allowlist, 1000), start(LaunchPhase::Public, 2000)],
+            burn_fees: false,
+        };
+        instantiate(deps.as_mut(), mock_env(), mock_info("creator", &[]), msg)
+            .expect("contract successfully handles InstantiateMsg");
+
+        let register = |name: &str| ExecuteMsg::Register {
+            name: name.to_string(),
+            bio: "".to_string(),
+            website: "".to_string(),
+            round_up: false,
+            referrer: None,
+        };
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::Phase {}).unwrap();
+        let value: PhaseResponse = from_binary(&res).unwrap();
+        assert_eq!(value.phase, LaunchPhase::ReservedOnly);
+        assert_eq!(value.next, Some(start(LaunchPhase::Allowlist, 1000)));
+        let res = execute(deps.as_mut(), mock_env(), mock_info("alice_key", &[]), register("alice"));
+        match res {
+            Ok(_) => panic!("Must return error"),
+            Err(ContractError::RegistrationNotOpen { phase }) => assert_eq!(phase, LaunchPhase::ReservedOnly),
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        }
+
+        // during the allowlist phase only allowlisted addresses register
+        let msg = ExecuteMsg::SetAllowlist { add: vec!["alice_key".to_string()], remove: vec![] };
+        execute(deps.as_mut(), mock_env(), mock_info("creator", &[]), msg)
+            .expect("contract successfully handles SetAllowlist message");
+        execute(deps.as_mut(), mock_env_at(1000), mock_info("alice_key", &[]), register("alice"))
+            .expect("contract successfully handles Register message");
+        let res = execute(deps.as_mut(), mock_env_at(1000), mock_info("bob_key", &[]), register("bob"));
+        match res {
+            Ok(_) => panic!("Must return error"),
+            Err(ContractError::RegistrationNotOpen { phase }) => assert_eq!(phase, LaunchPhase::Allowlist),
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        }
+
+        execute(deps.as_mut(), mock_env_at(2000), mock_info("bob_key", &[]), register("bob"))
+            .expect("contract successfully handles Register message");
+        let res = query(deps.as_ref(), mock_env_at(2000), QueryMsg::Phase {}).unwrap();
+        let value: PhaseResponse = from_binary(&res).unwrap();
+        assert_eq!(value, PhaseResponse { phase: LaunchPhase::Public, next: None });
+    }
+
+    #[test]
+    fn admin_pause_stops_every_execute() {
+        let mut deps = mock_dependencies();
+        mock_init_no_price(deps.as_mut());
+
+        let res = execute(deps.as_mut(), mock_env(), mock_info("anyone", &[]), ExecuteMsg::Pause {});
+        match res {
+            Ok(_) => panic!("Must return error"),
+            Err(ContractError::Unauthorized {}) => {}
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        }
+        let res = execute(deps.as_mut(), mock_env(), mock_info("creator", &[]), ExecuteMsg::Pause {})
+            .expect("contract successfully handles Pause message");
+        assert_eq!(res.attributes[1], ("paused", "true"));
+
+        let msg = ExecuteMsg::Register {
+            name: "alice".to_string(),
+            bio: "".to_string(),
+            website: "".to_string(),
+            round_up: false,
+            referrer: None,
+        };
+        let res = execute(deps.as_mut(), mock_env(), mock_info("alice_key", &[]), msg);
+        match res {
+            Ok(_) => panic!("Must return error"),
+            Err(ContractError::Paused {}) => {}
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        }
+        // the admin is stopped too, queries keep answering
+        let msg = ExecuteMsg::SetAllowlist { add: vec![], remove: vec![] };
+        let res = execute(deps.as_mut(), mock_env(), mock_info("creator", &[]), msg);
+        match res {
+            Ok(_) => panic!("Must return error"),
+            Err(ContractError::Paused {}) => {}
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        }
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::PausedActions {}).unwrap();
+        assert!(from_binary::<PausedActionsResponse>(&res).unwrap().paused);
+
+        execute(deps.as_mut(), mock_env(), mock_info("creator", &[]), ExecuteMsg::Unpause {})
+            .expect("contract successfully handles Unpause message");
+        mock_alice_registers_name(deps.as_mut(), &[]);
+    }
+
+    #[test]
+    fn register_batch_is_all_or_nothing() {
+        let mut deps = mock_dependencies();
+        mock_init_with_price(deps.as_mut(), coin(2, "token"), coin(2, "token"));
+
+        let batch = |names: &[&str]| ExecuteMsg::RegisterBatch {
+            registrations: names
+                .iter()
+                .map(|name| RegistrationData { name: name.to_string(), bio: "".to_string(), website: "".to_string() })
+                .collect(),
+        };
+        let info = mock_info("alice_key", &coins(5, "token"));
+        let res = execute(deps.as_mut(), mock_env(), info, batch(&["alice", "bob", "carl"]));
+        match res {
+            Ok(_) => panic!("Must return error"),
+            Err(ContractError::InsufficientFundsSend {}) => {}
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        }
+
+        // sent coins add up to the price of every name, the rest is refunded
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("alice_key", &[coin(4, "token"), coin(3, "token")]),
+            batch(&["alice", "bob", "carl"]),
+        )
+        .expect("contract successfully handles RegisterBatch message");
+        assert_eq!(
+            res.messages[0].msg,
+            CosmosMsg::Bank(BankMsg::Send { to_address: "alice_key".to_string(), amount: coins(1, "token") })
+        );
+        assert_name_owner(deps.as_ref(), "carl", "alice_key");
+
+        // one taken name fails the whole batch
+        let info = mock_info("bob_key", &coins(4, "token"));
+        let res = execute(deps.as_mut(), mock_env(), info, batch(&["dave", "alice"]));
+        match res {
+            Ok(_) => panic!("Must return error"),
+            Err(ContractError::NameTaken { name }) => assert_eq!(name, "alice"),
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        }
+    }
+
+    #[test]
+    fn owners_snapshot_follows_heights() {
+        let mut deps = mock_dependencies();
+        mock_init_no_price(deps.as_mut());
+        let at = |height| {
+            let mut env = mock_env();
+            env.block.height = height;
+            env
+        };
+        let snapshot = |deps: Deps, at_height| {
+            let msg = QueryMsg::OwnersSnapshot { at_height, start_after: None, limit: None };
+            from_binary::<OwnersSnapshotResponse>(&query(deps, mock_env(), msg).unwrap()).unwrap().owners
+        };
+        let owner = |name: &str, owner: &str| NameOwner { name: name.to_string(), owner: Addr::unchecked(owner) };
+
+        let register = ExecuteMsg::Register {
+            name: "alice".to_string(),
+            bio: "".to_string(),
+            website: "".to_string(),
+            round_up: false,
+            referrer: None,
+        };
+        execute(deps.as_mut(), at(100), mock_info("alice_key", &[]), register)
+            .expect("contract successfully handles Register message");
+        let transfer = ExecuteMsg::Transfer {
+            name: "alice".to_string(),
+            to: "bob_key".to_string(),
+            round_up: false,
+            memo: None,
+        };
+        execute(deps.as_mut(), at(200), mock_info("alice_key", &[]), transfer)
+            .expect("contract successfully handles Transfer message");
+        execute(deps.as_mut(), at(300), mock_info("bob_key", &[]), ExecuteMsg::Release { name: "alice".to_string() })
+            .expect("contract successfully handles Release message");
+
+        // a height sees the state before the executes of its own block
+        assert_eq!(snapshot(deps.as_ref(), 100), vec![]);
+        assert_eq!(snapshot(deps.as_ref(), 101), vec![owner("alice", "alice_key")]);
+        assert_eq!(snapshot(deps.as_ref(), 250), vec![owner("alice", "bob_key")]);
+        assert_eq!(snapshot(deps.as_ref(), 301), vec![]);
+    }
+
+    #[test]
+    fn invariants_catch_corrupted_state() {
+        let mut deps = mock_dependencies();
+        mock_init_with_price(deps.as_mut(), coin(10, "token"), coin(10, "token"));
+        let msg = ExecuteMsg::SetTreasury {
+            fee_collector: None,
+            revenue_split: vec![RevenueShare { address: "dev_key".to_string(), percent: 50 }],
+        };
+        execute(deps.as_mut(), mock_env(), mock_info("creator", &[]), msg)
+            .expect("contract successfully handles SetTreasury message");
+        mock_alice_registers_name(deps.as_mut(), &coins(10, "token"));
+        let invariants = |deps: Deps| {
+            from_binary::<InvariantsResponse>(&query(deps, mock_env(), QueryMsg::Invariants {}).unwrap()).unwrap()
+        };
+
+        // the share set aside for dev_key must still be in the balance
+        let report = invariants(deps.as_ref());
+        assert!(!report.holds);
+        assert_eq!(report.violations, vec!["escrow holds 5token but the balance is only 0token".to_string()]);
+        deps.querier.update_balance(MOCK_CONTRACT_ADDR, coins(10, "token"));
+        assert_eq!(invariants(deps.as_ref()), InvariantsResponse { holds: true, violations: vec![] });
+
+        TOTAL_NAMES.save(deps.as_mut().storage, &3).unwrap();
+        let report = invariants(deps.as_ref());
+        assert_eq!(report.violations, vec!["total_names is 3 but 1 names are registered".to_string()]);
+    }
+
+    #[test]
+    fn subnames_resolve_under_their_parent() {
+        let mut deps = mock_dependencies();
+        mock_init_no_price(deps.as_mut());
+        mock_alice_registers_name(deps.as_mut(), &[]);
+
+        let register = ExecuteMsg::RegisterSubname {
+            parent: "alice".to_string(),
+            label: "pay".to_string(),
+            owner: "wallet_key".to_string(),
+            bio: "".to_string(),
+            website: "".to_string(),
+        };
+        let res = execute(deps.as_mut(), mock_env(), mock_info("bob_key", &[]), register.clone());
+        match res {
+            Ok(_) => panic!("Must return error"),
+            Err(ContractError::Unauthorized {}) => {}
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        }
+        execute(deps.as_mut(), mock_env(), mock_info("alice_key", &[]), register.clone())
+            .expect("contract successfully handles RegisterSubname message");
+        assert_name_owner(deps.as_ref(), "pay.alice", "wallet_key");
+        assert_name_owner(deps.as_ref(), "alice", "alice_key");
+        assert_eq!(query_record_at(deps.as_ref(), "blog.alice", 0).address, None);
+
+        let remove = ExecuteMsg::RemoveSubname { parent: "alice".to_string(), label: "pay".to_string() };
+        execute(deps.as_mut(), mock_env(), mock_info("alice_key", &[]), remove.clone())
+            .expect("contract successfully handles RemoveSubname message");
+        assert_eq!(query_record_at(deps.as_ref(), "pay.alice", 0).address, None);
+        let res = execute(deps.as_mut(), mock_env(), mock_info("alice_key", &[]), remove);
+        match res {
+            Ok(_) => panic!("Must return error"),
+            Err(ContractError::SubnameNotExists { name }) => assert_eq!(name, "pay.alice"),
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        }
+
+        // subnames set up by the previous owner don't survive a transfer
+        execute(deps.as_mut(), mock_env(), mock_info("alice_key", &[]), register)
+            .expect("contract successfully handles RegisterSubname message");
+        let msg = ExecuteMsg::Transfer {
+            name: "alice".to_string(),
+            to: "bob_key".to_string(),
+            round_up: false,
+            memo: None,
+        };
+        execute(deps.as_mut(), mock_env(), mock_info("alice_key", &[]), msg)
+            .expect("contract successfully handles Transfer message");
+        assert_eq!(query_record_at(deps.as_ref(), "pay.alice", 0).address, None);
+    }
+
+    #[test]
+    fn fee_calculator_overrides_built_in_prices() {
+        let mut deps = mock_dependencies();
+        mock_init_with_price(deps.as_mut(), coin(2, "token"), coin(2, "token"));
+
+        let msg = ExecuteMsg::SetFeeCalculator { address: Some("fee_contract".to_string()) };
+        let res = execute(deps.as_mut(), mock_env(), mock_info("anyone", &[]), msg.clone());
+        match res {
+            Ok(_) => panic!("Must return error"),
+            Err(ContractError::Unauthorized {}) => {}
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        }
+        execute(deps.as_mut(), mock_env(), mock_info("creator", &[]), msg)
+            .expect("contract successfully handles SetFeeCalculator message");
+
+        // the calculator charges names by the sender, transfers are free
+        deps.querier.update_wasm(|query| match query {
+            WasmQuery::Smart { contract_addr, msg } if contract_addr == "fee_contract" => {
+                let FeeQueryMsg::Fee { action, sender, .. } = from_binary(msg).unwrap();
+                let prices = match (action.as_str(), sender.as_str()) {
+                    ("register", "alice_key") => coins(7, "token"),
+                    ("register", _) => coins(3, "token"),
+                    _ => vec![],
+                };
+                SystemResult::Ok(ContractResult::Ok(to_binary(&FeeResponse { prices }).unwrap()))
+            }
+            _ => panic!("Unexpected query"),
+        });
+
+        let msg = ExecuteMsg::Register {
+            name: "alice".to_string(),
+            bio: "".to_string(),
+            website: "".to_string(),
+            round_up: false,
+            referrer: None,
+        };
+        let res = execute(deps.as_mut(), mock_env(), mock_info("alice_key", &coins(2, "token")), msg.clone());
+        match res {
+            Ok(_) => panic!("Must return error"),
+            Err(ContractError::InsufficientFundsSend {}) => {}
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        }
+        let res = execute(deps.as_mut(), mock_env(), mock_info("alice_key", &coins(9, "token")), msg)
+            .expect("contract successfully handles Register message");
+        // the surplus above the calculated price is refunded
+        assert_eq!(
+            res.messages[0].msg,
+            CosmosMsg::Bank(BankMsg::Send { to_address: "alice_key".to_string(), amount: coins(2, "token") })
+        );
+
+        let msg = ExecuteMsg::Transfer {
+            name: "alice".to_string(),
+            to: "bob_key".to_string(),
+            round_up: false,
+            memo: None,
+        };
+        execute(deps.as_mut(), mock_env(), mock_info("alice_key", &[]), msg)
+            .expect("contract successfully handles Transfer message");
+        assert_name_owner(deps.as_ref(), "alice", "bob_key");
+
+        // without a calculator the built-in price applies again
+        let msg = ExecuteMsg::SetFeeCalculator { address: None };
+        execute(deps.as_mut(), mock_env(), mock_info("creator", &[]), msg)
+            .expect("contract successfully handles SetFeeCalculator message");
+        let config: ConfigResponse =
+            from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap()).unwrap();
+        assert_eq!(config.fee_calculator, None);
+        let msg = ExecuteMsg::Transfer {
+            name: "alice".to_string(),
+            to: "alice_key".to_string(),
+            round_up: false,
+            memo: None,
+        };
+        let res = execute(deps.as_mut(), mock_env(), mock_info("bob_key", &[]), msg);
+        match res {
+            Ok(_) => panic!("Must return error"),
+            Err(ContractError::InsufficientFundsSend {}) => {}
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        }
+    }
+
+    #[test]
+    fn resolve_records_keeps_input_order() {
+        let mut deps = mock_dependencies();
+        mock_init_no_price(deps.as_mut());
+        mock_alice_registers_name(deps.as_mut(), &[]);
+
+        let names = vec!["bob".to_string(), "alice".to_string(), "bob".to_string()];
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::ResolveRecords { names }).unwrap();
+        let res: ResolveRecordsResponse = from_binary(&res).unwrap();
+        assert_eq!(res.records.len(), 3);
+        assert_eq!(res.records[0], None);
+        assert_eq!(res.records[1].as_ref().unwrap().address, Some("alice_key".to_string()));
+        assert_eq!(res.records[2], None);
+
+        let names = (0..31).map(|i| format!("name{}", i)).collect();
+        assert!(query(deps.as_ref(), mock_env(), QueryMsg::ResolveRecords { names }).is_err());
+    }
+
+    #[test]
+    fn ibc_channels_resolve_names() {
+        let mut deps = mock_dependencies();
+        mock_init_no_price(deps.as_mut());
+        mock_alice_registers_name(deps.as_mut(), &[]);
+
+        // channels have to be whitelisted first
+        let open = mock_ibc_channel_open_init("channel-3", IbcOrder::Unordered, IBC_VERSION);
+        match ibc_channel_open(deps.as_mut(), mock_env(), open.clone()) {
+            Ok(_) => panic!("Must return error"),
+            Err(ContractError::IbcChannelNotAllowed { channel }) => assert_eq!(channel, "channel-3"),
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        }
+        let msg = ExecuteMsg::SetIbcChannels { channels: vec!["channel-3".to_string()] };
+        match execute(deps.as_mut(), mock_env(), mock_info("alice_key", &[]), msg.clone()) {
+            Ok(_) => panic!("Must return error"),
+            Err(ContractError::Unauthorized {}) => {}
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        }
+        execute(deps.as_mut(), mock_env(), mock_info("creator", &[]), msg)
+            .expect("contract successfully handles SetIbcChannels message");
+
+        ibc_channel_open(deps.as_mut(), mock_env(), open).unwrap();
+        let ordered = mock_ibc_channel_open_init("channel-3", IbcOrder::Ordered, IBC_VERSION);
+        match ibc_channel_open(deps.as_mut(), mock_env(), ordered) {
+            Ok(_) => panic!("Must return error"),
+            Err(ContractError::InvalidIbcOrder {}) => {}
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        }
+        let connect = mock_ibc_channel_connect_ack("channel-3", IbcOrder::Unordered, "ics20-1");
+        match ibc_channel_connect(deps.as_mut(), mock_env(), connect) {
+            Ok(_) => panic!("Must return error"),
+            Err(ContractError::InvalidIbcVersion { version }) => assert_eq!(version, "ics20-1"),
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        }
+        let connect = mock_ibc_channel_connect_ack("channel-3", IbcOrder::Unordered, IBC_VERSION);
+        ibc_channel_connect(deps.as_mut(), mock_env(), connect).unwrap();
+
+        let packet = ResolvePacket::Resolve { name: "alice".to_string() };
+        let res = ibc_packet_receive(deps.as_mut(), mock_env(), mock_ibc_packet_recv("channel-3", &packet).unwrap())
+            .unwrap();
+        match from_binary(&res.acknowledgement).unwrap() {
+            ResolveAck::Result(record) => assert_eq!(record.address, Some("alice_key".to_string())),
+            ResolveAck::Error(err) => panic!("Unexpected error: {}", err),
+        }
+
+        // packets of channels no longer whitelisted are acknowledged with an error
+        let res = ibc_packet_receive(deps.as_mut(), mock_env(), mock_ibc_packet_recv("channel-4", &packet).unwrap())
+            .unwrap();
+        assert!(matches!(from_binary(&res.acknowledgement).unwrap(), ResolveAck::Error(_)));
+    }
+
+    #[test]
+    fn holds_keep_names_for_the_holder() {
+        let mut deps = mock_dependencies();
+        mock_init_with_price(deps.as_mut(), coin(5, "token"), coin(5, "token"));
+
+        let reserve = ExecuteMsg::Reserve { name: "alice".to_string() };
+        match execute(deps.as_mut(), mock_env(), mock_info("alice_key", &coins(3, "token")), reserve.clone()) {
+            Ok(_) => panic!("Must return error"),
+            Err(ContractError::HoldsDisabled {}) => {}
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        }
+        let msg = ExecuteMsg::SetHoldTerms { hold_deposit: Some(coin(3, "token")), hold_duration: 100 };
+        execute(deps.as_mut(), mock_env(), mock_info("creator", &[]), msg)
+            .expect("contract successfully handles SetHoldTerms message");
+        execute(deps.as_mut(), mock_env(), mock_info("alice_key", &coins(3, "token")), reserve.clone())
+            .expect("contract successfully handles Reserve message");
+
+        // nobody else can reserve or register the name while it is held
+        match execute(deps.as_mut(), mock_env_at(99), mock_info("bob_key", &coins(3, "token")), reserve) {
+            Ok(_) => panic!("Must return error"),
+            Err(ContractError::NameHeld { name, .. }) => assert_eq!(name, "alice"),
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        }
+        let register = |name: &str| ExecuteMsg::Register {
+            name: name.to_string(),
+            bio: "".to_string(),
+            website: "".to_string(),
+            round_up: false,
+            referrer: None,
+        };
+        match execute(deps.as_mut(), mock_env_at(99), mock_info("bob_key", &coins(5, "token")), register("alice")) {
+            Ok(_) => panic!("Must return error"),
+            Err(ContractError::NameHeld { .. }) => {}
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        }
+        let names = vec!["alice".to_string()];
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::CheckAvailability { names }).unwrap();
+        let res: AvailabilityResponse = from_binary(&res).unwrap();
+        assert_eq!(res.names[0].status, AvailabilityStatus::Held);
+
+        // the deposit pays part of the holder's registration
+        execute(deps.as_mut(), mock_env_at(99), mock_info("alice_key", &coins(2, "token")), register("alice"))
+            .expect("contract successfully handles Register message");
+        assert_name_owner(deps.as_ref(), "alice", "alice_key");
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::Hold { name: "alice".to_string() }).unwrap();
+        assert_eq!(from_binary::<HoldResponse>(&res).unwrap().hold, None);
+
+        // an expired hold is forfeited to whoever registers the name next
+        let reserve = ExecuteMsg::Reserve { name: "bobby".to_string() };
+        execute(deps.as_mut(), mock_env(), mock_info("alice_key", &coins(3, "token")), reserve)
+            .expect("contract successfully handles Reserve message");
+        execute(deps.as_mut(), mock_env_at(100), mock_info("bob_key", &coins(5, "token")), register("bobby"))
+            .expect("contract successfully handles Register message");
+        assert_name_owner(deps.as_ref(), "bobby", "bob_key");
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::Invariants {}).unwrap();
+        assert!(from_binary::<InvariantsResponse>(&res).unwrap().holds);
+    }
+
+    #[test]
+    fn executes_emit_indexable_attributes() {
+        let mut deps = mock_dependencies();
+        mock_init_with_price(deps.as_mut(), coin(5, "token"), coin(3, "token"));
+
+        let msg = ExecuteMsg::Register {
+            name: "alice".to_string(),
+            bio: "".to_string(),
+            website: "".to_string(),
+            round_up: false,
+            referrer: None,
+        };
+        let res = execute(deps.as_mut(), mock_env(), mock_info("alice_key", &coins(5, "token")), msg)
+            .expect("contract successfully handles Register message");
+        assert_eq!(
+            res.attributes,
+            vec![
+                attr("action", "register"),
+                attr("name", "alice"),
+                attr("owner", "alice_key"),
+                attr("fees", "5token"),
+                attr("expires_at", ""),
+            ]
+        );
+
+        let msg = ExecuteMsg::Edit {
+            name: "alice".to_string(),
+            bio: "hello".to_string(),
+            website: "".to_string(),
+            round_up: false,
+        };
+        let res = execute(deps.as_mut(), mock_env(), mock_info("alice_key", &[]), msg)
+            .expect("contract successfully handles Edit message");
+        assert_eq!(
+            res.attributes,
+            vec![attr("action", "edit"), attr("name", "alice"), attr("owner", "alice_key"), attr("fees", "")]
+        );
+
+        let msg = ExecuteMsg::Transfer {
+            name: "alice".to_string(),
+            to: "bob_key".to_string(),
+            round_up: false,
+            memo: None,
+        };
+        let res = execute(deps.as_mut(), mock_env(), mock_info("alice_key", &coins(3, "token")), msg)
+            .expect("contract successfully handles Transfer message");
+        assert_eq!(
+            res.attributes,
+            vec![
+                attr("action", "transfer"),
+                attr("name", "alice"),
+                attr("owner", "alice_key"),
+                attr("new_owner", "bob_key"),
+                attr("fees", "3token"),
+            ]
+        );
+
+        let msg = ExecuteMsg::Editconf {
+            purchase_price: vec![coin(5, "token"), coin(2, "earth")],
+            transfer_price: vec![],
+            edit_price: coins(1, "token"),
+            length_prices: None,
+            name_policy: None,
+        };
+        let res = execute(deps.as_mut(), mock_env(), mock_info("creator", &coins(3, "token")), msg)
+            .expect("contract successfully handles Editconf message");
+        assert_eq!(
+            res.attributes,
+            vec![
+                attr("action", "edit_conf"),
+                attr("purchase_price", "5token,2earth"),
+                attr("transfer_price", ""),
+                attr("edit_price", "1token"),
+            ]
+        );
+    }
+
+    #[test]
+    fn rebuild_indexes_repairs_owner_index() {
+        let mut deps = mock_dependencies();
+        mock_init_no_price(deps.as_mut());
+        mock_alice_registers_name(deps.as_mut(), &[]);
+        let msg = ExecuteMsg::Register {
+            name: "bob".to_string(),
+            bio: "".to_string(),
+            website: "".to_string(),
+            round_up: false,
+            referrer: None,
+        };
+        execute(deps.as_mut(), mock_env(), mock_info("bob_key", &[]), msg)
+            .expect("contract successfully handles Register message");
+
+        // the index lost alice's entry and lists her name for bob instead
+        let mut record = name_resolver().load(deps.as_ref().storage, b"alice").unwrap();
+        name_resolver().idx.owner.remove(deps.as_mut().storage, b"alice", &record).unwrap();
+        record.owner = Addr::unchecked("bob_key");
+        name_resolver().idx.owner.save(deps.as_mut().storage, b"alice", &record).unwrap();
+        let holds = |deps: Deps| {
+            from_binary::<InvariantsResponse>(&query(deps, mock_env(), QueryMsg::Invariants {}).unwrap()).unwrap().holds
+        };
+        assert!(!holds(deps.as_ref()));
+
+        let msg = ExecuteMsg::RebuildIndexes { start_after: None, limit: Some(1) };
+        match execute(deps.as_mut(), mock_env(), mock_info("alice_key", &[]), msg.clone()) {
+            Ok(_) => panic!("Must return error"),
+            Err(ContractError::Unauthorized {}) => {}
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        }
+        let res = execute(deps.as_mut(), mock_env(), mock_info("creator", &[]), msg)
+            .expect("contract successfully handles RebuildIndexes message");
+        assert!(res.attributes.contains(&attr("next", "alice")));
+        let msg = SudoMsg::RebuildIndexes { start_after: Some("alice".to_string()), limit: Some(1) };
+        let res = sudo(deps.as_mut(), mock_env(), msg).unwrap();
+        assert!(res.attributes.contains(&attr("dropped", "1")));
+        assert!(res.attributes.contains(&attr("next", "bob")));
+        assert!(holds(deps.as_ref()));
+    }
+
+    #[test]
+    fn editconf_changes_name_policy() {
+        let mut deps = mock_dependencies();
+        mock_init_no_price(deps.as_mut());
+
+        let register = |name: &str, bio: &str| ExecuteMsg::Register {
+            name: name.to_string(),
+            bio: bio.to_string(),
+            website: "".to_string(),
+            round_up: false,
+            referrer: None,
+        };
+        match execute(deps.as_mut(), mock_env(), mock_info("alice_key", &[]), register("a_b", "")) {
+            Ok(_) => panic!("Must return error"),
+            Err(ContractError::InvalidCharacter { c }) => assert_eq!(c, '_'),
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        }
+
+        let policy = NamePolicy {
+            min_name_length: 2,
+            max_bio_length: 5,
+            charset: Charset { allow_underscore: true, ..Charset::default() },
+            ..NamePolicy::default()
+        };
+        let msg = ExecuteMsg::Editconf {
+            purchase_price: vec![],
+            transfer_price: vec![],
+            edit_price: vec![],
+            length_prices: None,
+            name_policy: Some(policy.clone()),
+        };
+        execute(deps.as_mut(), mock_env(), mock_info("creator", &[]), msg)
+            .expect("contract successfully handles Editconf message");
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap();
+        assert_eq!(from_binary::<ConfigResponse>(&res).unwrap().name_policy, policy);
+
+        execute(deps.as_mut(), mock_env(), mock_info("alice_key", &[]), register("a_b", ""))
+            .expect("contract successfully handles Register message");
+        execute(deps.as_mut(), mock_env(), mock_info("bob_key", &[]), register("bo", ""))
+            .expect("contract successfully handles Register message");
+        match execute(deps.as_mut(), mock_env(), mock_info("bob_key", &[]), register("bob", "hello!")) {
+            Ok(_) => panic!("Must return error"),
+            Err(ContractError::BioTooLong { max_length, .. }) => assert_eq!(max_length, 5),
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        }
+        assert_name_owner(deps.as_ref(), "a_b", "alice_key");
+    }
+
+    #[test]
+    fn gateways_sign_resolution_payloads() {
+        use k256::ecdsa::signature::Signer;
+        use k256::ecdsa::{Signature, SigningKey};
+
+        let mut deps = mock_dependencies();
+        mock_init_no_price(deps.as_mut());
+        mock_alice_registers_name(deps.as_mut(), &[]);
+
+        let gateway = SigningKey::from_bytes(&[7u8; 32]).unwrap();
+        let pubkey = Binary::from(gateway.verifying_key().to_bytes().to_vec());
+        let msg = ExecuteMsg::SetGatewayKey { id: "gw1".to_string(), pubkey: Some(Binary::from(vec![1u8; 33])) };
+        match execute(deps.as_mut(), mock_env(), mock_info("creator", &[]), msg) {
+            Ok(_) => panic!("Must return error"),
+            Err(ContractError::InvalidGatewayKey { id }) => assert_eq!(id, "gw1"),
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        }
+        let msg = ExecuteMsg::SetGatewayKey { id: "gw1".to_string(), pubkey: Some(pubkey.clone()) };
+        match execute(deps.as_mut(), mock_env(), mock_info("alice_key", &[]), msg.clone()) {
+            Ok(_) => panic!("Must return error"),
+            Err(ContractError::Unauthorized {}) => {}
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        }
+        execute(deps.as_mut(), mock_env(), mock_info("creator", &[]), msg)
+            .expect("contract successfully handles SetGatewayKey message");
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::GatewayKeys {}).unwrap();
+        assert_eq!(from_binary::<GatewayKeysResponse>(&res).unwrap().keys[0].pubkey, pubkey);
+
+        // the gateway signs the payload off-chain and clients check it against the registered key
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::ResolvePayload { name: "alice".to_string() }).unwrap();
+        let res: ResolvePayloadResponse = from_binary(&res).unwrap();
+        let payload: GatewayPayload = from_binary(&res.payload).unwrap();
+        assert_eq!(payload.record.address, Some("alice_key".to_string()));
+        assert_eq!(res.digest.to_vec(), Sha256::digest(res.payload.as_slice()).to_vec());
+        let signature: Signature = gateway.sign(res.payload.as_slice());
+        let verify = |deps: Deps, payload: &Binary| {
+            let msg = QueryMsg::VerifyGatewaySignature {
+                id: "gw1".to_string(),
+                payload: payload.clone(),
+                signature: Binary::from(signature.as_ref().to_vec()),
+            };
+            from_binary::<VerifyGatewaySignatureResponse>(&query(deps, mock_env(), msg).unwrap()).unwrap().valid
+        };
+        assert!(verify(deps.as_ref(), &res.payload));
+        assert!(!verify(deps.as_ref(), &Binary::from(b"forged".to_vec())));
+
+        // signatures of a revoked key no longer verify
+        let msg = ExecuteMsg::SetGatewayKey { id: "gw1".to_string(), pubkey: None };
+        execute(deps.as_mut(), mock_env(), mock_info("creator", &[]), msg)
+            .expect("contract successfully handles SetGatewayKey message");
+        assert!(!verify(deps.as_ref(), &res.payload));
+    }
+
+    #[test]
+    fn burn_fees_burns_what_is_paid() {
+        let mut deps = mock_dependencies();
+        let msg = InstantiateMsg {
+            admin: None,
+            purchase_price: coins(10, "token"),
+            transfer_price: coins(5, "token"),
+            edit_price: vec![],
+            max_supply: None,
+            registration_duration: None,
+            grace_period: None,
+            renewal_price: None,
+            preset: None,
+            launch_schedule: vec![],
+            burn_fees: true,
+        };
+        instantiate(deps.as_mut(), mock_env(), mock_info("creator", &[]), msg)
+            .expect("contract successfully handles InstantiateMsg");
+
+        // the price is burned and the overpayment goes back to the sender
+        let msg = ExecuteMsg::Register {
+            name: "alice".to_string(),
+            bio: "".to_string(),
+            website: "".to_string(),
+            round_up: false,
+            referrer: None,
+        };
+        let res = execute(deps.as_mut(), mock_env(), mock_info("alice_key", &coins(12, "token")), msg)
+            .expect("contract successfully handles Register message");
+        assert_eq!(
+            res.messages.iter().map(|msg| msg.msg.clone()).collect::<Vec<_>>(),
+            vec![
+                CosmosMsg::Bank(BankMsg::Send { to_address: "alice_key".to_string(), amount: coins(2, "token") }),
+                CosmosMsg::Bank(BankMsg::Burn { amount: coins(10, "token") }),
+            ]
+        );
+
+        let msg = ExecuteMsg::Transfer {
+            name: "alice".to_string(),
+            to: "bob_key".to_string(),
+            round_up: false,
+            memo: None,
+        };
+        let res = execute(deps.as_mut(), mock_env(), mock_info("alice_key", &coins(5, "token")), msg)
+            .expect("contract successfully handles Transfer message");
+        assert_eq!(res.messages[0].msg, CosmosMsg::Bank(BankMsg::Burn { amount: coins(5, "token") }));
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap();
+        assert!(from_binary::<ConfigResponse>(&res).unwrap().burn_fees);
+    }
+
+    #[test]
+    fn name_history_records_every_change() {
+        let mut deps = mock_dependencies();
+        mock_init_no_price(deps.as_mut());
+        mock_alice_registers_name(deps.as_mut(), &[]);
+
+        let msg = ExecuteMsg::Edit {
+            name: "alice".to_string(),
+            bio: "hello".to_string(),
+            website: "".to_string(),
+            round_up: false,
+        };
+        execute(deps.as_mut(), mock_env(), mock_info("alice_key", &[]), msg)
+            .expect("contract successfully handles Edit message");
+        let msg = ExecuteMsg::Transfer {
+            name: "alice".to_string(),
+            to: "bob_key".to_string(),
+            round_up: false,
+            memo: None,
+        };
+        execute(deps.as_mut(), mock_env(), mock_info("alice_key", &[]), msg)
+            .expect("contract successfully handles Transfer message");
+        let msg = ExecuteMsg::Release { name: "alice".to_string() };
+        execute(deps.as_mut(), mock_env(), mock_info("bob_key", &[]), msg)
+            .expect("contract successfully handles Release message");
+
+        // the history outlives the name
+        let history = |deps: Deps, start_after| {
+            let msg = QueryMsg::NameHistory { name: "alice".to_string(), start_after, limit: Some(2) };
+            from_binary::<NameHistoryResponse>(&query(deps, mock_env(), msg).unwrap()).unwrap().entries
+        };
+        let first = history(deps.as_ref(), None);
+        let alice = Addr::unchecked("alice_key");
+        let bob = Addr::unchecked("bob_key");
+        assert_eq!(first[0].event, HistoryEvent::Registered { owner: alice.clone() });
+        assert_eq!(first[1].event, HistoryEvent::Edited {});
+        assert_eq!(first[1].height, mock_env().block.height);
+        let rest = history(deps.as_ref(), Some(first[1].seq));
+        assert_eq!(rest[0].event, HistoryEvent::Transferred { from: alice.clone(), to: bob.clone() });
+        assert_eq!(rest[0].actor, alice);
+        assert_eq!(rest[1].event, HistoryEvent::Removed { reason: RemovalReason::Released });
+        assert_eq!(rest[1].actor, bob);
+        assert!(history(deps.as_ref(), Some(rest[1].seq)).is_empty());
+    }
+
+    #[test]
+    fn referrers_get_a_share_of_registrations() {
+        let mut deps = mock_dependencies();
+        mock_init_with_price(deps.as_mut(), coin(100, "token"), coin(0, "token"));
+        let msg = ExecuteMsg::SetReferralPercent { referral_percent: 101 };
+        match execute(deps.as_mut(), mock_env(), mock_info("creator", &[]), msg) {
+            Ok(_) => panic!("Must return error"),
+            Err(ContractError::InvalidReferralPercent {}) => {}
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        }
+        let msg = ExecuteMsg::SetReferralPercent { referral_percent: 10 };
+        execute(deps.as_mut(), mock_env(), mock_info("creator", &[]), msg)
+            .expect("contract successfully handles SetReferralPercent message");
+
+        let register = |referrer: &str| ExecuteMsg::Register {
+            name: "alice".to_string(),
+            bio: "".to_string(),
+            website: "".to_string(),
+            round_up: false,
+            referrer: Some(referrer.to_string()),
+        };
+        match execute(deps.as_mut(), mock_env(), mock_info("alice_key", &coins(100, "token")), register("alice_key")) {
+            Ok(_) => panic!("Must return error"),
+            Err(ContractError::SelfReferral {}) => {}
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        }
+        let res = execute(deps.as_mut(), mock_env(), mock_info("alice_key", &coins(100, "token")), register("bob_key"))
+            .expect("contract successfully handles Register message");
+        assert_eq!(
+            res.messages[0].msg,
+            CosmosMsg::Bank(BankMsg::Send { to_address: "bob_key".to_string(), amount: coins(10, "token") })
+        );
+        assert!(res.attributes.contains(&attr("referrer", "bob_key")));
+        assert!(res.attributes.contains(&attr("referral_reward", "10token")));
+        assert!(res.attributes.contains(&attr("fees", "100token")));
+        assert_name_owner(deps.as_ref(), "alice", "alice_key");
+    }
+
+    #[test]
+    fn expired_names_cost_a_decaying_premium() {
+        let mut deps = mock_dependencies();
+        let msg = InstantiateMsg {
+            admin: None,
+            purchase_price: coins(10, "token"),
+            transfer_price: vec![],
+            edit_price: vec![],
+            max_supply: None,
+            registration_duration: Some(100),
+            grace_period: Some(50),
+            renewal_price: None,
+            preset: None,
+            launch_schedule: vec![],
+            burn_fees: false,
+        };
+        instantiate(deps.as_mut(), mock_env(), mock_info("creator", &[]), msg)
+            .expect("contract successfully handles InstantiateMsg");
+        let msg = ExecuteMsg::SetPremiumDecay { premium_multiplier: 11, premium_window: 100 };
+        execute(deps.as_mut(), mock_env(), mock_info("creator", &[]), msg)
+            .expect("contract successfully handles SetPremiumDecay message");
+        mock_alice_registers_name(deps.as_mut(), &coins(10, "token"));
+
+        let quote = |deps: Deps, seconds| {
+            let msg = QueryMsg::RegistrationPrice { name: "alice".to_string(), duration: None };
+            from_binary::<RegistrationPriceResponse>(&query(deps, mock_env_at(seconds), msg).unwrap()).unwrap()
+        };
+        // the premium starts once the grace period is over at 150 and is gone at 250
+        assert_eq!(quote(deps.as_ref(), 149).status, AvailabilityStatus::InGracePeriod);
+        assert_eq!(quote(deps.as_ref(), 150).prices, coins(110, "token"));
+        let halfway = quote(deps.as_ref(), 200);
+        assert_eq!(halfway.prices, coins(60, "token"));
+        assert_eq!(halfway.premium_ends_at, Some(mock_env_at(250).block.time));
+        assert_eq!(quote(deps.as_ref(), 250).premium_ends_at, None);
+
+        let msg = ExecuteMsg::Register {
+            name: "alice".to_string(),
+            bio: "".to_string(),
+            website: "".to_string(),
+            round_up: false,
+            referrer: None,
+        };
+        match execute(deps.as_mut(), mock_env_at(200), mock_info("bob_key", &coins(59, "token")), msg.clone()) {
+            Ok(_) => panic!("Must return error"),
+            Err(ContractError::InsufficientFundsSend {}) => {}
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        }
+        execute(deps.as_mut(), mock_env_at(200), mock_info("bob_key", &coins(60, "token")), msg)
+            .expect("contract successfully handles Register message");
+        assert_name_owner(deps.as_ref(), "alice", "bob_key");
+        assert_eq!(quote(deps.as_ref(), 200).premium_ends_at, None);
+    }
+
+    #[test]
+    fn registration_price_quotes_whole_periods() {
+        let mut deps = mock_dependencies();
+        let msg = InstantiateMsg {
+            admin: None,
+            purchase_price: coins(10, "token"),
+            transfer_price: vec![],
+            edit_price: vec![],
+            max_supply: None,
+            registration_duration: Some(100),
+            grace_period: None,
+            renewal_price: Some(coin(2, "token")),
+            preset: None,
+            launch_schedule: vec![],
+            burn_fees: false,
+        };
+        instantiate(deps.as_mut(), mock_env(), mock_info("creator", &[]), msg)
+            .expect("contract successfully handles InstantiateMsg");
+        let msg = ExecuteMsg::ReserveNames { names: vec!["huahua".to_string()] };
+        execute(deps.as_mut(), mock_env(), mock_info("creator", &[]), msg)
+            .expect("contract successfully handles ReserveNames message");
+
+        let quote = |deps: Deps, name: &str, duration| {
+            let msg = QueryMsg::RegistrationPrice { name: name.to_string(), duration };
+            from_binary::<RegistrationPriceResponse>(&query(deps, mock_env(), msg).unwrap()).unwrap()
+        };
+        // 250 seconds take three periods, the first is bought and the others renewed
+        let res = quote(deps.as_ref(), "alice", Some(250));
+        assert_eq!((res.status, res.renew, res.periods), (AvailabilityStatus::Available, false, 3));
+        assert_eq!(res.prices, coins(14, "token"));
+        assert_eq!(quote(deps.as_ref(), "alice", None).prices, coins(10, "token"));
+        assert_eq!(quote(deps.as_ref(), "huahua", None).status, AvailabilityStatus::Reserved);
+
+        mock_alice_registers_name(deps.as_mut(), &coins(10, "token"));
+        let res = quote(deps.as_ref(), "alice", Some(200));
+        assert_eq!((res.status, res.renew, res.periods), (AvailabilityStatus::Taken, true, 2));
+        assert_eq!(res.prices, coins(4, "token"));
+    }
+
+    #[test]
+    fn governance_freezes_and_moves_names() {
+        let mut deps = mock_dependencies();
+        mock_init_no_price(deps.as_mut());
+        mock_alice_registers_name(deps.as_mut(), &[]);
+
+        // a misspelled name is caught rather than frozen
+        match sudo(deps.as_mut(), mock_env(), SudoMsg::FreezeName { name: "alcie".to_string() }) {
+            Ok(_) => panic!("Must return error"),
+            Err(ContractError::NameNotExists { name }) => assert_eq!(name, "alcie"),
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        }
+        match sudo(deps.as_mut(), mock_env(), SudoMsg::UnfreezeName { name: "alice".to_string() }) {
+            Ok(_) => panic!("Must return error"),
+            Err(ContractError::NameNotFrozen { name }) => assert_eq!(name, "alice"),
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        }
+        let msg = SudoMsg::FreezeName { name: "alice".to_string() };
+        sudo(deps.as_mut(), mock_env(), msg).expect("contract successfully handles FreezeName message");
+        let transfer = ExecuteMsg::Transfer {
+            name: "alice".to_string(),
+            to: "bob_key".to_string(),
+            round_up: false,
+            memo: None,
+        };
+        let release = ExecuteMsg::ReleaseBatch { names: vec!["alice".to_string()] };
+        for msg in [transfer.clone(), release] {
+            match execute(deps.as_mut(), mock_env(), mock_info("alice_key", &[]), msg) {
+                Ok(_) => panic!("Must return error"),
+                Err(ContractError::NameFrozen { name }) => assert_eq!(name, "alice"),
+                Err(e) => panic!("Unexpected error: {:?}", e),
+            }
+        }
+        // other names are not affected
+        let msg = ExecuteMsg::Register {
+            name: "bobby".to_string(),
+            bio: "".to_string(),
+            website: "".to_string(),
+            round_up: false,
+            referrer: None,
+        };
+        execute(deps.as_mut(), mock_env(), mock_info("bob_key", &[]), msg)
+            .expect("contract successfully handles Register message");
+
+        let msg = SudoMsg::ForceTransfer { name: "alice".to_string(), to: "carol_key".to_string() };
+        sudo(deps.as_mut(), mock_env(), msg).expect("contract successfully handles ForceTransfer message");
+        assert_name_owner(deps.as_ref(), "alice", "carol_key");
+        let msg = SudoMsg::UnfreezeName { name: "alice".to_string() };
+        sudo(deps.as_mut(), mock_env(), msg).expect("contract successfully handles UnfreezeName message");
+        execute(deps.as_mut(), mock_env(), mock_info("carol_key", &[]), transfer)
+            .expect("contract successfully handles Transfer message");
+
+        // governance sets parameters, a frozen config only takes a new admin
+        let update = ConfigUpdate { purchase_price: Some(coins(5, "token")), ..ConfigUpdate::default() };
+        sudo(deps.as_mut(), mock_env(), SudoMsg::UpdateConfig(update))
+            .expect("contract successfully handles UpdateConfig message");
+        execute(deps.as_mut(), mock_env(), mock_info("creator", &[]), ExecuteMsg::FreezeConfig {})
+            .expect("contract successfully handles FreezeConfig message");
+        let updates = [
+            ConfigUpdate { purchase_price: Some(coins(9, "token")), ..ConfigUpdate::default() },
+            ConfigUpdate { renewal_price: Some(coin(9, "token")), ..ConfigUpdate::default() },
+            ConfigUpdate { grace_period: Some(0), ..ConfigUpdate::default() },
+            ConfigUpdate { frozen: Some(false), ..ConfigUpdate::default() },
+        ];
+        for update in updates {
+            match sudo(deps.as_mut(), mock_env(), SudoMsg::UpdateConfig(update)) {
+                Ok(_) => panic!("Must return error"),
+                Err(ContractError::ConfigFrozen {}) => {}
+                Err(e) => panic!("Unexpected error: {:?}", e),
+            }
+        }
+        let update = ConfigUpdate { admin: Some("council".to_string()), ..ConfigUpdate::default() };
+        sudo(deps.as_mut(), mock_env(), SudoMsg::UpdateConfig(update))
+            .expect("contract successfully handles UpdateConfig message");
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap();
+        let config: ConfigResponse = from_binary(&res).unwrap();
+        assert_eq!(config.owner, Addr::unchecked("council"));
+        assert_eq!((config.purchase_price, config.frozen), (coins(5, "token"), true));
+    }
+
+    #[test]
+    fn owners_lock_and_admins_freeze_names() {
+        let mut deps = mock_dependencies();
+        mock_init_no_price(deps.as_mut());
+        mock_alice_registers_name(deps.as_mut(), &[]);
+
+        let lock = ExecuteMsg::Lock { name: "alice".to_string() };
+        match execute(deps.as_mut(), mock_env(), mock_info("bob_key", &[]), lock.clone()) {
+            Ok(_) => panic!("Must return error"),
+            Err(ContractError::Unauthorized {}) => {}
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        }
+        execute(deps.as_mut(), mock_env(), mock_info("alice_key", &[]), lock)
+            .expect("contract successfully handles Lock message");
+
+        let transfer = ExecuteMsg::Transfer {
+            name: "alice".to_string(),
+            to: "bob_key".to_string(),
+            round_up: false,
+            memo: None,
+        };
+        let edit = ExecuteMsg::Edit {
+            name: "alice".to_string(),
+            bio: "locked".to_string(),
+            website: "".to_string(),
+            round_up: false,
+        };
+        let approve =
+            ExecuteMsg::Approve { spender: "bob_key".to_string(), token_id: "alice".to_string(), expires: None };
+        for msg in [transfer.clone(), edit, approve] {
+            match execute(deps.as_mut(), mock_env(), mock_info("alice_key", &[]), msg) {
+                Ok(_) => panic!("Must return error"),
+                Err(ContractError::NameLocked { name }) => assert_eq!(name, "alice"),
+                Err(e) => panic!("Unexpected error: {:?}", e),
+            }
+        }
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::ResolveRecord { name: "alice".to_string() }).unwrap();
+        let record: ResolveRecordResponse = from_binary(&res).unwrap();
+        assert_eq!((record.locked, record.frozen), (true, false));
+
+        let unlock = ExecuteMsg::Unlock { name: "alice".to_string() };
+        execute(deps.as_mut(), mock_env(), mock_info("alice_key", &[]), unlock)
+            .expect("contract successfully handles Unlock message");
+
+        // the owner can't lift a freeze of the admin
+        let freeze = ExecuteMsg::SetNameFrozen { name: "alice".to_string(), frozen: true };
+        match execute(deps.as_mut(), mock_env(), mock_info("alice_key", &[]), freeze.clone()) {
+            Ok(_) => panic!("Must return error"),
+            Err(ContractError::Unauthorized {}) => {}
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        }
+        execute(deps.as_mut(), mock_env(), mock_info("creator", &[]), freeze)
+            .expect("contract successfully handles SetNameFrozen message");
+        let unlock = ExecuteMsg::Unlock { name: "alice".to_string() };
+        execute(deps.as_mut(), mock_env(), mock_info("alice_key", &[]), unlock)
+            .expect("contract successfully handles Unlock message");
+        match execute(deps.as_mut(), mock_env(), mock_info("alice_key", &[]), transfer.clone()) {
+            Ok(_) => panic!("Must return error"),
+            Err(ContractError::NameFrozen { name }) => assert_eq!(name, "alice"),
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        }
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::ResolveRecord { name: "alice".to_string() }).unwrap();
+        let record: ResolveRecordResponse = from_binary(&res).unwrap();
+        assert_eq!((record.locked, record.frozen), (false, true));
+
+        let unfreeze = ExecuteMsg::SetNameFrozen { name: "alice".to_string(), frozen: false };
+        execute(deps.as_mut(), mock_env(), mock_info("creator", &[]), unfreeze)
+            .expect("contract successfully handles SetNameFrozen message");
+        execute(deps.as_mut(), mock_env(), mock_info("alice_key", &[]), transfer)
+            .expect("contract successfully handles Transfer message");
+        assert_name_owner(deps.as_ref(), "alice", "bob_key");
+    }
+
+    #[test]
+    fn stats_count_names_and_fees() {
+        let mut deps = mock_dependencies();
+        mock_init_with_price(deps.as_mut(), coin(2, "token"), coin(1, "token"));
+        mock_alice_registers_name(deps.as_mut(), &coins(2, "token"));
+
+        let msg = ExecuteMsg::Transfer {
+            name: "alice".to_string(),
+            to: "bob_key".to_string(),
+            round_up: false,
+            memo: None,
+        };
+        execute(deps.as_mut(), mock_env(), mock_info("alice_key", &coins(1, "token")), msg)
+            .expect("contract successfully handles Transfer message");
+        let msg = ExecuteMsg::ReleaseBatch { names: vec!["alice".to_string()] };
+        execute(deps.as_mut(), mock_env(), mock_info("bob_key", &[]), msg)
+            .expect("contract successfully handles ReleaseBatch message");
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::Stats {}).unwrap();
+        let stats: StatsResponse = from_binary(&res).unwrap();
+        assert_eq!(
+            stats,
+            StatsResponse {
+                total_registered: 1,
+                total_active: 0,
+                fees_collected: coins(3, "token"),
+            }
+        );
+    }
+
+    #[test]
+    fn names_with_prefix_lists_a_family_of_names() {
+        let mut deps = mock_dependencies();
+        mock_init_no_price(deps.as_mut());
+        for name in ["alice", "alicia", "alf", "bob", "alibaba"] {
+            let msg = ExecuteMsg::Register {
+                name: name.to_string(),
+                bio: "".to_string(),
+                website: "".to_string(),
+                round_up: false,
+                referrer: None,
+            };
+            execute(deps.as_mut(), mock_env(), mock_info("alice_key", &[]), msg)
+                .expect("contract successfully handles Register message");
+        }
+
+        let names_with_prefix = |start_after: Option<&str>, limit: u32| -> Vec<String> {
+            let msg = QueryMsg::NamesWithPrefix {
+                prefix: "ali".to_string(),
+                start_after: start_after.map(str::to_string),
+                limit: Some(limit),
+            };
+            let res = query(deps.as_ref(), mock_env(), msg).unwrap();
+            let page: AllNamesResponse = from_binary(&res).unwrap();
+            page.names.into_iter().map(|info| info.name).collect()
+        };
+        assert_eq!(names_with_prefix(None, 10), vec!["alibaba", "alice", "alicia"]);
+        assert_eq!(names_with_prefix(None, 2), vec!["alibaba", "alice"]);
+        assert_eq!(names_with_prefix(Some("alice"), 10), vec!["alicia"]);
+        // a start_after before the prefix starts at the prefix
+        assert_eq!(names_with_prefix(Some("alf"), 1), vec!["alibaba"]);
+    }
+
+    #[test]
+    fn socials_are_set_and_resolved() {
+        let mut deps = mock_dependencies();
+        mock_init_no_price(deps.as_mut());
+        mock_alice_registers_name(deps.as_mut(), &[]);
+
+        let socials = Socials {
+            avatar_url: "https://example.com/alice.png".to_string(),
+            twitter: "alice".to_string(),
+            github: "alice-dev".to_string(),
+            discord: "".to_string(),
+        };
+        let msg = ExecuteMsg::SetSocials { name: "alice".to_string(), socials: socials.clone() };
+        match execute(deps.as_mut(), mock_env(), mock_info("bob_key", &[]), msg.clone()) {
+            Ok(_) => panic!("Must return error"),
+            Err(ContractError::Unauthorized {}) => {}
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        }
+        execute(deps.as_mut(), mock_env(), mock_info("alice_key", &[]), msg)
+            .expect("contract successfully handles SetSocials message");
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::ResolveRecord { name: "alice".to_string() }).unwrap();
+        let record: ResolveRecordResponse = from_binary(&res).unwrap();
+        assert_eq!(record.socials, Some(socials));
+
+        let msg = ExecuteMsg::SetSocials {
+            name: "alice".to_string(),
+            socials: Socials { twitter: "al ice".to_string(), ..Socials::default() },
+        };
+        match execute(deps.as_mut(), mock_env(), mock_info("alice_key", &[]), msg) {
+            Ok(_) => panic!("Must return error"),
+            Err(ContractError::InvalidSocial { field, .. }) => assert_eq!(field, "twitter"),
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        }
+    }
+
+    #[test]
+    fn migrate_transforms_records_by_stored_version() {
+        let mut deps = mock_dependencies();
+        mock_init_no_price(deps.as_mut());
+
+        // a newer release can't be migrated back to this one
+        cw2::set_contract_version(deps.as_mut().storage, "cw-huahua-name", "9.0.0").unwrap();
+        match migrate(deps.as_mut(), mock_env(), MigrateMsg { batch_size: None }) {
+            Ok(_) => panic!("Must return error"),
+            Err(ContractError::Std(StdError::GenericErr { msg, .. })) => assert!(msg.starts_with("Cannot downgrade")),
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        }
+
+        // 0.1 releases stored the records of NameRecordV1
+        cw2::set_contract_version(deps.as_mut().storage, "cw-huahua-name", "0.1.3").unwrap();
+        let legacy: Map<&[u8], LegacyNameRecord> = Map::new("name_resolver");
+        let record = LegacyNameRecord {
+            owner: Addr::unchecked("bob_key"),
+            bio: "old".to_string(),
+            website: "".to_string(),
+        };
+        legacy.save(deps.as_mut().storage, b"bob", &record).unwrap();
+        legacy.save(deps.as_mut().storage, b"carol", &record).unwrap();
+
+        let _res = migrate(deps.as_mut(), mock_env(), MigrateMsg { batch_size: Some(1) })
+            .expect("contract successfully handles MigrateMsg");
+        let msg = ExecuteMsg::ContinueMigration { limit: None };
+        execute(deps.as_mut(), mock_env(), mock_info("creator", &[]), msg)
+            .expect("contract successfully handles ContinueMigration message");
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::MigrationPlan { batch_size: None }).unwrap();
+        let plan: MigrationPlanResponse = from_binary(&res).unwrap();
+        assert_eq!(plan.records_missing_fields, 0);
+        let record = name_resolver().load(deps.as_ref().storage, b"bob").unwrap();
+        assert_eq!((record.bio.as_str(), record.socials), ("old", Socials::default()));
+        assert_name_owner(deps.as_ref(), "bob", "bob_key");
+        assert_eq!(cw2::get_contract_version(deps.as_ref().storage).unwrap().version, env!("CARGO_PKG_VERSION"));
+    }
+
+    #[test]
+    fn resolve_record_returns_the_whole_record() {
+        let mut deps = mock_dependencies();
+        let msg = InstantiateMsg {
+            admin: None,
+            purchase_price: vec![],
+            transfer_price: vec![],
+            edit_price: vec![],
+            max_supply: None,
+            registration_duration: Some(100),
+            grace_period: Some(50),
+            renewal_price: None,
+            preset: None,
+            launch_schedule: vec![],
+            burn_fees: false,
+        };
+        instantiate(deps.as_mut(), mock_env(), mock_info("creator", &[]), msg)
+            .expect("contract successfully handles InstantiateMsg");
+        mock_alice_registers_name(deps.as_mut(), &[]);
+        for key in ["email", "url"] {
+            let msg = ExecuteMsg::SetTextRecord {
+                name: "alice".to_string(),
+                key: key.to_string(),
+                value: "x".to_string(),
+            };
+            execute(deps.as_mut(), mock_env(), mock_info("alice_key", &[]), msg)
+                .expect("contract successfully handles SetTextRecord message");
+        }
+
+        let record = query_record_at(deps.as_ref(), "alice", 0);
+        assert!(record.exists);
+        assert_eq!(record.owner, Some(Addr::unchecked("alice_key")));
+        assert_eq!((record.text_records, record.locked), (2, false));
+
+        // expired names no longer resolve but still tell who holds them
+        let record = query_record_at(deps.as_ref(), "alice", 100);
+        assert_eq!((record.exists, record.address), (true, None));
+        assert_eq!(record.owner, Some(Addr::unchecked("alice_key")));
+
+        let record = query_record_at(deps.as_ref(), "nobody", 0);
+        assert_eq!((record.exists, record.owner), (false, None));
+    }
+
+    #[cw_serde]
+    enum Cw20QueryMsg {
+        Balance { address: String },
+    }
+
+    #[cw_serde]
+    struct Cw20BalanceResponse {
+        balance: Uint128,
+    }
+
+    #[test]
+    fn token_holders_register_early_and_admin_opens_registration() {
+        let mut deps = mock_dependencies();
+        let msg = InstantiateMsg {
+            admin: None,
+            purchase_price: vec![],
+            transfer_price: vec![],
+            edit_price: vec![],
+            max_supply: None,
+            registration_duration: None,
+            grace_period: None,
+            renewal_price: None,
+            preset: None,
+            launch_schedule: vec![PhaseStart { phase: LaunchPhase::Allowlist, starts_at: mock_env().block.time }],
+            burn_fees: false,
+        };
+        instantiate(deps.as_mut(), mock_env(), mock_info("creator", &[]), msg)
+            .expect("contract successfully handles InstantiateMsg");
+        deps.querier.update_wasm(|query| match query {
+            WasmQuery::Smart { contract_addr, msg } if contract_addr == "token_contract" => {
+                let Cw20QueryMsg::Balance { address } = from_binary(msg).unwrap();
+                let balance = Uint128::new(if address == "bob_key" { 10 } else { 9 });
+                SystemResult::Ok(ContractResult::Ok(to_binary(&Cw20BalanceResponse { balance }).unwrap()))
+            }
+            _ => panic!("Unexpected query"),
+        });
+
+        let gate = TokenGate::Cw20 { contract: Addr::unchecked("token_contract"), min_balance: Uint128::new(10) };
+        let msg = ExecuteMsg::SetAllowlistGate { gate: Some(gate) };
+        execute(deps.as_mut(), mock_env(), mock_info("creator", &[]), msg)
+            .expect("contract successfully handles SetAllowlistGate message");
+
+        let register = |name: &str| ExecuteMsg::Register {
+            name: name.to_string(),
+            bio: "".to_string(),
+            website: "".to_string(),
+            round_up: false,
+            referrer: None,
+        };
+        execute(deps.as_mut(), mock_env(), mock_info("bob_key", &[]), register("bob"))
+            .expect("contract successfully handles Register message");
+        match execute(deps.as_mut(), mock_env(), mock_info("carol_key", &[]), register("carol")) {
+            Ok(_) => panic!("Must return error"),
+            Err(ContractError::RegistrationNotOpen { phase }) => assert_eq!(phase, LaunchPhase::Allowlist),
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        }
+
+        // the admin opens registration without waiting for the schedule
+        let msg = ExecuteMsg::SetLaunchPhase { phase: Some(LaunchPhase::Public) };
+        match execute(deps.as_mut(), mock_env(), mock_info("carol_key", &[]), msg.clone()) {
+            Ok(_) => panic!("Must return error"),
+            Err(ContractError::Unauthorized {}) => {}
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        }
+        execute(deps.as_mut(), mock_env(), mock_info("creator", &[]), msg)
+            .expect("contract successfully handles SetLaunchPhase message");
+        execute(deps.as_mut(), mock_env(), mock_info("carol_key", &[]), register("carol"))
+            .expect("contract successfully handles Register message");
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::Phase {}).unwrap();
+        let value: PhaseResponse = from_binary(&res).unwrap();
+        assert_eq!(value, PhaseResponse { phase: LaunchPhase::Public, next: None });
+    }
+
+    #[test]
+    fn listed_names_are_bought_with_fee_and_royalty() {
+        let mut deps = mock_dependencies();
+        mock_init_no_price(deps.as_mut());
+        mock_alice_registers_name(deps.as_mut(), &[]);
+        let msg = ExecuteMsg::SetMarketplaceFees { marketplace_fee_percent: 60, royalty_percent: 50 };
+        match execute(deps.as_mut(), mock_env(), mock_info("creator", &[]), msg) {
+            Ok(_) => panic!("Must return error"),
+            Err(ContractError::InvalidMarketplaceFees {}) => {}
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        }
+        let msg = ExecuteMsg::SetMarketplaceFees { marketplace_fee_percent: 5, royalty_percent: 10 };
+        execute(deps.as_mut(), mock_env(), mock_info("creator", &[]), msg)
+            .expect("contract successfully handles SetMarketplaceFees message");
+
+        // alice sells to bob, the registrant gets no royalty on its own sale
+        let msg = ExecuteMsg::ListForSale { name: "alice".to_string(), price: coin(100, "token") };
+        match execute(deps.as_mut(), mock_env(), mock_info("bob_key", &[]), msg.clone()) {
+            Ok(_) => panic!("Must return error"),
+            Err(ContractError::Unauthorized {}) => {}
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        }
+        execute(deps.as_mut(), mock_env(), mock_info("alice_key", &[]), msg)
+            .expect("contract successfully handles ListForSale message");
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::Listings { start_after: None, limit: None }).unwrap();
+        let listings: ListingsResponse = from_binary(&res).unwrap();
+        assert_eq!(listings.listings.len(), 1);
+        assert_eq!(listings.listings[0].seller, Addr::unchecked("alice_key"));
+
+        let buy = ExecuteMsg::Buy { name: "alice".to_string() };
+        match execute(deps.as_mut(), mock_env(), mock_info("bob_key", &coins(99, "token")), buy.clone()) {
+            Ok(_) => panic!("Must return error"),
+            Err(ContractError::InsufficientFundsSend {}) => {}
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        }
+        let res = execute(deps.as_mut(), mock_env(), mock_info("bob_key", &coins(102, "token")), buy.clone())
+            .expect("contract successfully handles Buy message");
+        assert_eq!(
+            res.messages[0].msg,
+            CosmosMsg::Bank(BankMsg::Send { to_address: "bob_key".to_string(), amount: coins(2, "token") })
+        );
+        assert_name_owner(deps.as_ref(), "alice", "bob_key");
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::Payout { address: "alice_key".to_string() }).unwrap();
+        assert_eq!(from_binary::<PayoutResponse>(&res).unwrap().claimable, coins(95, "token"));
+
+        // bob resells to carol, alice as registrant gets the royalty
+        let msg = ExecuteMsg::ListForSale { name: "alice".to_string(), price: coin(200, "token") };
+        execute(deps.as_mut(), mock_env(), mock_info("bob_key", &[]), msg)
+            .expect("contract successfully handles ListForSale message");
+        execute(deps.as_mut(), mock_env(), mock_info("carol_key", &coins(200, "token")), buy.clone())
+            .expect("contract successfully handles Buy message");
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::Payout { address: "alice_key".to_string() }).unwrap();
+        assert_eq!(from_binary::<PayoutResponse>(&res).unwrap().claimable, coins(115, "token"));
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::Payout { address: "bob_key".to_string() }).unwrap();
+        assert_eq!(from_binary::<PayoutResponse>(&res).unwrap().claimable, coins(170, "token"));
+
+        // the sale ended the listing
+        match execute(deps.as_mut(), mock_env(), mock_info("dave_key", &coins(200, "token")), buy) {
+            Ok(_) => panic!("Must return error"),
+            Err(ContractError::ListingNotExists { name }) => assert_eq!(name, "alice"),
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        }
+    }
+
+    #[test]
+    fn registrars_register_names_for_users() {
+        let mut deps = mock_dependencies();
+        mock_init_with_price(deps.as_mut(), coin(2, "token"), coin(2, "token"));
+
+        let register_for = ExecuteMsg::RegisterFor {
+            name: "alice".to_string(),
+            owner: "alice_key".to_string(),
+            bio: "".to_string(),
+            website: "".to_string(),
+        };
+        match execute(deps.as_mut(), mock_env(), mock_info("bridge_contract", &[]), register_for.clone()) {
+            Ok(_) => panic!("Must return error"),
+            Err(ContractError::Unauthorized {}) => {}
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        }
+
+        let msg = ExecuteMsg::AddRegistrar { address: "bridge_contract".to_string() };
+        execute(deps.as_mut(), mock_env(), mock_info("creator", &[]), msg.clone())
+            .expect("contract successfully handles AddRegistrar message");
+        match execute(deps.as_mut(), mock_env(), mock_info("creator", &[]), msg) {
+            Ok(_) => panic!("Must return error"),
+            Err(ContractError::RegistrarExists { address }) => assert_eq!(address, "bridge_contract"),
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        }
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::Registrars {}).unwrap();
+        let value: RegistrarsResponse = from_binary(&res).unwrap();
+        assert_eq!(value.registrars, vec![Addr::unchecked("bridge_contract")]);
+
+        // registrars pay nothing
+        execute(deps.as_mut(), mock_env(), mock_info("bridge_contract", &[]), register_for)
+            .expect("contract successfully handles RegisterFor message");
+        assert_name_owner(deps.as_ref(), "alice", "alice_key");
+
+        let msg = ExecuteMsg::RemoveRegistrar { address: "bridge_contract".to_string() };
+        execute(deps.as_mut(), mock_env(), mock_info("creator", &[]), msg)
+            .expect("contract successfully handles RemoveRegistrar message");
+        let register_for = ExecuteMsg::RegisterFor {
+            name: "bobby".to_string(),
+            owner: "bob_key".to_string(),
+            bio: "".to_string(),
+            website: "".to_string(),
+        };
+        match execute(deps.as_mut(), mock_env(), mock_info("bridge_contract", &[]), register_for) {
+            Ok(_) => panic!("Must return error"),
+            Err(ContractError::Unauthorized {}) => {}
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        }
+    }
+
+    #[test]
+    fn registrations_are_rate_limited_and_capped() {
+        let mut deps = mock_dependencies();
+        mock_init_no_price(deps.as_mut());
+
+        let msg = ExecuteMsg::SetRegistrationLimits {
+            max_names_per_address: Some(3),
+            registration_cooldown_seconds: Some(60),
+        };
+        execute(deps.as_mut(), mock_env(), mock_info("creator", &[]), msg)
+            .expect("contract successfully handles SetRegistrationLimits message");
+
+        let register = |name: &str| ExecuteMsg::Register {
+            name: name.to_string(),
+            bio: "".to_string(),
+            website: "".to_string(),
+            round_up: false,
+            referrer: None,
+        };
+        execute(deps.as_mut(), mock_env(), mock_info("alice_key", &[]), register("alice"))
+            .expect("contract successfully handles Register message");
+        match execute(deps.as_mut(), mock_env_at(59), mock_info("alice_key", &[]), register("alicia")) {
+            Ok(_) => panic!("Must return error"),
+            Err(ContractError::RegistrationCooldown { until }) => assert_eq!(until, mock_env_at(60).block.time),
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        }
+        // the cooldown is per address
+        execute(deps.as_mut(), mock_env_at(59), mock_info("bob_key", &[]), register("bobby"))
+            .expect("contract successfully handles Register message");
+
+        let quota = QueryMsg::RegistrationQuota { address: "alice_key".to_string() };
+        let res = query(deps.as_ref(), mock_env_at(30), quota);
+        let value: RegistrationQuotaResponse = from_binary(&res.unwrap()).unwrap();
+        assert_eq!(value.name_count, 1);
+        assert_eq!(value.remaining, Some(2));
+        assert_eq!(value.next_registration_at, Some(mock_env_at(60).block.time));
+
+        // a batch counts every name against the cap
+        let batch = |names: &[&str]| ExecuteMsg::RegisterBatch {
+            registrations: names
+                .iter()
+                .map(|name| RegistrationData { name: name.to_string(), bio: "".to_string(), website: "".to_string() })
+                .collect(),
+        };
+        match execute(deps.as_mut(), mock_env_at(60), mock_info("alice_key", &[]), batch(&["carl", "dave", "erin"])) {
+            Ok(_) => panic!("Must return error"),
+            Err(ContractError::NameLimitReached { max_names_per_address }) => assert_eq!(max_names_per_address, 3),
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        }
+        execute(deps.as_mut(), mock_env_at(60), mock_info("alice_key", &[]), batch(&["carl", "dave"]))
+            .expect("contract successfully handles RegisterBatch message");
+        match execute(deps.as_mut(), mock_env_at(600), mock_info("alice_key", &[]), register("frank")) {
+            Ok(_) => panic!("Must return error"),
+            Err(ContractError::NameLimitReached { .. }) => {}
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        }
+
+        let quota = QueryMsg::RegistrationQuota { address: "alice_key".to_string() };
+        let res = query(deps.as_ref(), mock_env_at(600), quota);
+        let value: RegistrationQuotaResponse = from_binary(&res.unwrap()).unwrap();
+        assert_eq!(value.name_count, 3);
+        assert_eq!(value.remaining, Some(0));
+        assert_eq!(value.next_registration_at, None);
+
+        // lifting the limits lets alice register again
+        let msg = ExecuteMsg::SetRegistrationLimits {
+            max_names_per_address: None,
+            registration_cooldown_seconds: None,
+        };
+        execute(deps.as_mut(), mock_env(), mock_info("creator", &[]), msg)
+            .expect("contract successfully handles SetRegistrationLimits message");
+        execute(deps.as_mut(), mock_env_at(600), mock_info("alice_key", &[]), register("frank"))
+            .expect("contract successfully handles Register message");
+    }
+
+    #[test]
+    fn resolve_record_at_returns_past_owners() {
+        let mut deps = mock_dependencies();
+        mock_init_no_price(deps.as_mut());
+        let at = |height| {
+            let mut env = mock_env();
+            env.block.height = height;
+            env
+        };
+        let owner_at = |deps: Deps, height| {
+            let msg = QueryMsg::ResolveRecordAt { name: "alice".to_string(), height };
+            from_binary::<ResolveRecordAtResponse>(&query(deps, mock_env(), msg).unwrap()).unwrap().owner
+        };
+
+        let register = ExecuteMsg::Register {
+            name: "alice".to_string(),
+            bio: "".to_string(),
+            website: "".to_string(),
+            round_up: false,
+            referrer: None,
+        };
+        execute(deps.as_mut(), at(100), mock_info("alice_key", &[]), register)
+            .expect("contract successfully handles Register message");
+        let transfer = ExecuteMsg::Transfer {
+            name: "alice".to_string(),
+            to: "bob_key".to_string(),
+            round_up: false,
+            memo: None,
+        };
+        execute(deps.as_mut(), at(200), mock_info("alice_key", &[]), transfer)
+            .expect("contract successfully handles Transfer message");
+
+        assert_eq!(owner_at(deps.as_ref(), 50), None);
+        assert_eq!(owner_at(deps.as_ref(), 150), Some(Addr::unchecked("alice_key")));
+        // the transfer shows from the block after it
+        assert_eq!(owner_at(deps.as_ref(), 200), Some(Addr::unchecked("alice_key")));
+        assert_eq!(owner_at(deps.as_ref(), 201), Some(Addr::unchecked("bob_key")));
+    }
+
+    #[test]
+    fn content_hash_is_set_and_resolved() {
+        let mut deps = mock_dependencies();
+        mock_init_no_price(deps.as_mut());
+        mock_alice_registers_name(deps.as_mut(), &[]);
+
+        let content_hash = "ipfs://bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi".to_string();
+        let msg = ExecuteMsg::SetContentHash { name: "alice".to_string(), value: Some(content_hash.clone()) };
+        match execute(deps.as_mut(), mock_env(), mock_info("bob_key", &[]), msg.clone()) {
+            Ok(_) => panic!("Must return error"),
+            Err(ContractError::Unauthorized {}) => {}
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        }
+        execute(deps.as_mut(), mock_env(), mock_info("alice_key", &[]), msg)
+            .expect("contract successfully handles SetContentHash message");
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::ResolveRecord { name: "alice".to_string() }).unwrap();
+        let record: ResolveRecordResponse = from_binary(&res).unwrap();
+        assert_eq!(record.content_hash, Some(content_hash));
+
+        let msg = ExecuteMsg::SetContentHash {
+            name: "alice".to_string(),
+            value: Some("https://alice.com".to_string()),
+        };
+        match execute(deps.as_mut(), mock_env(), mock_info("alice_key", &[]), msg) {
+            Ok(_) => panic!("Must return error"),
+            Err(ContractError::InvalidContentHash { value }) => assert_eq!(value, "https://alice.com"),
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        }
+
+        let msg = ExecuteMsg::SetContentHash { name: "alice".to_string(), value: None };
+        execute(deps.as_mut(), mock_env(), mock_info("alice_key", &[]), msg)
+            .expect("contract successfully handles SetContentHash message");
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::ResolveRecord { name: "alice".to_string() }).unwrap();
+        let record: ResolveRecordResponse = from_binary(&res).unwrap();
+        assert_eq!(record.content_hash, None);
+    }
+
+    #[test]
+    fn anyone_renews_names_up_to_the_horizon() {
+        let mut deps = mock_dependencies();
+        let msg = InstantiateMsg {
+            admin: None,
+            purchase_price: vec![],
+            transfer_price: vec![],
+            edit_price: vec![],
+            max_supply: None,
+            registration_duration: Some(100),
+            grace_period: Some(50),
+            renewal_price: Some(coin(2, "token")),
+            preset: None,
+            launch_schedule: vec![],
+            burn_fees: false,
+        };
+        let _res = instantiate(deps.as_mut(), mock_env(), mock_info("creator", &[]), msg)
+            .expect("contract successfully handles InstantiateMsg");
+        mock_alice_registers_name(deps.as_mut(), &[]);
+
+        // renew stays with the owner, renew_for is open to anyone
+        let renew = ExecuteMsg::Renew { name: "alice".to_string() };
+        match execute(deps.as_mut(), mock_env(), mock_info("dao_key", &coins(2, "token")), renew) {
+            Ok(_) => panic!("Must return error"),
+            Err(ContractError::Unauthorized {}) => {}
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        }
+        let renew_for = |years| ExecuteMsg::RenewFor { name: "alice".to_string(), years };
+        match execute(deps.as_mut(), mock_env(), mock_info("dao_key", &coins(5, "token")), renew_for(3)) {
+            Ok(_) => panic!("Must return error"),
+            Err(ContractError::InsufficientFundsSend {}) => {}
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        }
+        let res = execute(deps.as_mut(), mock_env(), mock_info("dao_key", &coins(6, "token")), renew_for(3))
+            .expect("contract successfully handles RenewFor message");
+        assert_eq!(res.attributes[2], attr("payer", "dao_key"));
+        assert_eq!(query_record_at(deps.as_ref(), "alice", 0).expires_at, Some(mock_env_at(400).block.time));
+        assert_name_owner(deps.as_ref(), "alice", "alice_key");
+        match execute(deps.as_mut(), mock_env(), mock_info("dao_key", &[]), renew_for(0)) {
+            Ok(_) => panic!("Must return error"),
+            Err(ContractError::InvalidRenewalYears {}) => {}
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        }
+
+        let msg = ExecuteMsg::SetExpiryPolicy {
+            registration_duration: Some(100),
+            grace_period: 50,
+            renewal_price: Some(coin(2, "token")),
+            max_renewal_horizon: Some(500),
+        };
+        execute(deps.as_mut(), mock_env(), mock_info("creator", &[]), msg)
+            .expect("contract successfully handles SetExpiryPolicy message");
+        match execute(deps.as_mut(), mock_env(), mock_info("dao_key", &coins(4, "token")), renew_for(2)) {
+            Ok(_) => panic!("Must return error"),
+            Err(ContractError::RenewalBeyondHorizon { max_expires_at }) => {
+                assert_eq!(max_expires_at, mock_env_at(500).block.time)
+            }
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        }
+        execute(deps.as_mut(), mock_env(), mock_info("dao_key", &coins(2, "token")), renew_for(1))
+            .expect("contract successfully handles RenewFor message");
+        assert_eq!(query_record_at(deps.as_ref(), "alice", 0).expires_at, Some(mock_env_at(500).block.time));
+    }
+
+    fn mock_app_with_prices() -> App {
+        let mut app = App::new(InstantiateMsg {
+            admin: None,
+            purchase_price: coins(10, "token"),
+            transfer_price: coins(3, "token"),
+            edit_price: coins(2, "token"),
+            max_supply: None,
+            registration_duration: Some(100),
+            grace_period: Some(50),
+            renewal_price: Some(coin(4, "token")),
+            preset: None,
+            launch_schedule: vec![],
+            burn_fees: false,
+        });
+        for address in ["alice_key", "bob_key", "carol_key", ADMIN] {
+            app.fund(address, &coins(100, "token"));
+        }
+        app
+    }
+
+    #[test]
+    fn app_accounts_fees_through_a_name_lifecycle() {
+        let mut app = mock_app_with_prices();
+        let balances = |app: &App| {
+            ["alice_key", "bob_key", "carol_key", CONTRACT].map(|address| app.balance(address, "token").u128())
+        };
+        let register = ExecuteMsg::Register {
+            name: "alice".to_string(),
+            bio: "".to_string(),
+            website: "".to_string(),
+            round_up: false,
+            referrer: None,
+        };
+
+        // the overpayment comes back to the sender
+        let res = app
+            .execute("alice_key", &coins(12, "token"), register.clone())
+            .expect("contract successfully handles Register message");
+        assert_eq!(res.attributes[0], attr("action", "register"));
+        assert_eq!(res.attributes[3], attr("fees", "10token"));
+        assert_eq!(balances(&app), [90, 100, 100, 10]);
+
+        let edit = ExecuteMsg::Edit {
+            name: "alice".to_string(),
+            bio: "hello".to_string(),
+            website: "".to_string(),
+            round_up: false,
+        };
+        // failed executes keep their funds with the sender
+        match app.execute("bob_key", &coins(2, "token"), edit.clone()) {
+            Ok(_) => panic!("Must return error"),
+            Err(ContractError::Unauthorized {}) => {}
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        }
+        match app.execute("alice_key", &coins(200, "token"), edit.clone()) {
+            Ok(_) => panic!("Must return error"),
+            Err(ContractError::Std(StdError::GenericErr { msg, .. })) => assert!(msg.contains("more than alice_key")),
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        }
+        app.execute("alice_key", &coins(2, "token"), edit)
+            .expect("contract successfully handles Edit message");
+        assert_eq!(balances(&app), [88, 100, 100, 12]);
+
+        let transfer = ExecuteMsg::Transfer {
+            name: "alice".to_string(),
+            to: "bob_key".to_string(),
+            round_up: false,
+            memo: None,
+        };
+        app.execute("alice_key", &coins(3, "token"), transfer)
+            .expect("contract successfully handles Transfer message");
+        app.execute("bob_key", &coins(4, "token"), ExecuteMsg::Renew { name: "alice".to_string() })
+            .expect("contract successfully handles Renew message");
+        assert_eq!(balances(&app), [85, 96, 100, 19]);
+        let record: ResolveRecordResponse = app.query(QueryMsg::ResolveRecord { name: "alice".to_string() }).unwrap();
+        assert_eq!(record.owner, Some(Addr::unchecked("bob_key")));
+        assert_eq!(record.expires_at, Some(app.env().block.time.plus_seconds(200)));
+
+        // expired names are taken by anyone once their grace period is over
+        app.advance(200);
+        match app.execute("carol_key", &coins(10, "token"), register.clone()) {
+            Ok(_) => panic!("Must return error"),
+            Err(ContractError::NameTaken { .. }) => {}
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        }
+        app.advance(50);
+        app.execute("carol_key", &coins(10, "token"), register)
+            .expect("contract successfully handles Register message");
+        assert_eq!(balances(&app), [85, 96, 90, 29]);
+        let record: ResolveRecordResponse = app.query(QueryMsg::ResolveRecord { name: "alice".to_string() }).unwrap();
+        assert_eq!(record.owner, Some(Addr::unchecked("carol_key")));
+
+        let stats: StatsResponse = app.query(QueryMsg::Stats {}).unwrap();
+        assert_eq!(stats.fees_collected, coins(29, "token"));
+    }
+
+    #[test]
+    fn app_charges_the_transfer_fee_for_config_edits() {
+        let mut app = mock_app_with_prices();
+        let editconf = ExecuteMsg::Editconf {
+            purchase_price: coins(20, "token"),
+            transfer_price: coins(3, "token"),
+            edit_price: coins(2, "token"),
+            length_prices: None,
+            name_policy: None,
+        };
+
+        // Editconf asks the admin for the transfer price, and keeps it
+        match app.execute(ADMIN, &[], editconf.clone()) {
+            Ok(_) => panic!("Must return error"),
+            Err(ContractError::InsufficientFundsSend {}) => {}
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        }
+        app.execute(ADMIN, &coins(3, "token"), editconf)
+            .expect("contract successfully handles Editconf message");
+        assert_eq!(app.balance(ADMIN, "token").u128(), 97);
+        assert_eq!(app.balance(CONTRACT, "token").u128(), 3);
+
+        let config: ConfigResponse = app.query(QueryMsg::Config {}).unwrap();
+        assert_eq!(config.purchase_price, coins(20, "token"));
+    }
+
+    #[test]
+    fn release_refunds_what_was_paid() {
+        let mut app = App::new(InstantiateMsg {
+            admin: None,
+            purchase_price: coins(100, "token"),
+            transfer_price: vec![],
+            edit_price: vec![],
+            max_supply: None,
+            registration_duration: Some(1000),
+            grace_period: None,
+            renewal_price: None,
+            preset: None,
+            launch_schedule: vec![],
+            burn_fees: false,
+        });
+        app.fund("alice_key", &coins(100, "token"));
+        let register = ExecuteMsg::Register {
+            name: "alice".to_string(),
+            bio: "".to_string(),
+            website: "".to_string(),
+            round_up: false,
+            referrer: None,
+        };
+        app.execute("alice_key", &coins(100, "token"), register)
+            .expect("contract successfully handles Register message");
+
+        // raising the price later doesn't raise the refund
+        let editconf = ExecuteMsg::Editconf {
+            purchase_price: coins(400, "token"),
+            transfer_price: vec![],
+            edit_price: vec![],
+            length_prices: None,
+            name_policy: None,
+        };
+        app.execute(ADMIN, &[], editconf).expect("contract successfully handles Editconf message");
+        app.advance(250);
+        app.execute("alice_key", &[], ExecuteMsg::Release { name: "alice".to_string() })
+            .expect("contract successfully handles Release message");
+        assert_eq!(app.balance("alice_key", "token").u128(), 75);
+        assert_eq!(app.balance(CONTRACT, "token").u128(), 25);
+
+        // names claimed for free refund nothing
+        let hash = HexBinary::from(Sha256::digest(b"woof:bark-at-the-moon").to_vec());
+        app.execute(ADMIN, &[], ExecuteMsg::CommitPromoNames { hashes: vec![hash] })
+            .expect("contract successfully handles CommitPromoNames message");
+        let claim = ExecuteMsg::ClaimPromoName {
+            name: "woof".to_string(),
+            secret: "bark-at-the-moon".to_string(),
+            bio: "".to_string(),
+            website: "".to_string(),
+        };
+        app.execute("bob_key", &[], claim).expect("contract successfully handles ClaimPromoName message");
+        let res = app
+            .execute("bob_key", &[], ExecuteMsg::Release { name: "woof".to_string() })
+            .expect("contract successfully handles Release message");
+        assert!(res.messages.is_empty());
+        assert_eq!(app.balance("bob_key", "token").u128(), 0);
+        assert_eq!(app.balance(CONTRACT, "token").u128(), 25);
+    }
+
+    #[test]
+    fn frozen_names_cannot_be_reached_through_batches_or_subnames() {
+        let mut deps = mock_dependencies();
+        mock_init_no_price(deps.as_mut());
+        mock_alice_registers_name(deps.as_mut(), &[]);
+        let register = ExecuteMsg::RegisterSubname {
+            parent: "alice".to_string(),
+            label: "pay".to_string(),
+            owner: "wallet_key".to_string(),
+            bio: "".to_string(),
+            website: "".to_string(),
+        };
+        execute(deps.as_mut(), mock_env(), mock_info("alice_key", &[]), register.clone())
+            .expect("contract successfully handles RegisterSubname message");
+
+        let msg = SudoMsg::FreezeName { name: "alice".to_string() };
+        sudo(deps.as_mut(), mock_env(), msg).expect("contract successfully handles FreezeName message");
+        let batch = ExecuteMsg::RegisterBatch {
+            registrations: ["bobby", "alice"]
+                .iter()
+                .map(|name| RegistrationData { name: name.to_string(), bio: "".to_string(), website: "".to_string() })
+                .collect(),
+        };
+        let remove = ExecuteMsg::RemoveSubname { parent: "alice".to_string(), label: "pay".to_string() };
+        for (sender, msg) in [("bob_key", batch), ("alice_key", register), ("alice_key", remove)] {
+            match execute(deps.as_mut(), mock_env(), mock_info(sender, &[]), msg) {
+                Ok(_) => panic!("Must return error"),
+                Err(ContractError::NameFrozen { name }) => assert_eq!(name, "alice"),
+                Err(e) => panic!("Unexpected error: {:?}", e),
+            }
+        }
+        assert_name_owner(deps.as_ref(), "pay.alice", "wallet_key");
+    }
+
+    fn mock_alice_locks_name(mut deps: DepsMut) {
+        mock_alice_registers_name(deps.branch(), &[]);
+        let lock = ExecuteMsg::Lock { name: "alice".to_string() };
+        execute(deps, mock_env(), mock_info("alice_key", &[]), lock)
+            .expect("contract successfully handles Lock message");
+    }
+
+    #[test]
+    fn locked_names_keep_their_cache_control() {
+        let mut deps = mock_dependencies();
+        mock_init_no_price(deps.as_mut());
+        mock_alice_locks_name(deps.as_mut());
+        let msg = ExecuteMsg::SetCacheControl { name: "alice".to_string(), cache_control: CacheControl::Immutable };
+        match execute(deps.as_mut(), mock_env(), mock_info("alice_key", &[]), msg) {
+            Ok(_) => panic!("Must return error"),
+            Err(ContractError::NameLocked { name }) => assert_eq!(name, "alice"),
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        }
+    }
+
+    #[test]
+    fn locked_names_keep_their_webhook() {
+        let mut deps = mock_dependencies();
+        mock_init_no_price(deps.as_mut());
+        mock_alice_locks_name(deps.as_mut());
+        let msg = ExecuteMsg::SetWebhook {
+            name: "alice".to_string(),
+            contract: Some("hook_contract".to_string()),
+            payload: None,
+        };
+        match execute(deps.as_mut(), mock_env(), mock_info("alice_key", &[]), msg) {
+            Ok(_) => panic!("Must return error"),
+            Err(ContractError::NameLocked { name }) => assert_eq!(name, "alice"),
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        }
+    }
+
+    #[test]
+    fn locked_names_keep_their_subnames() {
+        let mut deps = mock_dependencies();
+        mock_init_no_price(deps.as_mut());
+        mock_alice_locks_name(deps.as_mut());
+        let msg = ExecuteMsg::RegisterSubname {
+            parent: "alice".to_string(),
+            label: "pay".to_string(),
+            owner: "wallet_key".to_string(),
+            bio: "".to_string(),
+            website: "".to_string(),
+        };
+        match execute(deps.as_mut(), mock_env(), mock_info("alice_key", &[]), msg) {
+            Ok(_) => panic!("Must return error"),
+            Err(ContractError::NameLocked { name }) => assert_eq!(name, "alice"),
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        }
+    }
+
+    #[test]
+    fn locked_names_keep_their_attestation_opt_in() {
+        let mut deps = mock_dependencies();
+        mock_init_no_price(deps.as_mut());
+        mock_alice_locks_name(deps.as_mut());
+        let msg = ExecuteMsg::SetAttestationOptIn { name: "alice".to_string(), accept: true };
+        match execute(deps.as_mut(), mock_env(), mock_info("alice_key", &[]), msg) {
+            Ok(_) => panic!("Must return error"),
+            Err(ContractError::NameLocked { name }) => assert_eq!(name, "alice"),
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        }
+    }
+
+    #[test]
+    fn end_block_expires_names_and_settles_auctions() {
+        let mut deps = mock_dependencies();
+        let msg = InstantiateMsg {
+            admin: None,
+            purchase_price: coins(10, "token"),
+            transfer_price: vec![],
+            edit_price: vec![],
+            max_supply: None,
+            registration_duration: Some(100),
+            grace_period: Some(50),
+            renewal_price: None,
+            preset: None,
+            launch_schedule: vec![],
+            burn_fees: false,
+        };
+        instantiate(deps.as_mut(), mock_env(), mock_info("creator", &[]), msg)
+            .expect("contract successfully handles InstantiateMsg");
+        let msg = ExecuteMsg::SetPremiumDecay { premium_multiplier: 11, premium_window: 100 };
+        execute(deps.as_mut(), mock_env(), mock_info("creator", &[]), msg)
+            .expect("contract successfully handles SetPremiumDecay message");
+        mock_alice_registers_name(deps.as_mut(), &coins(10, "token"));
+        // more names than a block looks at
+        for letter in 'a'..='u' {
+            let msg = ExecuteMsg::Register {
+                name: format!("{}name", letter),
+                bio: "".to_string(),
+                website: "".to_string(),
+                round_up: false,
+                referrer: None,
+            };
+            execute(deps.as_mut(), mock_env(), mock_info("bob_key", &coins(10, "token")), msg)
+                .expect("contract successfully handles Register message");
+        }
+        let msg = ExecuteMsg::StartAuction { name: "woof".to_string(), min_bid: coin(100, "token"), duration: 100 };
+        execute(deps.as_mut(), mock_env(), mock_info("creator", &[]), msg)
+            .expect("contract successfully handles StartAuction message");
+        let bid = ExecuteMsg::PlaceBid { name: "woof".to_string() };
+        execute(deps.as_mut(), mock_env(), mock_info("carol_key", &coins(100, "token")), bid)
+            .expect("contract successfully handles PlaceBid message");
+
+        // names in their grace period or premium stay, the ended auction goes to its highest bidder
+        for seconds in [120, 200] {
+            let res = sudo(deps.as_mut(), mock_env_at(seconds), SudoMsg::EndBlock {})
+                .expect("contract successfully handles EndBlock message");
+            assert_eq!(res.attributes[1], attr("expired", ""));
+        }
+        assert_name_owner(deps.as_ref(), "woof", "carol_key");
+        assert_eq!(query_supply(deps.as_ref()).total, 23);
+
+        let res = sudo(deps.as_mut(), mock_env_at(250), SudoMsg::EndBlock {})
+            .expect("contract successfully handles EndBlock message");
+        assert_eq!(res.attributes[1].value.split(',').count(), 20);
+        assert_eq!(query_supply(deps.as_ref()).total, 3);
+        let tombstone = query_tombstone(deps.as_ref(), "alice").unwrap();
+        assert_eq!((tombstone.previous_owner.as_str(), tombstone.reason), ("alice_key", RemovalReason::Expired));
+        // the next block goes on with the names left, woof was registered later and still runs
+        let res = sudo(deps.as_mut(), mock_env_at(251), SudoMsg::EndBlock {})
+            .expect("contract successfully handles EndBlock message");
+        assert_eq!(res.attributes[1], attr("expired", "tname,uname"));
+        assert_eq!(query_supply(deps.as_ref()).total, 1);
+        assert_name_owner(deps.as_ref(), "woof", "carol_key");
+    }
+
+    #[test]
+    fn every_execute_message_can_be_paused() {
+        let mut deps = mock_dependencies();
+        mock_init_no_price(deps.as_mut());
+
+        // the variants as they appear in the execute JSON
+        let schema = cosmwasm_schema::schema_for!(ExecuteMsg);
+        let actions: Vec<_> = schema
+            .schema
+            .subschemas
+            .and_then(|subschemas| subschemas.one_of)
+            .unwrap()
+            .into_iter()
+            .map(|variant| match variant {
+                Schema::Object(variant) => variant.object.unwrap().required.into_iter().next().unwrap(),
+                variant => panic!("Unexpected variant: {:?}", variant),
+            })
+            .collect();
+        assert!(actions.contains(&"transfer_nft".to_string()));
+        sudo(deps.as_mut(), mock_env(), SudoMsg::PauseActions { actions: actions.clone() })
+            .expect("contract successfully handles PauseActions message");
+        sudo(deps.as_mut(), mock_env(), SudoMsg::UnpauseActions { actions })
+            .expect("contract successfully handles UnpauseActions message");
+    }
+
+    #[test]
+    fn app_replies_to_failing_webhooks() {
+        let mut app = mock_app_with_prices();
+        let register = ExecuteMsg::Register {
+            name: "alice".to_string(),
+            bio: "".to_string(),
+            website: "".to_string(),
+            round_up: false,
+            referrer: None,
+        };
+        app.execute("alice_key", &coins(10, "token"), register)
+            .expect("contract successfully handles Register message");
+        let msg = ExecuteMsg::SetWebhook {
+            name: "alice".to_string(),
+            contract: Some("hook_contract".to_string()),
+            payload: None,
+        };
+        app.execute("alice_key", &[], msg).expect("contract successfully handles SetWebhook message");
+
+        // a webhook that goes through isn't replied to
+        let edit = ExecuteMsg::Edit {
+            name: "alice".to_string(),
+            bio: "woof".to_string(),
+            website: "".to_string(),
+            round_up: false,
+        };
+        app.execute("alice_key", &coins(2, "token"), edit).expect("contract successfully handles Edit message");
+        assert!(app.replies().is_empty());
+
+        // a failing webhook doesn't block the transfer of its name
+        app.fail_contract("hook_contract");
+        let transfer = ExecuteMsg::Transfer {
+            name: "alice".to_string(),
+            to: "bob_key".to_string(),
+            round_up: false,
+            memo: None,
+        };
+        app.execute("alice_key", &coins(3, "token"), transfer)
+            .expect("contract successfully handles Transfer message");
+        assert_eq!(app.replies().len(), 1);
+        assert_eq!(
+            app.replies()[0].attributes,
+            vec![attr("action", "webhook_failed"), attr("error", "Generic error: hook_contract failed")]
+        );
+        let record: ResolveRecordResponse =
+            app.query(QueryMsg::ResolveRecord { name: "alice".to_string() }).unwrap();
+        assert_eq!(record.owner, Some(Addr::unchecked("bob_key")));
+        assert_eq!(app.balance(CONTRACT, "token").u128(), 15);
+    }
 }
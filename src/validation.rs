@@ -0,0 +1,835 @@
+use cosmwasm_std::{Coin, Uint128};
+
+use crate::coin_helpers::{assert_sent_sufficient_coin, charge, Payment};
+use crate::error::ContractError;
+use crate::state::{Charset, Config, NamePolicy, PriceTier, Socials};
+
+// Default Name Policy
+pub const MIN_NAME_LENGTH: u64 = 3;
+pub const MAX_NAME_LENGTH: u64 = 30;
+pub const MAX_BIO_LENGTH: u64 = 200;
+pub const MAX_WEBSITE_LENGTH: u64 = 100;
+pub const MAX_CATEGORY_LENGTH: u64 = 20;
+pub const MAX_MARKS_PER_CHARACTER: usize = 3;
+
+// Social profile limits, as set by each platform
+pub const MAX_AVATAR_URL_LENGTH: u64 = 256;
+pub const MAX_TWITTER_LENGTH: u64 = 15;
+pub const MAX_GITHUB_LENGTH: u64 = 39;
+pub const MAX_DISCORD_LENGTH: u64 = 32;
+const AVATAR_URL_SCHEMES: [&str; 2] = ["https://", "ipfs://"];
+
+// Content hash limits, long enough for any CIDv1 of a 512 bit digest
+pub const MAX_CONTENT_HASH_LENGTH: u64 = 128;
+const CIDV0_LENGTH: usize = 46;
+const ARWEAVE_TX_ID_LENGTH: usize = 43;
+const BASE58_ALPHABET: &str = "123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+// Name Score Config
+pub const MAX_NAME_SCORE: u64 = 100;
+pub const LENGTH_PENALTY: u64 = 5;
+pub const DIGIT_PENALTY: u64 = 5;
+pub const HYPHEN_PENALTY: u64 = 10;
+pub const WORD_BONUS: u64 = 30;
+pub const WORD_PREFIX_BONUS: u64 = 10;
+
+/// PricedAction lists the actions charged a configured price
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PricedAction {
+    // length of the registered name
+    Register { length: u64 },
+    Transfer,
+    // bytes of profile data the edit writes
+    Edit { bytes: u64 },
+    Renew,
+}
+
+impl PricedAction {
+    /// name is how the action is told to a fee calculator
+    pub fn name(&self) -> &'static str {
+        match self {
+            PricedAction::Register { .. } => "register",
+            PricedAction::Transfer => "transfer",
+            PricedAction::Edit { .. } => "edit",
+            PricedAction::Renew => "renew",
+        }
+    }
+}
+
+/// normalize_name maps user input onto the canonical form accepted by validate_name: trimmed, lowercase and
+/// without zero-width characters
+pub fn normalize_name(name: &str) -> String {
+    name.trim().chars().filter(|c| !is_zero_width(*c)).flat_map(char::to_lowercase).collect()
+}
+
+/// is_zero_width tells whether `c` is invisible, names could hide it between their characters
+fn is_zero_width(c: char) -> bool {
+    matches!(c, '\u{ad}' | '\u{200b}'..='\u{200f}' | '\u{2060}'..='\u{2064}' | '\u{feff}')
+}
+
+/// is_combining_mark tells whether `c` joins the character before it into a single grapheme. It covers the
+/// combining blocks and the signs of the Hebrew, Arabic, Indic and Thai scripts, not every mark of Unicode
+pub fn is_combining_mark(c: char) -> bool {
+    let c = c as u32;
+    match c {
+        0x0300..=0x036f | 0x0483..=0x0489 | 0x1ab0..=0x1aff | 0x1dc0..=0x1dff | 0x20d0..=0x20ff => true,
+        0xfe20..=0xfe2f | 0x3099..=0x309a => true,
+        0x0591..=0x05bd | 0x05bf | 0x05c1..=0x05c2 | 0x05c4..=0x05c5 | 0x05c7 => true,
+        0x0610..=0x061a | 0x064b..=0x065f | 0x0670 | 0x06d6..=0x06dc | 0x06df..=0x06e4 | 0x06e7..=0x06e8 => true,
+        0x06ea..=0x06ed | 0x0e31 | 0x0e34..=0x0e3a | 0x0e47..=0x0e4e => true,
+        // from Devanagari to Malayalam the blocks share one layout, their signs sit at the same offsets
+        0x0900..=0x0d7f => matches!(c & 0x7f, 0x01..=0x03 | 0x3a..=0x3c | 0x3e..=0x4f | 0x51..=0x57 | 0x62..=0x63),
+        _ => false,
+    }
+}
+
+/// name_length counts the graphemes of a name, a character with its combining marks counts once
+pub fn name_length(name: &str) -> u64 {
+    name.chars().filter(|c| !is_combining_mark(*c)).count() as u64
+}
+
+/// Script is an alphabet with letters looking like those of the others
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Script {
+    Latin,
+    Greek,
+    Cyrillic,
+}
+
+/// script is the alphabet of `c` among those easily confused, if any
+fn script(c: char) -> Option<Script> {
+    match c as u32 {
+        _ if c.is_ascii_alphabetic() => Some(Script::Latin),
+        0x00c0..=0x024f | 0x1e00..=0x1eff if c.is_alphabetic() => Some(Script::Latin),
+        0x0370..=0x03ff | 0x1f00..=0x1fff => Some(Script::Greek),
+        0x0400..=0x052f => Some(Script::Cyrillic),
+        _ => None,
+    }
+}
+
+/// validate_unicode returns an error unless the name is composed, gives each character at most
+/// MAX_MARKS_PER_CHARACTER combining marks and doesn't mix the Latin, Greek and Cyrillic alphabets
+fn validate_unicode(name: &str) -> Result<(), ContractError> {
+    let mut base = None;
+    let mut marks = 0;
+    let mut alphabet = None;
+    for c in name.chars() {
+        if is_combining_mark(c) {
+            marks += 1;
+            match base {
+                None => return Err(ContractError::InvalidCharacter { c }),
+                _ if marks > MAX_MARKS_PER_CHARACTER => return Err(ContractError::InvalidCharacter { c }),
+                // accented letters of these alphabets have a precomposed character, NFC uses it
+                Some(base) if ('\u{300}'..='\u{36f}').contains(&c) && script(base).is_some() => {
+                    return Err(ContractError::NameNotNormalized { name: name.to_string() })
+                }
+                _ => continue,
+            }
+        }
+        // conjoining jamo compose into Hangul syllables
+        if ('\u{1100}'..='\u{11ff}').contains(&c) {
+            return Err(ContractError::NameNotNormalized { name: name.to_string() });
+        }
+        base = Some(c);
+        marks = 0;
+        match (alphabet, script(c)) {
+            (Some(alphabet), Some(script)) if alphabet != script => {
+                return Err(ContractError::ConfusableName { name: name.to_string() })
+            }
+            (None, script) => alphabet = script,
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+// let's not import a regexp library and just do these checks by hand
+pub fn invalid_char(c: char) -> bool {
+    let is_valid =
+        c.is_ascii_digit() || c.is_ascii_lowercase() || (c == '-' /*|| c == '.' || c == '_'*/);
+    !is_valid
+}
+
+/// allowed_char tells whether names may contain `c` under `charset`
+pub fn allowed_char(c: char, charset: &Charset) -> bool {
+    !invalid_char(c)
+        || (charset.allow_underscore && c == '_')
+        || (charset.allow_dot && c == '.')
+        || (charset.allow_unicode && !c.is_ascii() && c.is_alphanumeric() && !c.is_uppercase())
+        || (charset.allow_unicode && is_combining_mark(c))
+}
+
+/// validate_name returns an error if the name breaks `policy`, its length is counted in graphemes
+pub fn validate_name(name: &str, policy: &NamePolicy) -> Result<(), ContractError> {
+    let length = name_length(name);
+    if length < policy.min_name_length {
+        Err(ContractError::NameTooShort {
+            length,
+            min_length: policy.min_name_length,
+        })
+    } else if length > policy.max_name_length {
+        Err(ContractError::NameTooLong {
+            length,
+            max_length: policy.max_name_length,
+        })
+    } else {
+        match name.find(|c| !allowed_char(c, &policy.charset)) {
+            None if policy.charset.allow_unicode => validate_unicode(name),
+            None => Ok(()),
+            Some(bytepos_invalid_char_start) => {
+                let c = name[bytepos_invalid_char_start..].chars().next().unwrap();
+                Err(ContractError::InvalidCharacter { c })
+            }
+        }
+    }
+}
+
+/// validate_dictionary_word returns an error unless `word` is made of lowercase letters and could start a name
+pub fn validate_dictionary_word(word: &str) -> Result<(), ContractError> {
+    if word.is_empty() || word.len() as u64 > MAX_NAME_LENGTH || !word.chars().all(|c| c.is_ascii_lowercase()) {
+        return Err(ContractError::InvalidDictionaryWord { word: word.to_string() });
+    }
+    Ok(())
+}
+
+/// score_name rates a name from 0 to MAX_NAME_SCORE. Short names score high, digits mixed with letters
+/// and hyphens lower it, starting with the dictionary word `word` raises it, being that word even more
+pub fn score_name(name: &str, word: Option<&str>) -> u64 {
+    let length = name_length(name);
+    let digits = name.chars().filter(char::is_ascii_digit).count() as u64;
+    let hyphens = name.matches('-').count() as u64;
+
+    // the shortest names get the full score, every further character costs some
+    let mut score = MAX_NAME_SCORE.saturating_sub(length.saturating_sub(MIN_NAME_LENGTH) * LENGTH_PENALTY);
+    // numbers are a category of their own, only digits among letters count against the name
+    if digits != length {
+        score = score.saturating_sub(digits * DIGIT_PENALTY);
+    }
+    score = score.saturating_sub(hyphens * HYPHEN_PENALTY);
+    match word {
+        Some(word) if word == name => score += WORD_BONUS,
+        Some(_) => score += WORD_PREFIX_BONUS,
+        None => {}
+    }
+    score.min(MAX_NAME_SCORE)
+}
+
+/// validate_profile returns an error if the bio or website exceed their maximum length under `policy`
+pub fn validate_profile(bio: &str, website: &str, policy: &NamePolicy) -> Result<(), ContractError> {
+    let bio_length = bio.len() as u64;
+    let website_length = website.len() as u64;
+
+    if (bio_length) > policy.max_bio_length {
+        return Err(ContractError::BioTooLong {
+            bio_length,
+            max_length: policy.max_bio_length,
+        })
+    }
+
+    if (website_length) > policy.max_website_length {
+        return Err(ContractError::WebsiteTooLong {
+            website_length,
+            max_length: policy.max_website_length,
+        })
+    }
+
+    Ok(())
+}
+
+/// validate_socials returns an error if a social field is too long or malformed, empty fields are always valid
+pub fn validate_socials(socials: &Socials) -> Result<(), ContractError> {
+    let fields = [
+        ("avatar_url", &socials.avatar_url, MAX_AVATAR_URL_LENGTH),
+        ("twitter", &socials.twitter, MAX_TWITTER_LENGTH),
+        ("github", &socials.github, MAX_GITHUB_LENGTH),
+        ("discord", &socials.discord, MAX_DISCORD_LENGTH),
+    ];
+    for (field, value, max_length) in fields {
+        let length = value.len() as u64;
+        if length > max_length {
+            return Err(ContractError::SocialTooLong { field: field.to_string(), length, max_length });
+        }
+    }
+
+    let avatar_url = &socials.avatar_url;
+    let avatar_valid = avatar_url.is_empty()
+        || (AVATAR_URL_SCHEMES
+            .iter()
+            .any(|scheme| avatar_url.len() > scheme.len() && avatar_url.starts_with(scheme))
+            && !avatar_url.contains(char::is_whitespace));
+    // twitter handles are letters, digits and underscores
+    let twitter_valid = socials.twitter.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+    // github logins are letters, digits and single hyphens between them
+    let github = &socials.github;
+    let github_valid = github.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+        && !github.starts_with('-')
+        && !github.ends_with('-')
+        && !github.contains("--");
+    // discord usernames are at least 2 lowercase letters, digits, underscores and single periods
+    let discord = &socials.discord;
+    let discord_valid = discord.is_empty()
+        || (discord.len() >= 2
+            && discord.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_' || c == '.')
+            && !discord.contains(".."));
+
+    for (field, value, valid) in [
+        ("avatar_url", avatar_url, avatar_valid),
+        ("twitter", &socials.twitter, twitter_valid),
+        ("github", github, github_valid),
+        ("discord", discord, discord_valid),
+    ] {
+        if !valid {
+            return Err(ContractError::InvalidSocial { field: field.to_string(), value: value.clone() });
+        }
+    }
+    Ok(())
+}
+
+/// validate_category returns an error if the category label is invalid
+pub fn validate_category(category: &str) -> Result<(), ContractError> {
+    if category.is_empty() || (category.len() as u64) > MAX_CATEGORY_LENGTH || category.contains(invalid_char) {
+        return Err(ContractError::InvalidCategory {
+            category: category.to_string(),
+        });
+    }
+    Ok(())
+}
+
+/// price is the first of the prices accepted for `action` under `config`, if any
+pub fn price(config: &Config, action: PricedAction) -> Option<Coin> {
+    accepted_prices(config, action).into_iter().next()
+}
+
+/// accepted_prices lists the coins `action` can be paid with under `config`, paying any one of them is enough
+pub fn accepted_prices(config: &Config, action: PricedAction) -> Vec<Coin> {
+    match action {
+        PricedAction::Register { length } => registration_prices(config, length),
+        PricedAction::Transfer => config.transfer_price.clone(),
+        PricedAction::Edit { bytes } => edit_prices(config, bytes),
+        PricedAction::Renew => config.renewal_price.iter().cloned().collect(),
+    }
+}
+
+/// batch_prices lists what registering names accepting `prices` at once costs, one total for every denom all
+/// of the paid names accept, paying any one of them is enough. Free names add nothing
+pub fn batch_prices(prices: &[Vec<Coin>]) -> Result<Vec<Coin>, ContractError> {
+    let prices: Vec<&Vec<Coin>> = prices
+        .iter()
+        .filter(|prices| !prices.is_empty() && prices.iter().all(|price| !price.amount.is_zero()))
+        .collect();
+    let first = match prices.first() {
+        Some(first) => first,
+        None => return Ok(vec![]),
+    };
+
+    let totals: Vec<Coin> = first
+        .iter()
+        .filter_map(|price| {
+            prices
+                .iter()
+                .try_fold(Uint128::zero(), |total, prices| {
+                    prices.iter().find(|other| other.denom == price.denom).map(|other| total + other.amount)
+                })
+                .map(|total| Coin::new(total.u128(), &price.denom))
+        })
+        .collect();
+    if totals.is_empty() {
+        return Err(ContractError::BatchDenomMismatch {});
+    }
+    Ok(totals)
+}
+
+/// registration_prices is the price of the first tier fitting a name of `length`, or purchase_price
+fn registration_prices(config: &Config, length: u64) -> Vec<Coin> {
+    match config.length_prices.iter().find(|tier| length <= tier.max_length) {
+        Some(tier) => vec![tier.price.clone()],
+        None => config.purchase_price.clone(),
+    }
+}
+
+/// validate_name_policy returns an error unless names of some length satisfy `policy`
+pub fn validate_name_policy(policy: &NamePolicy) -> Result<(), ContractError> {
+    if policy.min_name_length == 0 || policy.min_name_length > policy.max_name_length {
+        return Err(ContractError::InvalidNamePolicy {});
+    }
+    Ok(())
+}
+
+/// validate_price_tiers returns an error unless the tiers are sorted by strictly increasing max_length
+pub fn validate_price_tiers(tiers: &[PriceTier]) -> Result<(), ContractError> {
+    if tiers.windows(2).any(|pair| pair[0].max_length >= pair[1].max_length) {
+        return Err(ContractError::InvalidPriceTiers {});
+    }
+    Ok(())
+}
+
+/// edit_prices is the flat edit price plus the per-byte price for every byte written,
+/// only payable in the denom of edit_byte_price when one is set
+fn edit_prices(config: &Config, bytes: u64) -> Vec<Coin> {
+    let byte_price = match &config.edit_byte_price {
+        Some(byte_price) => byte_price,
+        None => return config.edit_price.clone(),
+    };
+    let base = config
+        .edit_price
+        .iter()
+        .find(|coin| coin.denom == byte_price.denom)
+        .map(|coin| coin.amount)
+        .unwrap_or_default();
+    let amount = base.saturating_add(byte_price.amount.saturating_mul(Uint128::from(bytes)));
+    vec![Coin::new(amount.u128(), &byte_price.denom)]
+}
+
+/// assert_price_paid returns an error if `funds` do not cover any accepted price of `action`
+pub fn assert_price_paid(config: &Config, action: PricedAction, funds: &[Coin]) -> Result<(), ContractError> {
+    assert_sent_sufficient_coin(funds, &accepted_prices(config, action))
+}
+
+/// payment settles `funds` against the accepted prices of `action`
+pub fn payment(config: &Config, action: PricedAction, funds: &[Coin]) -> Result<Payment, ContractError> {
+    charge(funds, &accepted_prices(config, action))
+}
+
+
+/// validate_content_hash returns an error unless `value` is an ipfs:// CID or an ar:// transaction id.
+/// IPFS CIDs are either base58 CIDv0 (Qm...) or base32 CIDv1 (b...)
+pub fn validate_content_hash(value: &str) -> Result<(), ContractError> {
+    let length = value.len() as u64;
+    if length > MAX_CONTENT_HASH_LENGTH {
+        return Err(ContractError::ContentHashTooLong { length, max_length: MAX_CONTENT_HASH_LENGTH });
+    }
+
+    let valid = match (value.strip_prefix("ipfs://"), value.strip_prefix("ar://")) {
+        (Some(cid), _) if cid.starts_with("Qm") => {
+            cid.len() == CIDV0_LENGTH && cid.chars().all(|c| BASE58_ALPHABET.contains(c))
+        }
+        (Some(cid), _) => cid.strip_prefix('b').is_some_and(|cid| {
+            !cid.is_empty() && cid.chars().all(|c| c.is_ascii_lowercase() || ('2'..='7').contains(&c))
+        }),
+        (_, Some(tx_id)) => {
+            tx_id.len() == ARWEAVE_TX_ID_LENGTH
+                && tx_id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        }
+        _ => false,
+    };
+    if !valid {
+        return Err(ContractError::InvalidContentHash { value: value.to_string() });
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use cosmwasm_std::{coin, coins, Addr};
+
+    #[test]
+    fn normalized_names_validate() {
+        assert_eq!(normalize_name("  Alice-99 "), "alice-99");
+        validate_name(&normalize_name("  Alice-99 "), &NamePolicy::default()).unwrap();
+
+        match validate_name(&normalize_name("al ice"), &NamePolicy::default()) {
+            Ok(_) => panic!("Must return error"),
+            Err(ContractError::InvalidCharacter { c }) => assert_eq!(c, ' '),
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        }
+    }
+
+    #[test]
+    fn charset_policy_extends_names() {
+        let mut policy = NamePolicy::default();
+        for name in ["al_ice", "al.ice", "chihuahua\u{f1}o"] {
+            assert!(validate_name(name, &policy).is_err());
+        }
+
+        policy.charset = Charset { allow_underscore: true, allow_dot: true, allow_unicode: true };
+        for name in ["al_ice", "al.ice", "chihuahua\u{f1}o"] {
+            validate_name(name, &policy).unwrap();
+        }
+        // unicode names still have to be lowercase
+        match validate_name("chihuahua\u{d1}o", &policy) {
+            Ok(_) => panic!("Must return error"),
+            Err(ContractError::InvalidCharacter { c }) => assert_eq!(c, '\u{d1}'),
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        }
+
+        policy.min_name_length = 1;
+        validate_name("a", &policy).unwrap();
+        policy.max_name_length = 0;
+        match validate_name_policy(&policy) {
+            Ok(_) => panic!("Must return error"),
+            Err(ContractError::InvalidNamePolicy {}) => {}
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        }
+    }
+
+    #[test]
+    fn unicode_names_are_normalized() {
+        assert_eq!(normalize_name(" \u{d1}O\u{200b}\u{d1}O "), "\u{f1}o\u{f1}o");
+
+        let mut policy = NamePolicy::default();
+        policy.charset.allow_unicode = true;
+        // lengths count graphemes, not bytes: three CJK characters and a Devanagari word of three syllables
+        policy.max_name_length = 3;
+        for name in ["\u{65e5}\u{672c}\u{8a9e}", "\u{939}\u{93f}\u{928}\u{94d}\u{926}\u{940}"] {
+            assert_eq!(name_length(name), 3);
+            validate_name(name, &policy).unwrap();
+        }
+        policy.max_name_length = MAX_NAME_LENGTH;
+
+        match validate_name("n\u{303}o\u{303}no", &policy) {
+            Ok(_) => panic!("Must return error"),
+            Err(ContractError::NameNotNormalized { name }) => assert_eq!(name, "n\u{303}o\u{303}no"),
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        }
+        // a cyrillic a among latin letters
+        match validate_name("p\u{430}ypal", &policy) {
+            Ok(_) => panic!("Must return error"),
+            Err(ContractError::ConfusableName { .. }) => {}
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        }
+        match validate_name("al\u{200b}ice", &policy) {
+            Ok(_) => panic!("Must return error"),
+            Err(ContractError::InvalidCharacter { c }) => assert_eq!(c, '\u{200b}'),
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        }
+        match validate_name("\u{939}\u{93f}\u{93f}\u{93f}\u{93f}\u{928}\u{926}", &policy) {
+            Ok(_) => panic!("Must return error"),
+            Err(ContractError::InvalidCharacter { c }) => assert_eq!(c, '\u{93f}'),
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        }
+    }
+
+    #[test]
+    fn short_words_score_high() {
+        assert_eq!(score_name("dog", Some("dog")), MAX_NAME_SCORE);
+        assert_eq!(score_name("999", None), MAX_NAME_SCORE);
+        // 5 characters, one digit among letters
+        assert_eq!(score_name("alic3", None), 85);
+        // the hyphen costs what starting with a word earns
+        assert_eq!(score_name("dog-house", Some("dog")), 70);
+        assert_eq!(score_name("a-very-long-name-with-many-parts9", None), 0);
+    }
+
+    #[test]
+    fn price_follows_config() {
+        let config = Config {
+            owner: Addr::unchecked("admin"),
+            purchase_price: vec![coin(5, "token")],
+            transfer_price: vec![coin(3, "token")],
+            edit_price: vec![],
+            max_supply: None,
+            frozen: false,
+            community_fund: None,
+            min_delegation: None,
+            registration_duration: None,
+            grace_period: 0,
+            renewal_price: None,
+            edit_byte_price: None,
+            length_prices: vec![],
+            watch_deposit: None,
+            release_cooldown: 0,
+            fee_collector: None,
+            revenue_split: vec![],
+            fee_calculator: None,
+            ibc_channels: vec![],
+            hold_deposit: None,
+            hold_duration: 0,
+            name_policy: NamePolicy::default(),
+            burn_fees: false,
+            referral_percent: 0,
+            premium_multiplier: 0,
+            premium_window: 0,
+            launch_phase: None,
+            allowlist_gate: None,
+            marketplace_fee_percent: 0,
+            royalty_percent: 0,
+            max_names_per_address: None,
+            registration_cooldown_seconds: None,
+            max_renewal_horizon: None,
+        };
+        assert_eq!(price(&config, PricedAction::Register { length: 5 }), Some(coin(5, "token")));
+        assert_eq!(price(&config, PricedAction::Transfer), Some(coin(3, "token")));
+        assert_eq!(price(&config, PricedAction::Edit { bytes: 10 }), None);
+
+        assert_price_paid(&config, PricedAction::Edit { bytes: 10 }, &[]).unwrap();
+        assert_price_paid(&config, PricedAction::Register { length: 5 }, &coins(5, "token")).unwrap();
+        match assert_price_paid(&config, PricedAction::Register { length: 5 }, &coins(3, "token")) {
+            Ok(_) => panic!("Must return error"),
+            Err(ContractError::InsufficientFundsSend {}) => {}
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        }
+    }
+
+    #[test]
+    fn any_accepted_denom_pays() {
+        let config = Config {
+            owner: Addr::unchecked("admin"),
+            purchase_price: vec![coin(5, "token"), coin(2, "earth")],
+            transfer_price: vec![],
+            edit_price: vec![],
+            max_supply: None,
+            frozen: false,
+            community_fund: None,
+            min_delegation: None,
+            registration_duration: None,
+            grace_period: 0,
+            renewal_price: None,
+            edit_byte_price: None,
+            length_prices: vec![],
+            watch_deposit: None,
+            release_cooldown: 0,
+            fee_collector: None,
+            revenue_split: vec![],
+            fee_calculator: None,
+            ibc_channels: vec![],
+            hold_deposit: None,
+            hold_duration: 0,
+            name_policy: NamePolicy::default(),
+            burn_fees: false,
+            referral_percent: 0,
+            premium_multiplier: 0,
+            premium_window: 0,
+            launch_phase: None,
+            allowlist_gate: None,
+            marketplace_fee_percent: 0,
+            royalty_percent: 0,
+            max_names_per_address: None,
+            registration_cooldown_seconds: None,
+            max_renewal_horizon: None,
+        };
+        let action = PricedAction::Register { length: 5 };
+        assert_price_paid(&config, action, &coins(2, "earth")).unwrap();
+        assert_eq!(payment(&config, action, &coins(2, "earth")).unwrap().change, vec![]);
+        let paid = payment(&config, action, &[coin(1, "token"), coin(3, "earth")]).unwrap();
+        assert_eq!(paid.charge, Some(coin(2, "earth")));
+        assert_eq!(paid.change, vec![coin(1, "token"), coin(1, "earth")]);
+        // nothing is charged when no accepted price is covered
+        match payment(&config, action, &coins(4, "token")) {
+            Ok(_) => panic!("Must return error"),
+            Err(ContractError::InsufficientFundsSend {}) => {}
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        }
+    }
+
+    #[test]
+    fn batch_prices_add_up_per_denom() {
+        let mut config = Config {
+            owner: Addr::unchecked("admin"),
+            purchase_price: vec![coin(5, "token"), coin(2, "earth")],
+            transfer_price: vec![],
+            edit_price: vec![],
+            max_supply: None,
+            frozen: false,
+            community_fund: None,
+            min_delegation: None,
+            registration_duration: None,
+            grace_period: 0,
+            renewal_price: None,
+            edit_byte_price: None,
+            length_prices: vec![],
+            watch_deposit: None,
+            release_cooldown: 0,
+            fee_collector: None,
+            revenue_split: vec![],
+            fee_calculator: None,
+            ibc_channels: vec![],
+            hold_deposit: None,
+            hold_duration: 0,
+            name_policy: NamePolicy::default(),
+            burn_fees: false,
+            referral_percent: 0,
+            premium_multiplier: 0,
+            premium_window: 0,
+            launch_phase: None,
+            allowlist_gate: None,
+            marketplace_fee_percent: 0,
+            royalty_percent: 0,
+            max_names_per_address: None,
+            registration_cooldown_seconds: None,
+            max_renewal_horizon: None,
+        };
+        let registering = |config: &Config, lengths: &[u64]| -> Vec<Vec<Coin>> {
+            lengths.iter().map(|&length| accepted_prices(config, PricedAction::Register { length })).collect()
+        };
+        assert_eq!(batch_prices(&registering(&config, &[5, 6])).unwrap(), vec![coin(10, "token"), coin(4, "earth")]);
+        assert_eq!(batch_prices(&registering(&config, &[])).unwrap(), vec![]);
+
+        // short names are only sold for token, earth can't pay the whole batch
+        config.length_prices = vec![PriceTier { max_length: 3, price: coin(50, "token") }];
+        assert_eq!(batch_prices(&registering(&config, &[3, 5])).unwrap(), coins(55, "token"));
+        config.length_prices = vec![PriceTier { max_length: 3, price: coin(50, "smokin") }];
+        match batch_prices(&registering(&config, &[3, 5])) {
+            Ok(_) => panic!("Must return error"),
+            Err(ContractError::BatchDenomMismatch {}) => {}
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        }
+    }
+
+    #[test]
+    fn edit_price_grows_with_bytes() {
+        let mut config = Config {
+            owner: Addr::unchecked("admin"),
+            purchase_price: vec![],
+            transfer_price: vec![],
+            edit_price: vec![coin(10, "token")],
+            max_supply: None,
+            frozen: false,
+            community_fund: None,
+            min_delegation: None,
+            registration_duration: None,
+            grace_period: 0,
+            renewal_price: None,
+            edit_byte_price: Some(coin(2, "token")),
+            length_prices: vec![],
+            watch_deposit: None,
+            release_cooldown: 0,
+            fee_collector: None,
+            revenue_split: vec![],
+            fee_calculator: None,
+            ibc_channels: vec![],
+            hold_deposit: None,
+            hold_duration: 0,
+            name_policy: NamePolicy::default(),
+            burn_fees: false,
+            referral_percent: 0,
+            premium_multiplier: 0,
+            premium_window: 0,
+            launch_phase: None,
+            allowlist_gate: None,
+            marketplace_fee_percent: 0,
+            royalty_percent: 0,
+            max_names_per_address: None,
+            registration_cooldown_seconds: None,
+            max_renewal_horizon: None,
+        };
+        assert_eq!(price(&config, PricedAction::Edit { bytes: 0 }), Some(coin(10, "token")));
+        assert_eq!(price(&config, PricedAction::Edit { bytes: 7 }), Some(coin(24, "token")));
+
+        config.edit_price = vec![];
+        assert_eq!(price(&config, PricedAction::Edit { bytes: 7 }), Some(coin(14, "token")));
+    }
+
+    #[test]
+    fn short_names_use_their_tier() {
+        let mut config = Config {
+            owner: Addr::unchecked("admin"),
+            purchase_price: vec![coin(5, "token")],
+            transfer_price: vec![],
+            edit_price: vec![],
+            max_supply: None,
+            frozen: false,
+            community_fund: None,
+            min_delegation: None,
+            registration_duration: None,
+            grace_period: 0,
+            renewal_price: None,
+            edit_byte_price: None,
+            length_prices: vec![
+                PriceTier { max_length: 3, price: coin(100, "token") },
+                PriceTier { max_length: 4, price: coin(50, "token") },
+            ],
+            watch_deposit: None,
+            release_cooldown: 0,
+            fee_collector: None,
+            revenue_split: vec![],
+            fee_calculator: None,
+            ibc_channels: vec![],
+            hold_deposit: None,
+            hold_duration: 0,
+            name_policy: NamePolicy::default(),
+            burn_fees: false,
+            referral_percent: 0,
+            premium_multiplier: 0,
+            premium_window: 0,
+            launch_phase: None,
+            allowlist_gate: None,
+            marketplace_fee_percent: 0,
+            royalty_percent: 0,
+            max_names_per_address: None,
+            registration_cooldown_seconds: None,
+            max_renewal_horizon: None,
+        };
+        validate_price_tiers(&config.length_prices).unwrap();
+        assert_eq!(price(&config, PricedAction::Register { length: 3 }), Some(coin(100, "token")));
+        assert_eq!(price(&config, PricedAction::Register { length: 4 }), Some(coin(50, "token")));
+        assert_eq!(price(&config, PricedAction::Register { length: 5 }), Some(coin(5, "token")));
+
+        config.length_prices.reverse();
+        match validate_price_tiers(&config.length_prices) {
+            Ok(_) => panic!("Must return error"),
+            Err(ContractError::InvalidPriceTiers {}) => {}
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        }
+    }
+
+    #[test]
+    fn socials_validate() {
+        let socials = Socials {
+            avatar_url: "ipfs://bafybeigdyrzt".to_string(),
+            twitter: "huahua_name".to_string(),
+            github: "huahua-name".to_string(),
+            discord: "huahua.name".to_string(),
+        };
+        validate_socials(&socials).unwrap();
+        validate_socials(&Socials::default()).unwrap();
+
+        for (socials, field) in [
+            (Socials { avatar_url: "http://avatar.png".to_string(), ..socials.clone() }, "avatar_url"),
+            (Socials { twitter: "@huahua".to_string(), ..socials.clone() }, "twitter"),
+            (Socials { github: "huahua--name".to_string(), ..socials.clone() }, "github"),
+            (Socials { discord: "Huahua".to_string(), ..socials.clone() }, "discord"),
+        ] {
+            match validate_socials(&socials) {
+                Ok(_) => panic!("Must return error"),
+                Err(ContractError::InvalidSocial { field: invalid, .. }) => assert_eq!(invalid, field),
+                Err(e) => panic!("Unexpected error: {:?}", e),
+            }
+        }
+
+        let socials = Socials { twitter: "a".repeat(16), ..Socials::default() };
+        match validate_socials(&socials) {
+            Ok(_) => panic!("Must return error"),
+            Err(ContractError::SocialTooLong { field, length, max_length }) => {
+                assert_eq!((field.as_str(), length, max_length), ("twitter", 16, MAX_TWITTER_LENGTH))
+            }
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        }
+    }
+
+    #[test]
+    fn content_hash_validate() {
+        for value in [
+            "ipfs://QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG",
+            "ipfs://bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi",
+            "ar://bNbA3TEQVL60xlgCcqdz4ZPHFZ711cZ3hmkpGttDt_U",
+        ] {
+            validate_content_hash(value).unwrap();
+        }
+
+        for value in [
+            "QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG",
+            "https://huahua.name",
+            "ipfs://QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbd",
+            "ipfs://QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPb0G",
+            "ipfs://bafyBEIGDYRZT",
+            "ipfs://b",
+            "ar://bNbA3TEQVL60xlgCcqdz4ZPHFZ711cZ3hmkpGttDt",
+        ] {
+            match validate_content_hash(value) {
+                Ok(_) => panic!("Must return error"),
+                Err(ContractError::InvalidContentHash { value: invalid }) => assert_eq!(invalid, value),
+                Err(e) => panic!("Unexpected error: {:?}", e),
+            }
+        }
+
+        let value = format!("ipfs://b{}", "a".repeat(121));
+        match validate_content_hash(&value) {
+            Ok(_) => panic!("Must return error"),
+            Err(ContractError::ContentHashTooLong { length, max_length }) => {
+                assert_eq!((length, max_length), (129, MAX_CONTENT_HASH_LENGTH))
+            }
+            Err(e) => panic!("Unexpected error: {:?}", e),
+        }
+    }
+}